@@ -22,6 +22,19 @@ mod donation_nft {
         Legendary,   // > 1000 DOT
     }
 
+    /// Mirror of the platform's campaign state, used to decode cross-contract replies
+    ///
+    /// Variant order must match the platform's `CampaignState`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum CampaignState {
+        Active,
+        Successful,
+        Failed,
+        Withdrawn,
+        Merged,
+    }
+
     /// Represents metadata for a donation receipt NFT
     #[derive(Debug, Clone, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -39,10 +52,33 @@ mod donation_nft {
         pub donor: AccountId,
         /// Rarity tier based on donation amount
         pub rarity: RarityTier,
+        /// Rarity tier based on the donation's share of the campaign goal
+        /// (None if the goal wasn't passed to the mint)
+        pub relative_tier: Option<RarityTier>,
         /// Number of times this NFT has been transferred
         pub transfer_count: u32,
+        /// Block the receipt was minted in (for provenance)
+        pub block_number: BlockNumber,
+        /// Whether the receipt is bound to its recipient and can never be transferred
+        pub soulbound: bool,
+    }
+
+    /// What an achievement rule measures
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    #[cfg_attr(feature = "std", derive(::ink::storage::traits::StorageLayout))]
+    pub enum AchievementKind {
+        /// Number of receipts held
+        DonationCount,
+        /// Total amount of the receipts held
+        TotalAmount,
+        /// Number of receipts held of the given rarity
+        RarityCount(RarityTier),
     }
 
+    /// Achievement rule: (label, kind, threshold); unlocked when the measured value >= threshold
+    pub type AchievementRule = (String, AchievementKind, u128);
+
     /// NFT Token ID type
     pub type TokenId = u128;
 
@@ -64,6 +100,20 @@ mod donation_nft {
         TransferToSelf,
         /// Caller is not token owner
         NotOwner,
+        /// Achievement rule list is invalid (empty label or too many rules)
+        InvalidAchievementRule,
+        /// Receipt metadata is frozen
+        MetadataFrozen,
+        /// Campaign title is empty or too long
+        InvalidTitle,
+        /// Caller is neither the owner nor an approved operator
+        NotApproved,
+        /// Cannot approve self as operator
+        SelfApprove,
+        /// Rarity thresholds are not strictly increasing
+        InvalidRarityThresholds,
+        /// Token is soulbound and cannot be transferred
+        Soulbound,
     }
 
     /// Storage for the Donation NFT contract
@@ -75,8 +125,12 @@ mod donation_nft {
         token_metadata: Mapping<TokenId, DonationMetadata>,
         /// Mapping from owner to list of owned token IDs
         owned_tokens: Mapping<AccountId, Vec<TokenId>>,
-        /// Total number of tokens minted
+        /// Mapping from owner to the number of soulbound tokens they hold
+        soulbound_balances: Mapping<AccountId, u32>,
+        /// Total number of tokens minted (burning doesn't decrease it)
         total_supply: u128,
+        /// Number of tokens burned
+        burned_count: u128,
         /// Address of the donation platform contract (authorized minter)
         platform_contract: AccountId,
         /// Contract admin
@@ -89,8 +143,48 @@ mod donation_nft {
         transfers_enabled: bool,
         /// Mapping to track total donations by address for leaderboard
         total_donated: Mapping<AccountId, Balance>,
+        /// Campaigns that reached their goal, as reported by the platform
+        funded_campaigns: Mapping<u32, bool>,
+        /// Decimals of the chain's native token, used for rarity and achievement thresholds
+        decimals: u8,
+        /// Cached platform answers: campaign ID -> (funded, checked_at)
+        funded_status_cache: Mapping<u32, (bool, Timestamp)>,
+        /// Achievement rules evaluated by `get_achievements`, in display order
+        achievement_rules: Vec<AchievementRule>,
+        /// Platform contract proposed by the admin, awaiting acceptance
+        pending_platform_contract: Option<AccountId>,
+        /// Whether receipt metadata is permanently frozen
+        metadata_frozen: bool,
+        /// Accounts that opted out of the public leaderboard (absent = visible)
+        leaderboard_hidden: Mapping<AccountId, bool>,
+        /// Single-token approvals: (owner, token ID) -> operator
+        approvals: Mapping<(AccountId, TokenId), AccountId>,
+        /// Operators approved for all of an owner's tokens: (owner, operator) -> approved
+        operator_approvals: Mapping<(AccountId, AccountId), bool>,
+        /// Prefix of off-chain metadata URLs (empty = no token URIs)
+        base_uri: String,
+        /// Minimum amounts for Uncommon, Rare, Epic and Legendary receipts
+        rarity_thresholds: [Balance; 4],
     }
 
+    /// Default token decimals used by `new`
+    const DEFAULT_DECIMALS: u8 = 13;
+
+    /// How long a cached funded status is trusted before asking the platform again (1 minute)
+    const FUNDED_CACHE_TTL: u64 = 60_000;
+
+    /// Maximum number of donors per `get_achievements_batch` call
+    const MAX_ACHIEVEMENTS_BATCH: usize = 50;
+
+    /// Maximum number of achievement rules
+    const MAX_ACHIEVEMENT_RULES: usize = 50;
+
+    /// Maximum campaign title length (matches the platform's limit)
+    const MAX_TITLE_LENGTH: usize = 100;
+
+    /// Maximum number of tokens moved per `transfer_all` call
+    const MAX_TRANSFER_ALL_BATCH: usize = 50;
+
     impl DonationNft {
         /// Creates a new Donation NFT contract
         #[ink(constructor)]
@@ -99,10 +193,23 @@ mod donation_nft {
             collection_name: String,
             collection_symbol: String,
         ) -> Self {
-            Self {
+            Self::new_with_decimals(platform_contract, collection_name, collection_symbol, DEFAULT_DECIMALS)
+        }
+
+        /// Creates a new Donation NFT contract for a chain whose native token
+        /// uses `decimals` decimals (e.g. 10 on Polkadot)
+        #[ink(constructor)]
+        pub fn new_with_decimals(
+            platform_contract: AccountId,
+            collection_name: String,
+            collection_symbol: String,
+            decimals: u8,
+        ) -> Self {
+            let mut contract = Self {
                 token_owners: Mapping::default(),
                 token_metadata: Mapping::default(),
                 owned_tokens: Mapping::default(),
+                soulbound_balances: Mapping::default(),
                 total_supply: 0,
                 platform_contract,
                 admin: Self::env().caller(),
@@ -110,26 +217,97 @@ mod donation_nft {
                 collection_symbol,
                 transfers_enabled: true,
                 total_donated: Mapping::default(),
-            }
+                funded_campaigns: Mapping::default(),
+                decimals,
+                funded_status_cache: Mapping::default(),
+                achievement_rules: Vec::new(),
+                pending_platform_contract: None,
+                metadata_frozen: false,
+                leaderboard_hidden: Mapping::default(),
+                approvals: Mapping::default(),
+                operator_approvals: Mapping::default(),
+                burned_count: 0,
+                base_uri: String::new(),
+                rarity_thresholds: [0; 4],
+            };
+            let one_dot = contract.one_dot();
+            contract.achievement_rules = Self::default_achievement_rules(one_dot);
+            contract.rarity_thresholds = [
+                one_dot,
+                one_dot.saturating_mul(10),
+                one_dot.saturating_mul(100),
+                one_dot.saturating_mul(1000),
+            ];
+            contract
+        }
+
+        /// The built-in achievements
+        fn default_achievement_rules(one_dot: Balance) -> Vec<AchievementRule> {
+            use AchievementKind::*;
+            ink::prelude::vec![
+                // Donation count achievements
+                (String::from("First Donation"), DonationCount, 1),
+                (String::from("Generous Giver"), DonationCount, 5),
+                (String::from("Philanthropist"), DonationCount, 10),
+                (String::from("Champion Donor"), DonationCount, 25),
+                (String::from("Legendary Supporter"), DonationCount, 50),
+                // Amount achievements
+                (String::from("DOT Donor"), TotalAmount, one_dot),
+                (String::from("Big Spender"), TotalAmount, one_dot.saturating_mul(10)),
+                (String::from("Whale"), TotalAmount, one_dot.saturating_mul(100)),
+                (String::from("Mega Whale"), TotalAmount, one_dot.saturating_mul(1000)),
+                // Rarity achievements
+                (String::from("Legendary Collector"), RarityCount(RarityTier::Legendary), 1),
+                (String::from("Epic Collection"), RarityCount(RarityTier::Epic), 3),
+                (String::from("Rare Collector"), RarityCount(RarityTier::Rare), 5),
+                (String::from("NFT Enthusiast"), DonationCount, 10),
+            ]
+        }
+
+        /// One whole token in the smallest unit (10^decimals)
+        fn one_dot(&self) -> Balance {
+            Balance::from(10u8).saturating_pow(u32::from(self.decimals))
         }
 
         /// Helper function to determine rarity tier based on donation amount
-        fn get_rarity_tier(amount: Balance) -> RarityTier {
-            const ONE_DOT: Balance = 10_000_000_000_000; // 10^13 (assuming 13 decimals)
-            
-            if amount >= ONE_DOT * 1000 {
+        fn get_rarity_tier(&self, amount: Balance) -> RarityTier {
+            let [uncommon, rare, epic, legendary] = self.rarity_thresholds;
+
+            if amount >= legendary {
                 RarityTier::Legendary
-            } else if amount >= ONE_DOT * 100 {
+            } else if amount >= epic {
                 RarityTier::Epic
-            } else if amount >= ONE_DOT * 10 {
+            } else if amount >= rare {
                 RarityTier::Rare
-            } else if amount >= ONE_DOT {
+            } else if amount >= uncommon {
                 RarityTier::Uncommon
             } else {
                 RarityTier::Common
             }
         }
 
+        /// Helper function to determine rarity tier based on the donation's share of the goal
+        /// (Legendary >= 25%, Epic >= 10%, Rare >= 5%, Uncommon >= 1%)
+        fn get_relative_tier(amount: Balance, campaign_goal: Balance) -> Option<RarityTier> {
+            if campaign_goal == 0 {
+                return None;
+            }
+
+            let share_bps = amount.saturating_mul(10_000) / campaign_goal;
+
+            Some(if share_bps >= 2_500 {
+                RarityTier::Legendary
+            } else if share_bps >= 1_000 {
+                RarityTier::Epic
+            } else if share_bps >= 500 {
+                RarityTier::Rare
+            } else if share_bps >= 100 {
+                RarityTier::Uncommon
+            } else {
+                RarityTier::Common
+            })
+        }
+
         /// Mints a new donation receipt NFT
         /// Can only be called by the authorized platform contract
         #[ink(message)]
@@ -140,6 +318,21 @@ mod donation_nft {
             campaign_title: String,
             amount: Balance,
             timestamp: Timestamp,
+        ) -> Result<TokenId, Error> {
+            self.mint_donation_receipt_with_goal(to, campaign_id, campaign_title, amount, timestamp, 0)
+        }
+
+        /// Mints a new donation receipt NFT, also rating it against the campaign goal
+        /// Can only be called by the authorized platform contract
+        #[ink(message)]
+        pub fn mint_donation_receipt_with_goal(
+            &mut self,
+            to: AccountId,
+            campaign_id: u32,
+            campaign_title: String,
+            amount: Balance,
+            timestamp: Timestamp,
+            campaign_goal: Balance,
         ) -> Result<TokenId, Error> {
             let caller = self.env().caller();
             
@@ -164,8 +357,11 @@ mod donation_nft {
                 amount,
                 timestamp,
                 donor: to,
-                rarity: Self::get_rarity_tier(amount),
+                rarity: self.get_rarity_tier(amount),
+                relative_tier: Self::get_relative_tier(amount, campaign_goal),
                 transfer_count: 0,
+                block_number: self.env().block_number(),
+                soulbound: false,
             };
 
             // Update total donated amount for donor
@@ -198,6 +394,42 @@ mod donation_nft {
             Ok(token_id)
         }
 
+        /// Mints a non-transferable donation receipt, as proof of giving
+        /// Can only be called by the authorized platform contract
+        #[ink(message)]
+        pub fn mint_soulbound_receipt(
+            &mut self,
+            to: AccountId,
+            campaign_id: u32,
+            campaign_title: String,
+            amount: Balance,
+            timestamp: Timestamp,
+            campaign_goal: Balance,
+        ) -> Result<TokenId, Error> {
+            let token_id = self.mint_donation_receipt_with_goal(to, campaign_id, campaign_title, amount, timestamp, campaign_goal)?;
+
+            if let Some(mut metadata) = self.token_metadata.get(token_id) {
+                metadata.soulbound = true;
+                self.token_metadata.insert(token_id, &metadata);
+            }
+            let held = self.soulbound_balances.get(to).unwrap_or(0);
+            self.soulbound_balances.insert(to, &held.saturating_add(1));
+
+            Ok(token_id)
+        }
+
+        /// Check if a token is soulbound (false for unknown tokens)
+        #[ink(message)]
+        pub fn is_soulbound(&self, token_id: TokenId) -> bool {
+            self.token_metadata.get(token_id).is_some_and(|metadata| metadata.soulbound)
+        }
+
+        /// Gets the number of soulbound tokens an account holds
+        #[ink(message)]
+        pub fn soulbound_balance_of(&self, owner: AccountId) -> u32 {
+            self.soulbound_balances.get(owner).unwrap_or(0)
+        }
+
         /// Gets the owner of a token
         #[ink(message)]
         pub fn owner_of(&self, token_id: TokenId) -> Option<AccountId> {
@@ -210,6 +442,14 @@ mod donation_nft {
             self.token_metadata.get(token_id)
         }
 
+        /// Gets the absolute and goal-relative rarity of a donation NFT
+        #[ink(message)]
+        pub fn get_token_rarity(&self, token_id: TokenId) -> Option<(RarityTier, Option<RarityTier>)> {
+            self.token_metadata
+                .get(token_id)
+                .map(|metadata| (metadata.rarity, metadata.relative_tier))
+        }
+
         /// Gets all tokens owned by an account
         #[ink(message)]
         pub fn tokens_of_owner(&self, owner: AccountId) -> Vec<TokenId> {
@@ -249,16 +489,104 @@ mod donation_nft {
             self.collection_symbol.clone()
         }
 
-        /// Updates the platform contract address (admin only)
+        /// Sets the prefix of token metadata URLs, e.g. `ipfs://<cid>/` (admin only)
+        /// An empty string disables token URIs
+        #[ink(message)]
+        pub fn set_base_uri(&mut self, base_uri: String) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAuthorized);
+            }
+            self.base_uri = base_uri;
+            Ok(())
+        }
+
+        /// Gets the prefix of token metadata URLs
+        #[ink(message)]
+        pub fn base_uri(&self) -> String {
+            self.base_uri.clone()
+        }
+
+        /// Gets the metadata URL of a token: the base URI followed by the token ID
+        /// Returns None if no base URI is set or the token doesn't exist
+        #[ink(message)]
+        pub fn token_uri(&self, token_id: TokenId) -> Option<String> {
+            if self.base_uri.is_empty() || !self.token_owners.contains(token_id) {
+                return None;
+            }
+            Some(ink::prelude::format!("{}{}", self.base_uri, token_id))
+        }
+
+        /// Gets the token decimals used for rarity thresholds
+        #[ink(message)]
+        pub fn get_decimals(&self) -> u8 {
+            self.decimals
+        }
+
+        /// Gets the minimum amounts for Uncommon, Rare, Epic and Legendary receipts
+        #[ink(message)]
+        pub fn get_rarity_thresholds(&self) -> [Balance; 4] {
+            self.rarity_thresholds
+        }
+
+        /// Replaces the rarity thresholds (admin only)
+        ///
+        /// Only affects future mints; existing receipts keep the rarity they were minted with.
+        #[ink(message)]
+        pub fn set_rarity_thresholds(&mut self, thresholds: [Balance; 4]) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAuthorized);
+            }
+            if thresholds.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return Err(Error::InvalidRarityThresholds);
+            }
+            self.rarity_thresholds = thresholds;
+            Ok(())
+        }
+
+        /// Proposes a new platform contract address (admin only)
+        ///
+        /// The change only takes effect once `accept_platform_contract` is called, so a
+        /// mistyped address can't take over minting. Proposing again replaces the
+        /// pending address.
         #[ink(message)]
-        pub fn set_platform_contract(&mut self, new_contract: AccountId) -> Result<(), Error> {
+        pub fn propose_platform_contract(&mut self, new_contract: AccountId) -> Result<(), Error> {
             if self.env().caller() != self.admin {
                 return Err(Error::NotAuthorized);
             }
+            if new_contract == AccountId::from([0u8; 32]) {
+                return Err(Error::ZeroAddress);
+            }
+            self.pending_platform_contract = Some(new_contract);
+            Ok(())
+        }
+
+        /// Accepts the proposed platform contract (proposed contract or admin)
+        #[ink(message)]
+        pub fn accept_platform_contract(&mut self) -> Result<(), Error> {
+            let new_contract = self.pending_platform_contract.ok_or(Error::NotAuthorized)?;
+            let caller = self.env().caller();
+            if caller != new_contract && caller != self.admin {
+                return Err(Error::NotAuthorized);
+            }
+
+            let old_contract = self.platform_contract;
             self.platform_contract = new_contract;
+            self.pending_platform_contract = None;
+
+            self.env().emit_event(PlatformContractChanged {
+                old_contract,
+                new_contract,
+            });
+
             Ok(())
         }
 
+        /// Gets the proposed platform contract address, if any
+        #[ink(message)]
+        pub fn get_pending_platform_contract(&self) -> Option<AccountId> {
+            self.pending_platform_contract
+        }
+
         /// Gets the platform contract address
         #[ink(message)]
         pub fn get_platform_contract(&self) -> AccountId {
@@ -308,6 +636,30 @@ mod donation_nft {
             result
         }
 
+        /// Gets tokens across the whole collection, for explorers crawling it page by page
+        ///
+        /// Walks token IDs upwards from `offset`, skipping burned ones (no owner or
+        /// metadata), and stops after `limit` results or at the end of the supply. The
+        /// next page starts at the last returned token ID plus one.
+        #[ink(message)]
+        pub fn get_all_tokens(&self, offset: TokenId, limit: u32) -> Vec<(TokenId, DonationMetadata)> {
+            let mut result = Vec::new();
+
+            for token_id in offset..self.total_supply {
+                if result.len() >= limit as usize {
+                    break;
+                }
+                if self.token_owners.get(token_id).is_none() {
+                    continue;
+                }
+                if let Some(metadata) = self.token_metadata.get(token_id) {
+                    result.push((token_id, metadata));
+                }
+            }
+
+            result
+        }
+
         /// Transfer an NFT to another address
         #[ink(message)]
         pub fn transfer(&mut self, to: AccountId, token_id: TokenId) -> Result<(), Error> {
@@ -334,102 +686,360 @@ mod donation_nft {
                 return Err(Error::ZeroAddress);
             }
 
-            // Remove token from sender's list
-            let mut from_tokens = self.owned_tokens.get(caller).unwrap_or_default();
-            from_tokens.retain(|&id| id != token_id);
-            self.owned_tokens.insert(caller, &from_tokens);
+            self.move_token(caller, to, token_id)
+        }
+
+        /// Transfer the caller's NFTs to another address, e.g. when moving to a new wallet
+        ///
+        /// Checks at most `MAX_TRANSFER_ALL_BATCH` tokens per call, oldest first, and moves
+        /// them with the same rules as `transfer`. Soulbound tokens stay with the caller,
+        /// moved to the end of their list so later calls reach them last. Returns how many
+        /// transferable tokens the caller still holds; call again until it returns 0.
+        #[ink(message)]
+        pub fn transfer_all(&mut self, to: AccountId) -> Result<u32, Error> {
+            let caller = self.env().caller();
+
+            // Check if transfers are enabled
+            if !self.transfers_enabled && caller != self.admin {
+                return Err(Error::NotAuthorized);
+            }
+
+            // Cannot transfer to self
+            if to == caller {
+                return Err(Error::TransferToSelf);
+            }
+
+            // Cannot transfer to zero address
+            if to == AccountId::from([0u8; 32]) {
+                return Err(Error::ZeroAddress);
+            }
+
+            // Only a batch of tokens has its metadata read per call
+            let from_tokens = self.owned_tokens.get(caller).unwrap_or_default();
+            let mut moved = Vec::new();
+            let mut soulbound = Vec::new();
+            let mut scanned = 0;
+            for &token_id in from_tokens.iter().take(MAX_TRANSFER_ALL_BATCH) {
+                scanned += 1;
+                if self.is_soulbound(token_id) {
+                    soulbound.push(token_id);
+                } else {
+                    moved.push(token_id);
+                }
+            }
+
+            // Update both token lists once rather than per token
+            let mut kept = from_tokens[scanned..].to_vec();
+            kept.extend_from_slice(&soulbound);
+            self.owned_tokens.insert(caller, &kept);
 
-            // Add token to recipient's list
             let mut to_tokens = self.owned_tokens.get(to).unwrap_or_default();
-            to_tokens.push(token_id);
+            to_tokens.extend_from_slice(&moved);
             self.owned_tokens.insert(to, &to_tokens);
 
-            // Update owner
-            self.token_owners.insert(token_id, &to);
+            for token_id in moved {
+                self.reassign_token(caller, to, token_id);
+            }
 
-            // Update transfer count in metadata
-            if let Some(mut metadata) = self.token_metadata.get(token_id) {
-                metadata.transfer_count = metadata.transfer_count.saturating_add(1);
-                self.token_metadata.insert(token_id, &metadata);
+            // Soulbound tokens never move, so they don't count as remaining
+            #[allow(clippy::cast_possible_truncation)]
+            Ok((kept.len() as u32).saturating_sub(self.soulbound_balance_of(caller)))
+        }
+
+        /// Approve or revoke an operator for one of the caller's tokens, or for all of
+        /// them when `token_id` is None (PSP34 `approve`)
+        #[ink(message)]
+        pub fn approve(&mut self, operator: AccountId, token_id: Option<TokenId>, approved: bool) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            if operator == caller {
+                return Err(Error::SelfApprove);
             }
 
-            // Emit event
-            self.env().emit_event(Transfer {
-                from: Some(caller),
-                to: Some(to),
+            match token_id {
+                Some(token_id) => {
+                    let owner = self.token_owners.get(token_id).ok_or(Error::TokenNotFound)?;
+                    if owner != caller {
+                        return Err(Error::NotOwner);
+                    }
+                    if approved {
+                        self.approvals.insert((caller, token_id), &operator);
+                    } else {
+                        self.approvals.remove((caller, token_id));
+                    }
+                }
+                None => {
+                    if approved {
+                        self.operator_approvals.insert((caller, operator), &true);
+                    } else {
+                        self.operator_approvals.remove((caller, operator));
+                    }
+                }
+            }
+
+            self.env().emit_event(Approval {
+                owner: caller,
+                operator,
                 token_id,
+                approved,
             });
 
             Ok(())
         }
 
-        /// Enable or disable NFT transfers (admin only)
+        /// Check if an operator may transfer an owner's token, or all of their tokens
+        /// when `token_id` is None (PSP34 `allowance`)
         #[ink(message)]
-        pub fn set_transfers_enabled(&mut self, enabled: bool) -> Result<(), Error> {
-            if self.env().caller() != self.admin {
-                return Err(Error::NotAuthorized);
+        pub fn allowance(&self, owner: AccountId, operator: AccountId, token_id: Option<TokenId>) -> bool {
+            if self.operator_approvals.get((owner, operator)).unwrap_or(false) {
+                return true;
+            }
+            match token_id {
+                Some(token_id) => self.approvals.get((owner, token_id)) == Some(operator),
+                None => false,
             }
-            self.transfers_enabled = enabled;
-            Ok(())
         }
 
-        /// Check if transfers are enabled
+        /// Transfer an NFT on behalf of its owner, as the owner or an approved operator
         #[ink(message)]
-        pub fn are_transfers_enabled(&self) -> bool {
-            self.transfers_enabled
-        }
+        pub fn transfer_from(&mut self, from: AccountId, to: AccountId, token_id: TokenId) -> Result<(), Error> {
+            let caller = self.env().caller();
 
-        /// Get leaderboard of top donors by total amount donated
-        #[ink(message)]
-        pub fn get_leaderboard(&self, limit: u32) -> Vec<(AccountId, Balance, u32)> {
-            // Note: This is a simplified implementation
-            // In production, you'd want to maintain a sorted list or use off-chain indexing
-            let mut leaderboard = Vec::new();
-            
-            // This will only work well with a limited number of unique donors
-            // For a production system, consider using off-chain indexing
-            for token_id in 0..self.total_supply {
-                if let Some(metadata) = self.token_metadata.get(token_id) {
-                    let donor = metadata.donor;
-                    let total = self.total_donated.get(donor).unwrap_or(0);
-                    
-                    // Check if donor already in leaderboard
-                    if !leaderboard.iter().any(|(addr, _, _)| addr == &donor) {
-                        #[allow(clippy::cast_possible_truncation)]
-                        let token_count = self.owned_tokens.get(donor).unwrap_or_default().len() as u32;
-                        leaderboard.push((donor, total, token_count));
-                    }
-                }
+            // Check if transfers are enabled
+            if !self.transfers_enabled && caller != self.admin {
+                return Err(Error::NotAuthorized);
             }
 
-            // Sort by total amount (descending)
-            leaderboard.sort_by(|a, b| b.1.cmp(&a.1));
-            leaderboard.truncate(limit as usize);
-            
-            leaderboard
-        }
+            // Check token exists and belongs to `from`
+            let owner = self.token_owners.get(token_id).ok_or(Error::TokenNotFound)?;
+            if owner != from {
+                return Err(Error::NotOwner);
+            }
 
-        /// Get NFTs by rarity tier
-        #[ink(message)]
-        pub fn get_nfts_by_rarity(&self, owner: AccountId, rarity: RarityTier) -> Vec<(TokenId, DonationMetadata)> {
-            let token_ids = self.owned_tokens.get(owner).unwrap_or_default();
-            let mut result = Vec::new();
-            
-            for token_id in token_ids {
-                if let Some(metadata) = self.token_metadata.get(token_id) {
-                    if metadata.rarity == rarity {
-                        result.push((token_id, metadata));
-                    }
-                }
+            if caller != from && !self.allowance(from, caller, Some(token_id)) {
+                return Err(Error::NotApproved);
             }
-            
-            result
+
+            // Cannot transfer to self
+            if to == from {
+                return Err(Error::TransferToSelf);
+            }
+
+            // Cannot transfer to zero address
+            if to == AccountId::from([0u8; 32]) {
+                return Err(Error::ZeroAddress);
+            }
+
+            self.move_token(from, to, token_id)
         }
 
-        /// Get rarity distribution for an owner
+        /// Destroy a receipt (owner or admin)
+        /// The donor's leaderboard total is kept, since the donation itself still happened
         #[ink(message)]
-        pub fn get_rarity_distribution(&self, owner: AccountId) -> (u32, u32, u32, u32, u32) {
-            let token_ids = self.owned_tokens.get(owner).unwrap_or_default();
+        pub fn burn(&mut self, token_id: TokenId) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let owner = self.token_owners.get(token_id).ok_or(Error::TokenNotFound)?;
+            if owner != caller && caller != self.admin {
+                return Err(Error::NotOwner);
+            }
+
+            let mut tokens = self.owned_tokens.get(owner).unwrap_or_default();
+            tokens.retain(|&id| id != token_id);
+            self.owned_tokens.insert(owner, &tokens);
+
+            if self.is_soulbound(token_id) {
+                let held = self.soulbound_balances.get(owner).unwrap_or(0);
+                self.soulbound_balances.insert(owner, &held.saturating_sub(1));
+            }
+
+            self.token_owners.remove(token_id);
+            self.token_metadata.remove(token_id);
+            self.approvals.remove((owner, token_id));
+            self.burned_count = self.burned_count.saturating_add(1);
+
+            self.env().emit_event(Transfer {
+                from: Some(owner),
+                to: None,
+                token_id,
+            });
+
+            Ok(())
+        }
+
+        /// Gets the number of tokens in existence (minted minus burned)
+        #[ink(message)]
+        pub fn circulating_supply(&self) -> u128 {
+            self.total_supply.saturating_sub(self.burned_count)
+        }
+
+        /// Gets the number of burned tokens
+        #[ink(message)]
+        pub fn burned_count(&self) -> u128 {
+            self.burned_count
+        }
+
+        /// Move a receipt between accounts on behalf of the platform (platform only)
+        ///
+        /// Lets campaign mechanics reassign receipts (e.g. when a donation is redirected)
+        /// without the owner's signature. The owner check is replaced by checking that
+        /// `from` owns the token; the transfers-enabled rule still applies.
+        #[ink(message)]
+        pub fn platform_transfer(&mut self, from: AccountId, to: AccountId, token_id: TokenId) -> Result<(), Error> {
+            if self.env().caller() != self.platform_contract {
+                return Err(Error::NotAuthorized);
+            }
+            if !self.transfers_enabled {
+                return Err(Error::NotAuthorized);
+            }
+
+            let owner = self.token_owners.get(token_id).ok_or(Error::TokenNotFound)?;
+            if owner != from {
+                return Err(Error::NotOwner);
+            }
+            if to == from {
+                return Err(Error::TransferToSelf);
+            }
+            if to == AccountId::from([0u8; 32]) {
+                return Err(Error::ZeroAddress);
+            }
+
+            self.move_token(from, to, token_id)
+        }
+
+        /// Reassign a token whose transfer has already been validated
+        /// Every transfer path ends here, so soulbound receipts are refused here
+        fn move_token(&mut self, from: AccountId, to: AccountId, token_id: TokenId) -> Result<(), Error> {
+            // Soulbound receipts never move, whoever asks
+            if self.is_soulbound(token_id) {
+                return Err(Error::Soulbound);
+            }
+
+            // Remove token from sender's list
+            let mut from_tokens = self.owned_tokens.get(from).unwrap_or_default();
+            from_tokens.retain(|&id| id != token_id);
+            self.owned_tokens.insert(from, &from_tokens);
+
+            // Add token to recipient's list
+            let mut to_tokens = self.owned_tokens.get(to).unwrap_or_default();
+            to_tokens.push(token_id);
+            self.owned_tokens.insert(to, &to_tokens);
+
+            self.reassign_token(from, to, token_id);
+            Ok(())
+        }
+
+        /// Hand a token to its new owner once both owners' token lists are updated
+        fn reassign_token(&mut self, from: AccountId, to: AccountId, token_id: TokenId) {
+            // A stale operator must not be able to move the token again
+            self.approvals.remove((from, token_id));
+
+            // Update owner
+            self.token_owners.insert(token_id, &to);
+
+            // Update transfer count in metadata
+            if let Some(mut metadata) = self.token_metadata.get(token_id) {
+                metadata.transfer_count = metadata.transfer_count.saturating_add(1);
+                self.token_metadata.insert(token_id, &metadata);
+            }
+
+            // Emit event
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                token_id,
+            });
+        }
+
+        /// Enable or disable NFT transfers (admin only)
+        #[ink(message)]
+        pub fn set_transfers_enabled(&mut self, enabled: bool) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAuthorized);
+            }
+            self.transfers_enabled = enabled;
+            Ok(())
+        }
+
+        /// Check if transfers are enabled
+        #[ink(message)]
+        pub fn are_transfers_enabled(&self) -> bool {
+            self.transfers_enabled
+        }
+
+        /// Show or hide the caller on the public leaderboard (visible by default)
+        /// Their donation totals are still tracked either way
+        #[ink(message)]
+        pub fn set_leaderboard_visible(&mut self, visible: bool) {
+            let caller = self.env().caller();
+            if visible {
+                self.leaderboard_hidden.remove(caller);
+            } else {
+                self.leaderboard_hidden.insert(caller, &true);
+            }
+        }
+
+        /// Check if an account appears on the public leaderboard
+        #[ink(message)]
+        pub fn is_leaderboard_visible(&self, account: AccountId) -> bool {
+            !self.leaderboard_hidden.contains(account)
+        }
+
+        /// Get leaderboard of top donors by total amount donated
+        /// Accounts that opted out via `set_leaderboard_visible` are left out
+        #[ink(message)]
+        pub fn get_leaderboard(&self, limit: u32) -> Vec<(AccountId, Balance, u32)> {
+            // Note: This is a simplified implementation
+            // In production, you'd want to maintain a sorted list or use off-chain indexing
+            let mut leaderboard = Vec::new();
+            
+            // This will only work well with a limited number of unique donors
+            // For a production system, consider using off-chain indexing
+            for token_id in 0..self.total_supply {
+                if let Some(metadata) = self.token_metadata.get(token_id) {
+                    let donor = metadata.donor;
+                    if self.leaderboard_hidden.contains(donor) {
+                        continue;
+                    }
+                    let total = self.total_donated.get(donor).unwrap_or(0);
+                    
+                    // Check if donor already in leaderboard
+                    if !leaderboard.iter().any(|(addr, _, _)| addr == &donor) {
+                        #[allow(clippy::cast_possible_truncation)]
+                        let token_count = self.owned_tokens.get(donor).unwrap_or_default().len() as u32;
+                        leaderboard.push((donor, total, token_count));
+                    }
+                }
+            }
+
+            // Sort by total amount (descending)
+            leaderboard.sort_by(|a, b| b.1.cmp(&a.1));
+            leaderboard.truncate(limit as usize);
+            
+            leaderboard
+        }
+
+        /// Get NFTs by rarity tier
+        #[ink(message)]
+        pub fn get_nfts_by_rarity(&self, owner: AccountId, rarity: RarityTier) -> Vec<(TokenId, DonationMetadata)> {
+            let token_ids = self.owned_tokens.get(owner).unwrap_or_default();
+            let mut result = Vec::new();
+            
+            for token_id in token_ids {
+                if let Some(metadata) = self.token_metadata.get(token_id) {
+                    if metadata.rarity == rarity {
+                        result.push((token_id, metadata));
+                    }
+                }
+            }
+            
+            result
+        }
+
+        /// Get rarity distribution for an owner
+        #[ink(message)]
+        pub fn get_rarity_distribution(&self, owner: AccountId) -> (u32, u32, u32, u32, u32) {
+            let token_ids = self.owned_tokens.get(owner).unwrap_or_default();
             let mut common = 0u32;
             let mut uncommon = 0u32;
             let mut rare = 0u32;
@@ -447,91 +1057,830 @@ mod donation_nft {
                     }
                 }
             }
-            
-            (common, uncommon, rare, epic, legendary)
-        }
+            
+            (common, uncommon, rare, epic, legendary)
+        }
+
+        /// Get total amount donated by an address (original donations only)
+        #[ink(message)]
+        pub fn get_total_donated(&self, donor: AccountId) -> Balance {
+            self.total_donated.get(donor).unwrap_or(0)
+        }
+
+        /// Get the combined donation amount of the receipts an address currently holds
+        /// Unlike `get_total_donated`, this follows receipts when they are transferred
+        #[ink(message)]
+        pub fn get_held_donation_value(&self, owner: AccountId) -> Balance {
+            self.owned_tokens
+                .get(owner)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|token_id| self.token_metadata.get(token_id))
+                .fold(0, |total: Balance, metadata| total.saturating_add(metadata.amount))
+        }
+
+        /// Get achievement status for a donor
+        #[ink(message)]
+        pub fn get_achievements(&self, donor: AccountId) -> Vec<String> {
+            let (donation_count, total_amount) = self.get_donation_stats(donor);
+            let (common, uncommon, rare, epic, legendary) = self.get_rarity_distribution(donor);
+
+            self.achievement_rules
+                .iter()
+                .filter(|(_, kind, threshold)| {
+                    let value = match kind {
+                        AchievementKind::DonationCount => u128::from(donation_count),
+                        AchievementKind::TotalAmount => total_amount,
+                        AchievementKind::RarityCount(tier) => u128::from(match tier {
+                            RarityTier::Common => common,
+                            RarityTier::Uncommon => uncommon,
+                            RarityTier::Rare => rare,
+                            RarityTier::Epic => epic,
+                            RarityTier::Legendary => legendary,
+                        }),
+                    };
+                    value >= *threshold
+                })
+                .map(|(label, _, _)| label.clone())
+                .collect()
+        }
+
+        /// Gets the achievement rules evaluated by `get_achievements`
+        #[ink(message)]
+        pub fn get_achievement_rules(&self) -> Vec<AchievementRule> {
+            self.achievement_rules.clone()
+        }
+
+        /// Replaces the achievement rules (admin only)
+        ///
+        /// Lets the platform add seasonal badges without a redeploy. Rules are evaluated
+        /// in order and every label must be non-empty.
+        #[ink(message)]
+        pub fn set_achievement_rules(&mut self, rules: Vec<AchievementRule>) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAuthorized);
+            }
+            if rules.len() > MAX_ACHIEVEMENT_RULES || rules.iter().any(|(label, _, _)| label.is_empty()) {
+                return Err(Error::InvalidAchievementRule);
+            }
+            self.achievement_rules = rules;
+            Ok(())
+        }
+
+        /// Appends an achievement rule (admin only)
+        #[ink(message)]
+        pub fn add_achievement_rule(&mut self, label: String, kind: AchievementKind, threshold: u128) -> Result<(), Error> {
+            let mut rules = self.achievement_rules.clone();
+            rules.push((label, kind, threshold));
+            self.set_achievement_rules(rules)
+        }
+
+        /// Get achievements for several donors in one call (e.g. a leaderboard page)
+        ///
+        /// Only the first `MAX_ACHIEVEMENTS_BATCH` donors are processed.
+        #[ink(message)]
+        pub fn get_achievements_batch(&self, donors: Vec<AccountId>) -> Vec<(AccountId, Vec<String>)> {
+            donors
+                .into_iter()
+                .take(MAX_ACHIEVEMENTS_BATCH)
+                .map(|donor| (donor, self.get_achievements(donor)))
+                .collect()
+        }
+
+        /// Marks a campaign as funded (platform or admin only)
+        ///
+        /// Called by the platform when a campaign reaches its goal. Receipts read the flag
+        /// lazily, so no token is touched here.
+        #[ink(message)]
+        pub fn mark_campaign_funded(&mut self, campaign_id: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if caller != self.platform_contract && caller != self.admin {
+                return Err(Error::NotAuthorized);
+            }
+            self.funded_campaigns.insert(campaign_id, &true);
+            Ok(())
+        }
+
+        /// Check whether a campaign has been marked as funded
+        #[ink(message)]
+        pub fn is_campaign_funded(&self, campaign_id: u32) -> bool {
+            self.funded_campaigns.get(campaign_id).unwrap_or(false)
+        }
+
+        /// Check whether a receipt's campaign was funded ("Funded" badge)
+        #[ink(message)]
+        pub fn is_receipt_funded(&self, token_id: TokenId) -> bool {
+            self.token_metadata
+                .get(token_id)
+                .map(|metadata| self.is_campaign_funded(metadata.campaign_id))
+                .unwrap_or(false)
+        }
+
+        /// Check whether a receipt's campaign was funded by asking the platform
+        ///
+        /// Unlike `is_receipt_funded`, this does not rely on the platform pushing
+        /// `mark_campaign_funded`: it queries the campaign's state and caches the answer
+        /// for `FUNDED_CACHE_TTL`. A campaign counts as funded once it is `Successful`
+        /// or its funds were `Withdrawn`. Returns `None` for unknown tokens or when the
+        /// platform can't be reached.
+        #[ink(message)]
+        pub fn is_receipt_campaign_funded(&mut self, token_id: TokenId) -> Option<bool> {
+            let campaign_id = self.token_metadata.get(token_id)?.campaign_id;
+            if self.is_campaign_funded(campaign_id) {
+                return Some(true);
+            }
+
+            let now = self.env().block_timestamp();
+            if let Some((funded, checked_at)) = self.funded_status_cache.get(campaign_id) {
+                if now.saturating_sub(checked_at) < FUNDED_CACHE_TTL {
+                    return Some(funded);
+                }
+            }
+
+            let state = self.query_campaign_state(self.platform_contract, campaign_id)?;
+            let funded = matches!(state, CampaignState::Successful | CampaignState::Withdrawn);
+            self.funded_status_cache.insert(campaign_id, &(funded, now));
+            Some(funded)
+        }
+
+        /// Calls `get_campaign_state` on the platform contract
+        #[cfg(not(test))]
+        fn query_campaign_state(&self, platform: AccountId, campaign_id: u32) -> Option<CampaignState> {
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+
+            build_call::<ink::env::DefaultEnvironment>()
+                .call_v1(platform)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("get_campaign_state")))
+                        .push_arg(campaign_id)
+                )
+                .returns::<Option<CampaignState>>()
+                .try_invoke()
+                .ok()
+                .and_then(|result| result.ok())
+                .flatten()
+        }
+
+        /// The off-chain test environment can't call other contracts, so tests use a mock
+        #[cfg(test)]
+        fn query_campaign_state(&self, platform: AccountId, campaign_id: u32) -> Option<CampaignState> {
+            tests::mock_get_campaign_state(platform, campaign_id)
+        }
+
+        /// Rebuilds `owned_tokens` for the owners of tokens in `[offset, offset + limit)` (admin only)
+        ///
+        /// `token_owners` is treated as the source of truth: each affected owner's list drops
+        /// tokens they no longer own and gains tokens from the range that were missing.
+        /// Call repeatedly with increasing offsets to cover the whole supply.
+        /// Returns the number of owner lists that were rewritten.
+        #[ink(message)]
+        pub fn reconstruct_owned_tokens(&mut self, offset: TokenId, limit: TokenId) -> Result<u32, Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAuthorized);
+            }
+
+            let end = offset.saturating_add(limit).min(self.total_supply);
+            let mut owners: Vec<AccountId> = Vec::new();
+            for token_id in offset..end {
+                if let Some(owner) = self.token_owners.get(token_id) {
+                    if !owners.contains(&owner) {
+                        owners.push(owner);
+                    }
+                }
+            }
+
+            let mut repaired = 0u32;
+            for owner in owners {
+                let current = self.owned_tokens.get(owner).unwrap_or_default();
+
+                let mut rebuilt: Vec<TokenId> = current
+                    .iter()
+                    .copied()
+                    .filter(|&id| self.token_owners.get(id) == Some(owner))
+                    .collect();
+                rebuilt.dedup();
+                for token_id in offset..end {
+                    if self.token_owners.get(token_id) == Some(owner) && !rebuilt.contains(&token_id) {
+                        rebuilt.push(token_id);
+                    }
+                }
+
+                if rebuilt != current {
+                    self.owned_tokens.insert(owner, &rebuilt);
+                    repaired = repaired.saturating_add(1);
+                }
+            }
+
+            Ok(repaired)
+        }
+
+        /// Corrects the campaign title on a campaign's receipts in `[offset, offset + limit)` (platform or admin)
+        ///
+        /// Call repeatedly with increasing offsets to cover the whole supply.
+        /// Returns the number of receipts that were updated.
+        #[ink(message)]
+        pub fn update_campaign_title_on_receipts(
+            &mut self,
+            campaign_id: u32,
+            new_title: String,
+            offset: TokenId,
+            limit: TokenId,
+        ) -> Result<u32, Error> {
+            let caller = self.env().caller();
+            if caller != self.platform_contract && caller != self.admin {
+                return Err(Error::NotAuthorized);
+            }
+            if self.metadata_frozen {
+                return Err(Error::MetadataFrozen);
+            }
+            if new_title.is_empty() || new_title.len() > MAX_TITLE_LENGTH {
+                return Err(Error::InvalidTitle);
+            }
+
+            let end = offset.saturating_add(limit).min(self.total_supply);
+            let mut updated = 0u32;
+            for token_id in offset..end {
+                if let Some(mut metadata) = self.token_metadata.get(token_id) {
+                    if metadata.campaign_id == campaign_id && metadata.campaign_title != new_title {
+                        metadata.campaign_title = new_title.clone();
+                        self.token_metadata.insert(token_id, &metadata);
+                        updated = updated.saturating_add(1);
+                    }
+                }
+            }
+
+            Ok(updated)
+        }
+
+        /// Permanently freezes receipt metadata (admin only)
+        #[ink(message)]
+        pub fn freeze_metadata(&mut self) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAuthorized);
+            }
+            self.metadata_frozen = true;
+            Ok(())
+        }
+
+        /// Whether receipt metadata is frozen
+        #[ink(message)]
+        pub fn is_metadata_frozen(&self) -> bool {
+            self.metadata_frozen
+        }
+
+        /// Overwrites an owner's token list, to simulate a desync in tests
+        #[cfg(test)]
+        fn set_owned_tokens(&mut self, owner: AccountId, tokens: Vec<TokenId>) {
+            self.owned_tokens.insert(owner, &tokens);
+        }
+    }
+
+    /// Event emitted when a token is transferred
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        token_id: TokenId,
+    }
+
+    /// Event emitted when an operator is approved or revoked (mirrors PSP34)
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        /// The token, or None for all of the owner's tokens
+        #[ink(topic)]
+        token_id: Option<TokenId>,
+        approved: bool,
+    }
+
+    /// Event emitted when a donation NFT is minted
+    #[ink(event)]
+    pub struct DonationNftMinted {
+        #[ink(topic)]
+        token_id: TokenId,
+        #[ink(topic)]
+        owner: AccountId,
+        campaign_id: u32,
+        amount: Balance,
+    }
+
+    /// Event emitted when a new platform contract is accepted
+    #[ink(event)]
+    pub struct PlatformContractChanged {
+        #[ink(topic)]
+        old_contract: AccountId,
+        #[ink(topic)]
+        new_contract: AccountId,
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::cell::RefCell;
+
+        thread_local! {
+            /// (platform, campaign_id, state) entries known to the mock platform
+            static MOCK_CAMPAIGN_STATES: RefCell<Vec<(AccountId, u32, CampaignState)>> = const { RefCell::new(Vec::new()) };
+        }
+
+        /// Mock of the platform's `get_campaign_state`; unknown platforms are unreachable
+        pub(super) fn mock_get_campaign_state(platform: AccountId, campaign_id: u32) -> Option<CampaignState> {
+            MOCK_CAMPAIGN_STATES.with(|states| {
+                states
+                    .borrow()
+                    .iter()
+                    .find(|(p, id, _)| *p == platform && *id == campaign_id)
+                    .map(|(_, _, state)| *state)
+            })
+        }
+
+        fn set_mock_campaign_state(platform: AccountId, campaign_id: u32, state: CampaignState) {
+            MOCK_CAMPAIGN_STATES.with(|states| {
+                let mut states = states.borrow_mut();
+                states.retain(|(p, id, _)| !(*p == platform && *id == campaign_id));
+                states.push((platform, campaign_id, state));
+            });
+        }
+
+        #[ink::test]
+        fn new_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let nft = DonationNft::new(
+                accounts.bob,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+            assert_eq!(nft.total_supply(), 0);
+            assert_eq!(nft.collection_name(), "DotNation Receipt");
+            assert_eq!(nft.collection_symbol(), "DNFT");
+        }
+
+        #[ink::test]
+        fn mint_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            // Set caller to admin
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            let result = nft.mint_donation_receipt(
+                accounts.bob,
+                1,
+                String::from("Save the Forest"),
+                1000000,
+                12345678,
+            );
+
+            assert!(result.is_ok());
+            assert_eq!(nft.total_supply(), 1);
+            assert_eq!(nft.owner_of(0), Some(accounts.bob));
+        }
+
+        #[ink::test]
+        fn unauthorized_mint_fails() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            // Try to mint as unauthorized user
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+
+            let result = nft.mint_donation_receipt(
+                accounts.bob,
+                1,
+                String::from("Save the Forest"),
+                1000000,
+                12345678,
+            );
+
+            assert_eq!(result, Err(Error::NotAuthorized));
+        }
+
+        #[ink::test]
+        fn tokens_of_owner_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            // Mint 3 tokens to bob
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("Campaign 1"), 1000, 100).unwrap();
+            nft.mint_donation_receipt(accounts.bob, 2, String::from("Campaign 2"), 2000, 200).unwrap();
+            nft.mint_donation_receipt(accounts.bob, 3, String::from("Campaign 3"), 3000, 300).unwrap();
+
+            let tokens = nft.tokens_of_owner(accounts.bob);
+            assert_eq!(tokens.len(), 3);
+        }
+
+        #[ink::test]
+        fn donation_stats_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("Campaign 1"), 1000, 100).unwrap();
+            nft.mint_donation_receipt(accounts.bob, 2, String::from("Campaign 2"), 2000, 200).unwrap();
+
+            let (count, total) = nft.get_donation_stats(accounts.bob);
+            assert_eq!(count, 2);
+            assert_eq!(total, 3000);
+        }
+
+        #[ink::test]
+        fn transfer_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            // Mint token to bob
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("Campaign 1"), 1000, 100).unwrap();
+            assert_eq!(nft.owner_of(0), Some(accounts.bob));
+
+            // Transfer from bob to charlie
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let result = nft.transfer(accounts.charlie, 0);
+            assert!(result.is_ok());
+            assert_eq!(nft.owner_of(0), Some(accounts.charlie));
+            
+            // Check transfer count increased
+            let metadata = nft.get_token_metadata(0).unwrap();
+            assert_eq!(metadata.transfer_count, 1);
+        }
+
+        #[ink::test]
+        fn rarity_tier_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            const ONE_DOT: Balance = 10_000_000_000_000;
+
+            // Test different rarity tiers
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), ONE_DOT / 2, 100).unwrap();
+            nft.mint_donation_receipt(accounts.bob, 2, String::from("C2"), ONE_DOT * 5, 200).unwrap();
+            nft.mint_donation_receipt(accounts.bob, 3, String::from("C3"), ONE_DOT * 50, 300).unwrap();
+
+            let metadata0 = nft.get_token_metadata(0).unwrap();
+            let metadata1 = nft.get_token_metadata(1).unwrap();
+            let metadata2 = nft.get_token_metadata(2).unwrap();
+
+            assert_eq!(metadata0.rarity, RarityTier::Common);
+            assert_eq!(metadata1.rarity, RarityTier::Uncommon);
+            assert_eq!(metadata2.rarity, RarityTier::Rare);
+        }
+
+        #[ink::test]
+        fn achievements_work() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            // Mint 5 NFTs
+            for i in 0..5 {
+                nft.mint_donation_receipt(accounts.bob, i, String::from("Campaign"), 1000, 100).unwrap();
+            }
+
+            let achievements = nft.get_achievements(accounts.bob);
+            assert!(achievements.len() >= 2); // Should have "First Donation" and "Generous Giver"
+        }
+
+        #[ink::test]
+        fn reconstruct_owned_tokens_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            for to in [accounts.bob, accounts.charlie, accounts.bob] {
+                nft.mint_donation_receipt(to, 1, String::from("Save the Forest"), 1000000, 12345678).unwrap();
+            }
+
+            // Corrupt the lists: Bob lost token 2, Charlie claims Bob's token 0
+            nft.set_owned_tokens(accounts.bob, vec![0]);
+            nft.set_owned_tokens(accounts.charlie, vec![1, 0]);
+
+            // Only admin can repair
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(nft.reconstruct_owned_tokens(0, 10), Err(Error::NotAuthorized));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            // Resumable in chunks
+            assert_eq!(nft.reconstruct_owned_tokens(0, 2), Ok(1));
+            assert_eq!(nft.tokens_of_owner(accounts.charlie), vec![1]);
+            assert_eq!(nft.reconstruct_owned_tokens(2, 2), Ok(1));
+            assert_eq!(nft.tokens_of_owner(accounts.bob), vec![0, 2]);
+
+            // Nothing left to fix
+            assert_eq!(nft.reconstruct_owned_tokens(0, 10), Ok(0));
+        }
+
+        #[ink::test]
+        fn receipt_reflects_funded_campaign() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            let funded = nft.mint_donation_receipt(accounts.bob, 1, String::from("Save the Forest"), 1000000, 12345678).unwrap();
+            let other = nft.mint_donation_receipt(accounts.bob, 2, String::from("Clean Water"), 1000000, 12345678).unwrap();
+            assert!(!nft.is_receipt_funded(funded));
+
+            // Only the platform (or admin) can report success
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(nft.mark_campaign_funded(1), Err(Error::NotAuthorized));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(nft.mark_campaign_funded(1), Ok(()));
+
+            assert!(nft.is_receipt_funded(funded));
+            assert!(!nft.is_receipt_funded(other));
+            assert!(!nft.is_receipt_funded(99));
+        }
+
+        #[ink::test]
+        fn mint_records_block_number() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            let minted_at = ink::env::block_number::<ink::env::DefaultEnvironment>();
+
+            let token_id = nft.mint_donation_receipt(accounts.bob, 1, String::from("Save the Forest"), 1000000, 12345678).unwrap();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+
+            let metadata = nft.get_token_metadata(token_id).unwrap();
+            assert_eq!(metadata.block_number, minted_at);
+            assert_eq!(nft.tokens_of_owner_with_metadata(accounts.bob)[0].1.block_number, minted_at);
+        }
+
+        #[ink::test]
+        fn rarity_respects_decimals() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new_with_decimals(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+                10,
+            );
+            assert_eq!(nft.get_decimals(), 10);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            const ONE_DOT: Balance = 10_000_000_000; // 10^10 on Polkadot
+
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), ONE_DOT - 1, 100).unwrap();
+            nft.mint_donation_receipt(accounts.bob, 2, String::from("C2"), ONE_DOT, 200).unwrap();
+
+            assert_eq!(nft.get_token_metadata(0).unwrap().rarity, RarityTier::Common);
+            assert_eq!(nft.get_token_metadata(1).unwrap().rarity, RarityTier::Uncommon);
+            assert!(nft.get_achievements(accounts.bob).contains(&String::from("DOT Donor")));
+        }
+
+        #[ink::test]
+        fn receipt_campaign_funded_queries_platform() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            nft.mint_donation_receipt(accounts.bob, 7, String::from("C7"), 1000, 100).unwrap();
+
+            // Platform unreachable and unknown token
+            assert_eq!(nft.is_receipt_campaign_funded(0), None);
+            assert_eq!(nft.is_receipt_campaign_funded(99), None);
+
+            set_mock_campaign_state(accounts.alice, 7, CampaignState::Active);
+            assert_eq!(nft.is_receipt_campaign_funded(0), Some(false));
+
+            // The cached answer is served until it expires
+            set_mock_campaign_state(accounts.alice, 7, CampaignState::Successful);
+            assert_eq!(nft.is_receipt_campaign_funded(0), Some(false));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(FUNDED_CACHE_TTL);
+            assert_eq!(nft.is_receipt_campaign_funded(0), Some(true));
+        }
+
+        #[ink::test]
+        fn platform_transfer_requires_platform() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), 1000, 100).unwrap();
+
+            // A random account can't move someone else's receipt
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(nft.platform_transfer(accounts.bob, accounts.eve, 0), Err(Error::NotAuthorized));
+
+            // The platform can, as long as `from` is the owner
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(nft.platform_transfer(accounts.charlie, accounts.django, 0), Err(Error::NotOwner));
+            assert_eq!(nft.platform_transfer(accounts.bob, accounts.charlie, 0), Ok(()));
+            assert_eq!(nft.owner_of(0), Some(accounts.charlie));
+            assert_eq!(nft.tokens_of_owner(accounts.bob), Vec::<TokenId>::new());
+            assert_eq!(nft.tokens_of_owner(accounts.charlie), vec![0]);
+            assert_eq!(nft.get_token_metadata(0).unwrap().transfer_count, 1);
+
+            // Disabled transfers apply to the platform too
+            nft.set_transfers_enabled(false).unwrap();
+            assert_eq!(nft.platform_transfer(accounts.charlie, accounts.bob, 0), Err(Error::NotAuthorized));
+        }
+
+        #[ink::test]
+        fn get_all_tokens_pages_through_collection() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            for campaign_id in 0..5 {
+                nft.mint_donation_receipt(accounts.bob, campaign_id, String::from("C"), 1000, 100).unwrap();
+            }
+
+            // Simulate burned tokens
+            nft.token_owners.remove(1);
+            nft.token_metadata.remove(3);
+
+            let mut crawled = Vec::new();
+            let mut offset = 0;
+            loop {
+                let page = nft.get_all_tokens(offset, 2);
+                match page.last() {
+                    Some((last_id, _)) => offset = last_id + 1,
+                    None => break,
+                }
+                crawled.extend(page.into_iter().map(|(token_id, _)| token_id));
+            }
+            assert_eq!(crawled, vec![0, 2, 4]);
 
-        /// Get total amount donated by an address (original donations only)
-        #[ink(message)]
-        pub fn get_total_donated(&self, donor: AccountId) -> Balance {
-            self.total_donated.get(donor).unwrap_or(0)
+            assert_eq!(nft.get_all_tokens(4, 10).len(), 1);
+            assert!(nft.get_all_tokens(5, 10).is_empty());
+            assert!(nft.get_all_tokens(TokenId::MAX, 10).is_empty());
+            assert!(nft.get_all_tokens(0, 0).is_empty());
         }
 
-        /// Get achievement status for a donor
-        #[ink(message)]
-        pub fn get_achievements(&self, donor: AccountId) -> Vec<String> {
-            let mut achievements = Vec::new();
-            let (donation_count, total_amount) = self.get_donation_stats(donor);
-            let (common, uncommon, rare, epic, legendary) = self.get_rarity_distribution(donor);
-            
-            // Donation count achievements
-            if donation_count >= 1 { achievements.push(String::from("First Donation")); }
-            if donation_count >= 5 { achievements.push(String::from("Generous Giver")); }
-            if donation_count >= 10 { achievements.push(String::from("Philanthropist")); }
-            if donation_count >= 25 { achievements.push(String::from("Champion Donor")); }
-            if donation_count >= 50 { achievements.push(String::from("Legendary Supporter")); }
-            
-            // Amount achievements (using 1 DOT = 10^13)
+        #[ink::test]
+        fn achievements_batch_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
             const ONE_DOT: Balance = 10_000_000_000_000;
-            if total_amount >= ONE_DOT { achievements.push(String::from("DOT Donor")); }
-            if total_amount >= ONE_DOT * 10 { achievements.push(String::from("Big Spender")); }
-            if total_amount >= ONE_DOT * 100 { achievements.push(String::from("Whale")); }
-            if total_amount >= ONE_DOT * 1000 { achievements.push(String::from("Mega Whale")); }
-            
-            // Rarity achievements
-            if legendary > 0 { achievements.push(String::from("Legendary Collector")); }
-            if epic >= 3 { achievements.push(String::from("Epic Collection")); }
-            if rare >= 5 { achievements.push(String::from("Rare Collector")); }
-            let total_nfts = common.saturating_add(uncommon).saturating_add(rare).saturating_add(epic).saturating_add(legendary);
-            if total_nfts >= 10 { 
-                achievements.push(String::from("NFT Enthusiast")); 
+
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), 1000, 100).unwrap();
+            for i in 0..5 {
+                nft.mint_donation_receipt(accounts.charlie, i, String::from("C"), ONE_DOT, 100).unwrap();
             }
-            
-            achievements
+
+            let batch = nft.get_achievements_batch(vec![accounts.bob, accounts.charlie, accounts.django]);
+            assert_eq!(batch.len(), 3);
+            assert_eq!(batch[0], (accounts.bob, vec![String::from("First Donation")]));
+            assert_eq!(batch[1], (accounts.charlie, vec![
+                String::from("First Donation"),
+                String::from("Generous Giver"),
+                String::from("DOT Donor"),
+            ]));
+            assert_eq!(batch[2], (accounts.django, Vec::<String>::new()));
+
+            // Oversized requests are truncated
+            let donors = vec![accounts.bob; MAX_ACHIEVEMENTS_BATCH + 1];
+            assert_eq!(nft.get_achievements_batch(donors).len(), MAX_ACHIEVEMENTS_BATCH);
         }
-    }
 
-    /// Event emitted when a token is transferred
-    #[ink(event)]
-    pub struct Transfer {
-        #[ink(topic)]
-        from: Option<AccountId>,
-        #[ink(topic)]
-        to: Option<AccountId>,
-        #[ink(topic)]
-        token_id: TokenId,
-    }
+        #[ink::test]
+        fn custom_achievement_unlocks_at_threshold() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
 
-    /// Event emitted when a donation NFT is minted
-    #[ink(event)]
-    pub struct DonationNftMinted {
-        #[ink(topic)]
-        token_id: TokenId,
-        #[ink(topic)]
-        owner: AccountId,
-        campaign_id: u32,
-        amount: Balance,
-    }
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                nft.add_achievement_rule(String::from("Spring Supporter"), AchievementKind::DonationCount, 2),
+                Err(Error::NotAuthorized)
+            );
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                nft.add_achievement_rule(String::new(), AchievementKind::DonationCount, 2),
+                Err(Error::InvalidAchievementRule)
+            );
+            nft.add_achievement_rule(String::from("Spring Supporter"), AchievementKind::DonationCount, 2).unwrap();
+            nft.add_achievement_rule(String::from("Common Folk"), AchievementKind::RarityCount(RarityTier::Common), 2).unwrap();
+
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), 1000, 100).unwrap();
+            assert_eq!(nft.get_achievements(accounts.bob), vec![String::from("First Donation")]);
+
+            nft.mint_donation_receipt(accounts.bob, 2, String::from("C2"), 1000, 200).unwrap();
+            assert_eq!(nft.get_achievements(accounts.bob), vec![
+                String::from("First Donation"),
+                String::from("Spring Supporter"),
+                String::from("Common Folk"),
+            ]);
+
+            // Replacing the list drops the built-in badges
+            nft.set_achievement_rules(vec![(String::from("Spring Supporter"), AchievementKind::DonationCount, 2)]).unwrap();
+            assert_eq!(nft.get_achievements(accounts.bob), vec![String::from("Spring Supporter")]);
+        }
 
         #[ink::test]
-        fn new_works() {
+        fn platform_contract_change_is_two_step() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            let nft = DonationNft::new(
-                accounts.bob,
+            let mut nft = DonationNft::new(
+                accounts.alice,
                 String::from("DotNation Receipt"),
                 String::from("DNFT"),
             );
-            assert_eq!(nft.total_supply(), 0);
-            assert_eq!(nft.collection_name(), "DotNation Receipt");
-            assert_eq!(nft.collection_symbol(), "DNFT");
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(nft.propose_platform_contract(accounts.bob), Err(Error::NotAuthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(nft.accept_platform_contract(), Err(Error::NotAuthorized));
+            nft.propose_platform_contract(accounts.charlie).unwrap();
+            assert_eq!(nft.get_pending_platform_contract(), Some(accounts.charlie));
+
+            // Nothing changes until the proposed contract accepts
+            assert_eq!(nft.get_platform_contract(), accounts.alice);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(nft.accept_platform_contract(), Err(Error::NotAuthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(nft.accept_platform_contract(), Ok(()));
+            assert_eq!(nft.get_platform_contract(), accounts.charlie);
+            assert_eq!(nft.get_pending_platform_contract(), None);
+
+            // The new platform can mint
+            assert!(nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), 1000, 100).is_ok());
+            assert_eq!(nft.owner_of(0), Some(accounts.bob));
         }
 
         #[ink::test]
-        fn mint_works() {
+        fn update_campaign_title_on_receipts_works() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
             let mut nft = DonationNft::new(
                 accounts.alice,
@@ -539,24 +1888,61 @@ mod donation_nft {
                 String::from("DNFT"),
             );
 
-            // Set caller to admin
+            for campaign_id in [1, 2, 1, 1] {
+                nft.mint_donation_receipt(accounts.bob, campaign_id, String::from("Save the Forrest"), 1000000, 12345678).unwrap();
+            }
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                nft.update_campaign_title_on_receipts(1, String::from("Save the Forest"), 0, 10),
+                Err(Error::NotAuthorized)
+            );
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(nft.update_campaign_title_on_receipts(1, String::new(), 0, 10), Err(Error::InvalidTitle));
 
-            let result = nft.mint_donation_receipt(
-                accounts.bob,
-                1,
-                String::from("Save the Forest"),
-                1000000,
-                12345678,
+            // In batches of two tokens; campaign 2's receipt is left alone
+            assert_eq!(nft.update_campaign_title_on_receipts(1, String::from("Save the Forest"), 0, 2), Ok(1));
+            assert_eq!(nft.get_token_metadata(2).unwrap().campaign_title, "Save the Forrest");
+            assert_eq!(nft.update_campaign_title_on_receipts(1, String::from("Save the Forest"), 2, 2), Ok(2));
+
+            for token_id in [0, 2, 3] {
+                assert_eq!(nft.get_token_metadata(token_id).unwrap().campaign_title, "Save the Forest");
+            }
+            assert_eq!(nft.get_token_metadata(1).unwrap().campaign_title, "Save the Forrest");
+
+            // Frozen metadata can't change
+            nft.freeze_metadata().unwrap();
+            assert!(nft.is_metadata_frozen());
+            assert_eq!(
+                nft.update_campaign_title_on_receipts(2, String::from("Clean Water"), 0, 10),
+                Err(Error::MetadataFrozen)
             );
+        }
 
-            assert!(result.is_ok());
-            assert_eq!(nft.total_supply(), 1);
-            assert_eq!(nft.owner_of(0), Some(accounts.bob));
+        #[ink::test]
+        fn relative_tier_depends_on_goal() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            const ONE_DOT: Balance = 10_000_000_000_000;
+
+            // 5 DOT to a 10 DOT campaign, a 1,000 DOT one, and without a goal
+            nft.mint_donation_receipt_with_goal(accounts.bob, 1, String::from("Small"), ONE_DOT * 5, 100, ONE_DOT * 10).unwrap();
+            nft.mint_donation_receipt_with_goal(accounts.bob, 2, String::from("Large"), ONE_DOT * 5, 200, ONE_DOT * 1000).unwrap();
+            nft.mint_donation_receipt(accounts.bob, 3, String::from("Unknown"), ONE_DOT * 5, 300).unwrap();
+
+            assert_eq!(nft.get_token_rarity(0), Some((RarityTier::Uncommon, Some(RarityTier::Legendary))));
+            assert_eq!(nft.get_token_rarity(1), Some((RarityTier::Uncommon, Some(RarityTier::Common))));
+            assert_eq!(nft.get_token_rarity(2), Some((RarityTier::Uncommon, None)));
+            assert_eq!(nft.get_token_rarity(3), None);
         }
 
         #[ink::test]
-        fn unauthorized_mint_fails() {
+        fn leaderboard_skips_hidden_donors() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
             let mut nft = DonationNft::new(
                 accounts.alice,
@@ -564,22 +1950,28 @@ mod donation_nft {
                 String::from("DNFT"),
             );
 
-            // Try to mint as unauthorized user
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), 5_000, 100).unwrap();
+            nft.mint_donation_receipt(accounts.charlie, 1, String::from("C1"), 1_000, 200).unwrap();
+            nft.mint_donation_receipt(accounts.django, 1, String::from("C1"), 3_000, 300).unwrap();
 
-            let result = nft.mint_donation_receipt(
-                accounts.bob,
-                1,
-                String::from("Save the Forest"),
-                1000000,
-                12345678,
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(nft.is_leaderboard_visible(accounts.bob));
+            nft.set_leaderboard_visible(false);
+            assert!(!nft.is_leaderboard_visible(accounts.bob));
+
+            // Bob has the highest total but doesn't show up
+            assert_eq!(
+                nft.get_leaderboard(10),
+                vec![(accounts.django, 3_000, 1), (accounts.charlie, 1_000, 1)]
             );
+            assert_eq!(nft.get_total_donated(accounts.bob), 5_000);
 
-            assert_eq!(result, Err(Error::NotAuthorized));
+            nft.set_leaderboard_visible(true);
+            assert_eq!(nft.get_leaderboard(1), vec![(accounts.bob, 5_000, 1)]);
         }
 
         #[ink::test]
-        fn tokens_of_owner_works() {
+        fn transfer_all_moves_every_token() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
             let mut nft = DonationNft::new(
                 accounts.alice,
@@ -587,19 +1979,35 @@ mod donation_nft {
                 String::from("DNFT"),
             );
 
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            for campaign_id in 0..3 {
+                nft.mint_donation_receipt(accounts.bob, campaign_id, String::from("C"), 1_000, 100).unwrap();
+            }
+            nft.mint_donation_receipt(accounts.django, 9, String::from("C"), 1_000, 100).unwrap();
 
-            // Mint 3 tokens to bob
-            nft.mint_donation_receipt(accounts.bob, 1, String::from("Campaign 1"), 1000, 100).unwrap();
-            nft.mint_donation_receipt(accounts.bob, 2, String::from("Campaign 2"), 2000, 200).unwrap();
-            nft.mint_donation_receipt(accounts.bob, 3, String::from("Campaign 3"), 3000, 300).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(nft.transfer_all(accounts.bob), Err(Error::TransferToSelf));
+            assert_eq!(nft.transfer_all(AccountId::from([0u8; 32])), Err(Error::ZeroAddress));
+
+            let events_before = ink::env::test::recorded_events().count();
+            assert_eq!(nft.transfer_all(accounts.charlie), Ok(0));
+            assert_eq!(ink::env::test::recorded_events().count(), events_before + 3);
+            assert!(nft.tokens_of_owner(accounts.bob).is_empty());
+            assert_eq!(nft.tokens_of_owner(accounts.charlie), vec![0, 1, 2]);
+            for token_id in 0..3 {
+                assert_eq!(nft.owner_of(token_id), Some(accounts.charlie));
+                assert_eq!(nft.get_token_metadata(token_id).unwrap().transfer_count, 1);
+            }
+            assert_eq!(nft.owner_of(3), Some(accounts.django));
 
-            let tokens = nft.tokens_of_owner(accounts.bob);
-            assert_eq!(tokens.len(), 3);
+            // With transfers disabled, only the admin may use it
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            nft.set_transfers_enabled(false).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(nft.transfer_all(accounts.bob), Err(Error::NotAuthorized));
         }
 
         #[ink::test]
-        fn donation_stats_works() {
+        fn transfer_all_moves_one_batch_per_call() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
             let mut nft = DonationNft::new(
                 accounts.alice,
@@ -607,18 +2015,81 @@ mod donation_nft {
                 String::from("DNFT"),
             );
 
+            nft.mint_soulbound_receipt(accounts.bob, 0, String::from("C"), 1_000, 100, 0).unwrap();
+            for campaign_id in 1..=(MAX_TRANSFER_ALL_BATCH as u32 + 1) {
+                nft.mint_donation_receipt(accounts.bob, campaign_id, String::from("C"), 1_000, 100).unwrap();
+            }
+
+            // The first call checks one batch; the soulbound receipt moves to the back
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(nft.transfer_all(accounts.charlie), Ok(2));
+            assert_eq!(nft.tokens_of_owner(accounts.charlie).len(), MAX_TRANSFER_ALL_BATCH - 1);
+            let last = MAX_TRANSFER_ALL_BATCH as TokenId + 1;
+            assert_eq!(nft.tokens_of_owner(accounts.bob), vec![last - 1, last, 0]);
+
+            // The soulbound receipt is never counted as remaining
+            assert_eq!(nft.transfer_all(accounts.charlie), Ok(0));
+            assert_eq!(nft.tokens_of_owner(accounts.bob), vec![0]);
+            assert_eq!(nft.owner_of(last), Some(accounts.charlie));
+            assert_eq!(nft.transfer_all(accounts.charlie), Ok(0));
+            assert_eq!(nft.soulbound_balance_of(accounts.bob), 1);
+
+            // A wallet of mostly soulbound receipts is still only read a batch at a time
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            for campaign_id in 0..=(MAX_TRANSFER_ALL_BATCH as u32) {
+                nft.mint_soulbound_receipt(accounts.eve, campaign_id, String::from("C"), 1_000, 100, 0).unwrap();
+            }
+            let transferable = nft.mint_donation_receipt(accounts.eve, 0, String::from("C"), 1_000, 100).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(nft.transfer_all(accounts.charlie), Ok(1));
+            assert_eq!(nft.owner_of(transferable), Some(accounts.eve));
+            assert_eq!(nft.transfer_all(accounts.charlie), Ok(0));
+            assert_eq!(nft.owner_of(transferable), Some(accounts.charlie));
+            assert_eq!(nft.tokens_of_owner(accounts.eve).len(), MAX_TRANSFER_ALL_BATCH + 1);
+        }
 
-            nft.mint_donation_receipt(accounts.bob, 1, String::from("Campaign 1"), 1000, 100).unwrap();
-            nft.mint_donation_receipt(accounts.bob, 2, String::from("Campaign 2"), 2000, 200).unwrap();
+        #[ink::test]
+        fn approved_operator_can_transfer_once() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
 
-            let (count, total) = nft.get_donation_stats(accounts.bob);
-            assert_eq!(count, 2);
-            assert_eq!(total, 3000);
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), 1_000, 100).unwrap();
+            nft.mint_donation_receipt(accounts.bob, 2, String::from("C2"), 1_000, 200).unwrap();
+
+            // Unapproved operators are rejected
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(nft.transfer_from(accounts.bob, accounts.charlie, 0), Err(Error::NotApproved));
+            assert_eq!(nft.approve(accounts.django, Some(0), true), Err(Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(nft.approve(accounts.bob, Some(0), true), Err(Error::SelfApprove));
+            nft.approve(accounts.charlie, Some(0), true).unwrap();
+            assert!(nft.allowance(accounts.bob, accounts.charlie, Some(0)));
+            assert!(!nft.allowance(accounts.bob, accounts.charlie, Some(1)));
+            assert!(!nft.allowance(accounts.bob, accounts.charlie, None));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(nft.transfer_from(accounts.bob, accounts.bob, 0), Err(Error::TransferToSelf));
+            assert_eq!(nft.transfer_from(accounts.bob, AccountId::from([0u8; 32]), 0), Err(Error::ZeroAddress));
+            assert_eq!(nft.transfer_from(accounts.bob, accounts.django, 1), Err(Error::NotApproved));
+            nft.transfer_from(accounts.bob, accounts.django, 0).unwrap();
+            assert_eq!(nft.owner_of(0), Some(accounts.django));
+
+            // The approval is cleared, even if the token comes back
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            nft.transfer(accounts.bob, 0).unwrap();
+            assert!(!nft.allowance(accounts.bob, accounts.charlie, Some(0)));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(nft.transfer_from(accounts.bob, accounts.charlie, 0), Err(Error::NotApproved));
         }
 
         #[ink::test]
-        fn transfer_works() {
+        fn operator_for_all_can_transfer_any_token() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
             let mut nft = DonationNft::new(
                 accounts.alice,
@@ -626,25 +2097,31 @@ mod donation_nft {
                 String::from("DNFT"),
             );
 
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), 1_000, 100).unwrap();
+            nft.mint_donation_receipt(accounts.bob, 2, String::from("C2"), 1_000, 200).unwrap();
 
-            // Mint token to bob
-            nft.mint_donation_receipt(accounts.bob, 1, String::from("Campaign 1"), 1000, 100).unwrap();
-            assert_eq!(nft.owner_of(0), Some(accounts.bob));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            nft.approve(accounts.charlie, None, true).unwrap();
+            assert!(nft.allowance(accounts.bob, accounts.charlie, None));
+            assert!(nft.allowance(accounts.bob, accounts.charlie, Some(1)));
 
-            // Transfer from bob to charlie
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            nft.transfer_from(accounts.bob, accounts.django, 0).unwrap();
+            nft.transfer_from(accounts.bob, accounts.charlie, 1).unwrap();
+            assert_eq!(nft.tokens_of_owner(accounts.django), vec![0]);
+            assert_eq!(nft.tokens_of_owner(accounts.charlie), vec![1]);
+
+            // Revoking stops further transfers
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            nft.transfer(accounts.bob, 0).unwrap();
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            let result = nft.transfer(accounts.charlie, 0);
-            assert!(result.is_ok());
-            assert_eq!(nft.owner_of(0), Some(accounts.charlie));
-            
-            // Check transfer count increased
-            let metadata = nft.get_token_metadata(0).unwrap();
-            assert_eq!(metadata.transfer_count, 1);
+            nft.approve(accounts.charlie, None, false).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(nft.transfer_from(accounts.bob, accounts.charlie, 0), Err(Error::NotApproved));
         }
 
         #[ink::test]
-        fn rarity_tier_works() {
+        fn burn_removes_token_and_tracks_supply() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
             let mut nft = DonationNft::new(
                 accounts.alice,
@@ -652,26 +2129,60 @@ mod donation_nft {
                 String::from("DNFT"),
             );
 
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), 1_000, 100).unwrap();
+            nft.mint_donation_receipt(accounts.bob, 2, String::from("C2"), 1_000, 200).unwrap();
+            nft.mint_donation_receipt(accounts.charlie, 3, String::from("C3"), 1_000, 300).unwrap();
+
+            // Only the owner or admin may burn
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(nft.burn(0), Err(Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            nft.burn(0).unwrap();
+            assert_eq!(nft.owner_of(0), None);
+            assert!(nft.get_token_metadata(0).is_none());
+            assert_eq!(nft.tokens_of_owner(accounts.bob), vec![1]);
+            assert_eq!(nft.burn(0), Err(Error::TokenNotFound));
+
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            nft.burn(2).unwrap();
+            assert!(nft.tokens_of_owner(accounts.charlie).is_empty());
 
-            const ONE_DOT: Balance = 10_000_000_000_000;
+            assert_eq!(nft.total_supply(), 3);
+            assert_eq!(nft.burned_count(), 2);
+            assert_eq!(nft.circulating_supply(), 1);
 
-            // Test different rarity tiers
-            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), ONE_DOT / 2, 100).unwrap();
-            nft.mint_donation_receipt(accounts.bob, 2, String::from("C2"), ONE_DOT * 5, 200).unwrap();
-            nft.mint_donation_receipt(accounts.bob, 3, String::from("C3"), ONE_DOT * 50, 300).unwrap();
+            // New mints keep counting up
+            assert_eq!(nft.mint_donation_receipt(accounts.bob, 4, String::from("C4"), 1_000, 400), Ok(3));
+        }
 
-            let metadata0 = nft.get_token_metadata(0).unwrap();
-            let metadata1 = nft.get_token_metadata(1).unwrap();
-            let metadata2 = nft.get_token_metadata(2).unwrap();
+        #[ink::test]
+        fn token_uri_uses_base_uri() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
 
-            assert_eq!(metadata0.rarity, RarityTier::Common);
-            assert_eq!(metadata1.rarity, RarityTier::Uncommon);
-            assert_eq!(metadata2.rarity, RarityTier::Rare);
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), 1_000, 100).unwrap();
+            assert_eq!(nft.token_uri(0), None);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(nft.set_base_uri(String::from("ipfs://cid/")), Err(Error::NotAuthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            nft.set_base_uri(String::from("ipfs://cid/")).unwrap();
+            assert_eq!(nft.base_uri(), String::from("ipfs://cid/"));
+            assert_eq!(nft.token_uri(0), Some(String::from("ipfs://cid/0")));
+            assert_eq!(nft.token_uri(1), None);
+
+            nft.set_base_uri(String::new()).unwrap();
+            assert_eq!(nft.token_uri(0), None);
         }
 
         #[ink::test]
-        fn achievements_work() {
+        fn held_value_follows_transferred_receipts() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
             let mut nft = DonationNft::new(
                 accounts.alice,
@@ -679,15 +2190,96 @@ mod donation_nft {
                 String::from("DNFT"),
             );
 
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), 1_000, 100).unwrap();
+            nft.mint_donation_receipt(accounts.bob, 2, String::from("C2"), 2_500, 200).unwrap();
+            assert_eq!(nft.get_held_donation_value(accounts.bob), 3_500);
+            assert_eq!(nft.get_held_donation_value(accounts.charlie), 0);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            nft.transfer(accounts.charlie, 1).unwrap();
+
+            // Held value moves with the receipt, original credit stays with the donor
+            assert_eq!(nft.get_held_donation_value(accounts.bob), 1_000);
+            assert_eq!(nft.get_held_donation_value(accounts.charlie), 2_500);
+            assert_eq!(nft.get_total_donated(accounts.bob), 3_500);
+            assert_eq!(nft.get_total_donated(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn rarity_thresholds_apply_to_new_mints_only() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new_with_decimals(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+                10,
+            );
+
+            const ONE_DOT: Balance = 10_000_000_000;
+            assert_eq!(
+                nft.get_rarity_thresholds(),
+                [ONE_DOT, ONE_DOT * 10, ONE_DOT * 100, ONE_DOT * 1000]
+            );
+
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), ONE_DOT * 10, 100).unwrap();
+            assert_eq!(nft.get_token_metadata(0).unwrap().rarity, RarityTier::Rare);
+
+            assert_eq!(nft.set_rarity_thresholds([1, 2, 2, 3]), Err(Error::InvalidRarityThresholds));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(nft.set_rarity_thresholds([1, 2, 3, 4]), Err(Error::NotAuthorized));
+
+            // Stricter tiers: the same amount is now only Uncommon
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let stricter = [ONE_DOT * 10, ONE_DOT * 100, ONE_DOT * 1000, ONE_DOT * 10_000];
+            nft.set_rarity_thresholds(stricter).unwrap();
+            assert_eq!(nft.get_rarity_thresholds(), stricter);
 
-            // Mint 5 NFTs
-            for i in 0..5 {
-                nft.mint_donation_receipt(accounts.bob, i, String::from("Campaign"), 1000, 100).unwrap();
-            }
+            nft.mint_donation_receipt(accounts.bob, 2, String::from("C2"), ONE_DOT * 10, 200).unwrap();
+            assert_eq!(nft.get_token_metadata(1).unwrap().rarity, RarityTier::Uncommon);
+            assert_eq!(nft.get_token_metadata(0).unwrap().rarity, RarityTier::Rare);
+        }
 
-            let achievements = nft.get_achievements(accounts.bob);
-            assert!(achievements.len() >= 2); // Should have "First Donation" and "Generous Giver"
+        #[ink::test]
+        fn soulbound_receipts_cannot_be_transferred() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut nft = DonationNft::new(
+                accounts.alice,
+                String::from("DotNation Receipt"),
+                String::from("DNFT"),
+            );
+
+            nft.mint_donation_receipt(accounts.bob, 1, String::from("C1"), 1_000, 100).unwrap();
+            nft.mint_soulbound_receipt(accounts.bob, 2, String::from("C2"), 1_000, 200, 0).unwrap();
+            nft.mint_soulbound_receipt(accounts.alice, 3, String::from("C3"), 1_000, 300, 0).unwrap();
+            assert!(!nft.is_soulbound(0));
+            assert!(nft.is_soulbound(1));
+
+            // Not even the admin can move a soulbound receipt
+            assert_eq!(nft.transfer(accounts.bob, 2), Err(Error::Soulbound));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(nft.transfer(accounts.charlie, 1), Err(Error::Soulbound));
+            nft.approve(accounts.charlie, None, true).unwrap();
+            nft.transfer(accounts.charlie, 0).unwrap();
+            assert_eq!(nft.owner_of(0), Some(accounts.charlie));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(nft.transfer_from(accounts.bob, accounts.django, 1), Err(Error::Soulbound));
+
+            // transfer_all leaves soulbound receipts behind
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(nft.transfer_all(accounts.django), Ok(0));
+            assert_eq!(nft.tokens_of_owner(accounts.bob), vec![1]);
+
+            // Nor can the platform
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(nft.platform_transfer(accounts.bob, accounts.charlie, 1), Err(Error::Soulbound));
+            assert_eq!(nft.owner_of(1), Some(accounts.bob));
+
+            // Burning is still allowed and updates the soulbound count
+            assert_eq!(nft.soulbound_balance_of(accounts.bob), 1);
+            nft.burn(1).unwrap();
+            assert_eq!(nft.soulbound_balance_of(accounts.bob), 0);
         }
     }
 }
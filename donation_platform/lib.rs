@@ -29,6 +29,12 @@
 /// - State machine prevents invalid transitions
 /// - Integer overflow protection with checked arithmetic
 ///
+/// ## Rounding
+/// - Fees are rounded down; the campaign receives the rest of each donation
+/// - Matching shares are rounded down and the remainder goes to the round's
+///   highest-scoring campaign, so a distributed round pays out exactly its pool
+/// - A round with no eligible campaigns returns its pool to the matching pool
+///
 /// ## Documentation
 /// - Full docs: cargo doc --open
 /// - Features: /FEATURES.md
@@ -42,6 +48,7 @@
 #[ink::contract]
 mod donation_platform_v2 {
     use ink::prelude::vec::Vec;
+    use ink::prelude::collections::BTreeSet;
     use ink::prelude::string::String;
     use ink::storage::Mapping;
 
@@ -104,6 +111,50 @@ mod donation_platform_v2 {
         NoActiveRound,
         /// Round has already ended.
         RoundEnded,
+        /// Matching for the campaign's round has not been distributed yet.
+        MatchingNotDistributed,
+        /// A campaign tag is invalid (e.g., empty, too long, duplicated or too many tags).
+        InvalidTag,
+        /// The platform fee is out of range.
+        InvalidFee,
+        /// Milestone voting hasn't been open for the minimum voting duration yet.
+        VotingStillOpen,
+        /// The campaign's withdrawal cooldown hasn't elapsed since the last payout.
+        WithdrawalCooldown,
+        /// The campaign no longer accepts donations.
+        DonationsFrozen,
+        /// The operation requires the contract to be marked as deprecated.
+        NotDeprecated,
+        /// Donations are paused.
+        ContractPaused,
+        /// No queued admin action with this ID.
+        AdminActionNotFound,
+        /// The admin action's ETA hasn't been reached yet.
+        TimelockNotExpired,
+        /// Funding exponent outside the linear..quadratic range.
+        InvalidFundingExponent,
+        /// The campaign has reached its maximum number of distinct donors.
+        DonorLimitReached,
+        /// The contract's storage has already been initialized.
+        AlreadyInitialized,
+        /// The contract holds enough to refund the campaign in full.
+        NotInsolvent,
+        /// Donor-breadth weighting above `MAX_DONOR_BREADTH_BPS`.
+        InvalidDonorBreadth,
+        /// The campaign's refund window has closed.
+        RefundWindowClosed,
+        /// The campaign's refund window is still open (or has no end).
+        RefundWindowOpen,
+        /// The setting can only be changed before the campaign receives donations.
+        CampaignHasDonations,
+        /// Refund window shorter than `MIN_REFUND_WINDOW`.
+        InvalidRefundWindow,
+        /// The matching round has opened, so its rules are fixed.
+        RoundAlreadyStarted,
+        /// `FORCE_REFUND_GRACE_PERIOD` has not elapsed since the campaign deadline.
+        GracePeriodActive,
+        /// The zero address was given for a role that can't be changed later.
+        ZeroAddress,
     }
 
     /// Represents the lifecycle state of a fundraising campaign.
@@ -122,6 +173,8 @@ mod donation_platform_v2 {
         Failed,
         /// The funds for the campaign have been withdrawn by the beneficiary.
         Withdrawn,
+        /// The campaign's donations were moved into another campaign (see `merged_into`).
+        Merged,
     }
 
     /// Represents a single donation made to a fundraising campaign.
@@ -138,6 +191,10 @@ mod donation_platform_v2 {
         amount: Balance,
         /// The timestamp of the donation.
         timestamp: Timestamp,
+        /// Whether this is a fee-free top-up by the campaign owner (excluded from QF).
+        owner_contribution: bool,
+        /// The platform fee taken from this donation.
+        fee: Balance,
     }
 
     /// Represents a single fundraising campaign.
@@ -176,6 +233,50 @@ mod donation_platform_v2 {
         milestones: Vec<Milestone>,
         /// Whether campaign uses milestone-based fund release
         uses_milestones: bool,
+        /// Whether the matching amount has been paid out to the beneficiary
+        matching_withdrawn: bool,
+        /// Whether the admin has opened refunds regardless of the campaign state
+        refundable: bool,
+        /// Whether voting on the first milestone opens automatically when the goal is reached
+        auto_activate_first_milestone: bool,
+        /// Number of distinct donors (owner top-ups excluded)
+        unique_donor_count: u32,
+        /// Platform fees taken from this campaign's donations so far
+        fees_paid: Balance,
+        /// Linear vesting period for withdrawn funds in milliseconds (0 = paid at once)
+        vesting_duration: u64,
+        /// When vesting started (set by `withdraw_funds`)
+        vesting_start: Timestamp,
+        /// Total amount being vested to the beneficiary
+        vesting_total: Balance,
+        /// Amount already paid out of `vesting_total`
+        vesting_withdrawn: Balance,
+        /// Minimum time between payouts to the beneficiary in milliseconds (0 = none)
+        withdrawal_cooldown: u64,
+        /// When funds were last paid out to the beneficiary
+        last_withdrawal_at: Option<Timestamp>,
+        /// Whether the campaign stopped accepting donations (see `freeze_donations`).
+        donations_frozen: bool,
+        /// Minimum amount for the campaign to succeed (0 = the goal). When set, `goal`
+        /// acts as the hard cap up to which donations are still accepted.
+        soft_cap: Balance,
+        /// The campaign this one was merged into, if any.
+        merged_into: Option<u32>,
+        /// Contract notified via `on_campaign_success` when the campaign succeeds.
+        success_hook: Option<AccountId>,
+        /// Number of old donations summarized out of detailed storage. They are
+        /// always the lowest indices, `0..archived_donation_count`.
+        archived_donation_count: u32,
+        /// Total amount of the archived donations.
+        archived_donation_total: Balance,
+        /// Maximum number of distinct donors (0 = unlimited); existing donors can keep donating.
+        max_donors: u32,
+        /// When the campaign failed or was opened for refunds.
+        failed_at: Option<Timestamp>,
+        /// How long refunds stay claimable after failure (0 = forever).
+        refund_window: u64,
+        /// Where unclaimed refunds go once the refund window closes.
+        refund_fallback: Option<AccountId>,
     }
 
     /// A composite struct that holds the details of a campaign along with its donations.
@@ -208,6 +309,111 @@ mod donation_platform_v2 {
         success_ids: Vec<u32>,
     }
 
+    /// Contract-wide settings bundled for frontends, returned by `get_config`.
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct PlatformConfig {
+        /// Contract logic version.
+        version: u32,
+        /// Maximum operations per batch transaction.
+        max_batch_size: u32,
+        /// Whether donations mint NFT receipts.
+        nft_enabled: bool,
+        /// The NFT receipt contract, if set.
+        nft_contract: Option<AccountId>,
+        /// Unallocated matching pool.
+        matching_pool_balance: Balance,
+        /// The active matching round, if any.
+        current_round: Option<u32>,
+        /// Platform fee in basis points.
+        fee_bps: u32,
+        /// Whether donations are paused.
+        paused: bool,
+        /// Account receiving platform fees.
+        treasury: AccountId,
+    }
+
+    /// What the caller is allowed to do, for UI gating.
+    ///
+    /// There is no separate role system yet, so every flag follows `admin`.
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Permissions {
+        /// Can perform admin-only actions.
+        is_admin: bool,
+        /// Can pause and unpause donations.
+        is_pauser: bool,
+        /// Can change the platform fee and fee discounts.
+        is_fee_manager: bool,
+    }
+
+    /// Compact state of one campaign, as reported in an `IndexerSnapshot`.
+    #[derive(Debug, PartialEq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct CampaignSnapshot {
+        /// Campaign ID.
+        id: u32,
+        /// Current state.
+        state: CampaignState,
+        /// Total raised (gross).
+        raised: Balance,
+        /// Number of donations.
+        donation_count: u32,
+    }
+
+    /// A range of campaigns summarized for indexers reconciling after downtime.
+    ///
+    /// Returned by `get_events_summary`.
+    #[derive(Debug, PartialEq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct IndexerSnapshot {
+        /// The block the snapshot was taken at.
+        block_number: BlockNumber,
+        /// Total number of campaigns at that block.
+        campaign_count: u32,
+        /// The campaigns in the requested range, by ID.
+        campaigns: Vec<CampaignSnapshot>,
+    }
+
+    /// A donation receipt enriched with the donor's subscription status.
+    ///
+    /// Returned by `donate_and_notify_subscription` so UIs can show both in one call.
+    #[derive(Debug, PartialEq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct DonationReceipt {
+        /// The campaign that received the donation.
+        campaign_id: u32,
+        /// The donor.
+        donor: AccountId,
+        /// The amount accepted (any part past a hard cap went back to the donor).
+        amount: Balance,
+        /// Whether the donor has an active subscription to the creator
+        /// (`None` if no subscription manager is configured or the lookup failed).
+        is_subscriber: Option<bool>,
+    }
+
+    /// A complete record of a single donation, e.g. for tax software.
+    ///
+    /// Returned by `get_donation_receipt`.
+    #[derive(Debug, PartialEq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct TaxReceipt {
+        /// The campaign that received the donation.
+        campaign_id: u32,
+        /// The campaign title at the time of the query.
+        campaign_title: String,
+        /// The donor.
+        donor: AccountId,
+        /// The index of the donation within the campaign.
+        donation_index: u32,
+        /// The gross donated amount.
+        amount: Balance,
+        /// The amount that went to the campaign after the platform fee.
+        net_amount: Balance,
+        /// When the donation was made.
+        timestamp: Timestamp,
+    }
+
     /// Represents a milestone in a campaign (for DAO voting).
     #[derive(Debug, Clone, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -227,6 +433,77 @@ mod donation_platform_v2 {
         released: bool,
         /// Whether voting is currently active
         voting_active: bool,
+        /// When voting was last activated
+        activated_at: Timestamp,
+    }
+
+    /// A sensitive admin change that must go through the timelock.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    #[cfg_attr(feature = "std", derive(::ink::storage::traits::StorageLayout))]
+    #[allow(clippy::enum_variant_names)]
+    pub enum AdminAction {
+        /// Set the platform fee (basis points).
+        SetFee(u32),
+        /// Set the account that receives platform fees.
+        SetTreasury(AccountId),
+        /// Pause or resume donations.
+        SetPaused(bool),
+    }
+
+    /// Derived status of a milestone, for display.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum MilestoneStatus {
+        /// Not released and voting hasn't been opened.
+        Pending,
+        /// Donors are voting on the release.
+        Voting,
+        /// The milestone's funds were released.
+        Released,
+        /// The deadline passed without a release or open vote.
+        Overdue,
+    }
+
+    /// A milestone with its derived status and the viewer's vote.
+    #[derive(Debug, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct MilestoneView {
+        /// The milestone itself.
+        milestone: Milestone,
+        /// Its derived status.
+        status: MilestoneStatus,
+        /// Whether the viewer has voted on it.
+        viewer_voted: bool,
+        /// The weight of the viewer's vote (0 if they haven't voted).
+        viewer_vote_weight: Balance,
+    }
+
+    /// Everything a milestone campaign page needs, returned by `get_milestone_dashboard`.
+    #[derive(Debug, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct MilestoneDashboard {
+        /// The campaign.
+        campaign: Campaign,
+        /// Its milestones, in order.
+        milestones: Vec<MilestoneView>,
+        /// The viewer's voting power: their weight on the milestone being voted on, or
+        /// their total donations to the campaign when no vote is open.
+        viewer_voting_power: Balance,
+    }
+
+    /// Where a matching round is in its lifecycle, derived from the block timestamp.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum RoundPhase {
+        /// Announced, but donations don't count toward matching yet.
+        Scheduled,
+        /// Donations count toward matching.
+        Active,
+        /// Over and waiting for distribution.
+        Ended,
+        /// Matching has been paid out.
+        Distributed,
     }
 
     /// Represents a matching round for quadratic funding.
@@ -238,12 +515,25 @@ mod donation_platform_v2 {
         id: u32,
         /// Total matching pool for this round
         pool_amount: Balance,
+        /// When the round opens; earlier donations don't count toward matching
+        start_time: Timestamp,
         /// When the round ends
         end_time: Timestamp,
         /// Whether matching has been distributed
         distributed: bool,
         /// Campaign IDs in this round
         campaign_ids: Vec<u32>,
+        /// Minimum unique donors a campaign needs to receive matching
+        min_unique_donors: u32,
+        /// Whether donations emit `EstimatedMatchingUpdated` events (opt-in, costs gas)
+        emit_estimates: bool,
+        /// Power the sum of donation roots is raised to, in thousandths
+        /// (1000 = linear, 2000 = quadratic)
+        funding_exponent: u32,
+        /// Donations below this amount don't count toward the QF score (anti-sybil)
+        min_qf_donation: Balance,
+        /// Score bonus per doubling of a campaign's unique donors, in basis points (0 = off)
+        donor_breadth_bps: u32,
     }
 
     /// The main storage struct for the donation platform contract.
@@ -254,8 +544,9 @@ mod donation_platform_v2 {
     pub struct DonationPlatformV2 {
         /// A mapping from campaign ID to campaign data.
         campaigns: Mapping<u32, Campaign>,
-        /// A mapping from campaign ID to a list of its donations.
-        campaign_donations: Mapping<u32, Vec<Donation>>,
+        /// A mapping from (campaign_id, donation_index) to a single donation.
+        /// The next free index for a campaign is its `donation_count`.
+        campaign_donations: Mapping<(u32, u32), Donation>,
         /// A mapping to track refund claims: (campaign_id, donor) -> has_claimed
         refund_claimed: Mapping<(u32, AccountId), bool>,
         /// The total number of campaigns created.
@@ -282,15 +573,177 @@ mod donation_platform_v2 {
         round_count: u32,
         /// Track unique donors per campaign: (campaign_id, donor) -> donated
         unique_donors: Mapping<(u32, AccountId), bool>,
-        /// DAO voting: Track votes (campaign_id, milestone_index, voter) -> vote_weight
-        milestone_votes: Mapping<(u32, u32, AccountId), Balance>,
+        /// Aggregated donations per campaign: (campaign_id, donor) -> total donated
+        donor_totals: Mapping<(u32, AccountId), Balance>,
+        /// DAO voting: Track votes (campaign_id, milestone_index, voter) -> (vote_weight, approve)
+        #[allow(clippy::type_complexity)]
+        milestone_votes: Mapping<(u32, u32, AccountId), (Balance, bool)>,
         /// Treasury account for platform fees
         treasury_account: AccountId,
+        /// Free-form tags per campaign: campaign_id -> tags
+        campaign_tags: Mapping<u32, Vec<String>>,
+        /// Tag index for exact-tag lookup: tag -> campaign IDs
+        tag_index: Mapping<String, Vec<u32>>,
+        /// Minimum campaign goal required to add milestones (0 disables the check)
+        min_goal_for_milestones: Balance,
+        /// Donor-chosen refund destinations: (campaign_id, donor) -> destination
+        refund_addresses: Mapping<(u32, AccountId), AccountId>,
+        /// Total platform fees sent to the treasury to date
+        total_fees_collected: Balance,
+        /// Address of the subscription manager contract, for donor subscription lookups
+        subscription_manager: Option<AccountId>,
+        /// DAO voting: Vote log in voting order (campaign_id, milestone_index, vote_index) -> (voter, cast_at)
+        #[allow(clippy::type_complexity)]
+        milestone_voters: Mapping<(u32, u32, u32), (AccountId, Timestamp)>,
+        /// DAO voting: Number of votes cast per (campaign_id, milestone_index); the next free vote index
+        milestone_voter_count: Mapping<(u32, u32), u32>,
+        /// Refunds whose transfer failed, claimable later: account -> amount
+        refund_credits: Mapping<AccountId, Balance>,
+        /// Platform fee taken from each donation, in basis points
+        fee_bps: u32,
+        /// Number of campaigns currently in the `Active` state
+        active_count: u32,
+        /// Platform fees held by the contract until the admin collects them
+        accrued_fees: Balance,
+        /// Minimum time milestone voting stays open before funds can be released
+        min_voting_duration: u64,
+        /// Fee charged for creating a campaign (0 = free creation)
+        campaign_creation_fee: Balance,
+        /// Grand total donated by each account across all campaigns, less refunds
+        donor_total_contributions: Mapping<AccountId, Balance>,
+        /// Whether the contract is being retired (enables `emergency_withdraw`).
+        deprecated: bool,
+        /// Whether donations are paused (changed through the admin timelock).
+        paused: bool,
+        /// Timelocked admin actions waiting to be executed, with their ETA.
+        queued_actions: Mapping<u32, (AdminAction, Timestamp)>,
+        /// The ID the next queued admin action gets.
+        next_action_id: u32,
+        /// Reduced fees for repeat donors: `(lifetime_threshold, fee_bps)`, ascending.
+        fee_discount_tiers: Vec<(Balance, u32)>,
+        /// Donations kept in detail per campaign; older ones are archived (0 = unlimited).
+        max_donation_history: u32,
+        /// Per-donor aggregate of archived donations: `(amount, latest_timestamp)`.
+        #[allow(clippy::type_complexity)]
+        archived_donor_totals: Mapping<(u32, AccountId), (Balance, Timestamp)>,
+        /// Whether admin and treasury are set up; false after `new_uninitialized` until the
+        /// deployer calls `initialize`.
+        initialized: bool,
+        /// Total refunded (or redirected) per campaign
+        campaign_refunded: Mapping<u32, Balance>,
+        /// Pro-rata refund state per insolvent campaign: (remaining available, remaining owed)
+        pro_rata_refunds: Mapping<u32, (Balance, Balance)>,
+        /// Campaign IDs per beneficiary, in creation order
+        beneficiary_index: Mapping<AccountId, Vec<u32>>,
+        /// Cached `campaign_liability` per campaign, kept in sync by `save_campaign`
+        campaign_liabilities: Mapping<u32, Balance>,
+        /// Sum of `campaign_liabilities`, so `get_liabilities` needn't scan every campaign
+        total_campaign_liabilities: Balance,
+        /// Pools of matching rounds that haven't been distributed yet
+        undistributed_round_pools: Balance,
+        /// Campaigns whose NFT receipts are minted soulbound
+        soulbound_receipt_campaigns: Mapping<u32, bool>,
+        /// Per campaign, the QF sum of roots of its archived donations
+        archived_qf_roots: Mapping<u32, u128>,
+        /// Donors with an archived donation that counts for QF, per campaign
+        archived_qf_donors: Mapping<(u32, AccountId), bool>,
+        /// Number of `archived_qf_donors` per campaign
+        archived_qf_donor_count: Mapping<u32, u32>,
+        /// Donations made while a milestone vote was open: (campaign_id, milestone_index,
+        /// donor) -> (the vote's `activated_at`, amount), so `vote_weight_at` is O(1)
+        #[allow(clippy::type_complexity)]
+        late_donations: Mapping<(u32, u32, AccountId), (Timestamp, Balance)>,
     }
 
     /// Minimum donation amount to prevent dust spam (0.001 DOT = 1,000,000 planck)
     const MIN_DONATION: Balance = 1_000_000;
 
+    /// Default platform fee (3%)
+    const DEFAULT_FEE_BPS: u32 = 300;
+
+    /// Maximum platform fee the admin may set (10%)
+    const MAX_FEE_BPS: u32 = 1000;
+
+    /// Time after a campaign's deadline before the admin may force it refundable (90 days)
+    const FORCE_REFUND_GRACE_PERIOD: Timestamp = 7_776_000_000;
+
+    /// Shortest refund window a campaign may set (7 days)
+    const MIN_REFUND_WINDOW: u64 = 604_800_000;
+
+    /// Maximum number of results returned by a single filtered query
+    const MAX_QUERY_LIMIT: u32 = 100;
+
+    /// Maximum number of campaigns a filtered query examines per call
+    const MAX_CAMPAIGN_SCAN: u32 = 500;
+
+    /// Minimum time between consecutive milestone deadlines (1 day)
+    const MIN_MILESTONE_GAP: Timestamp = 86_400_000;
+
+    /// Maximum vesting period for withdrawn funds (1 year)
+    const MAX_VESTING_DURATION: u64 = 31_536_000_000;
+
+    /// Default minimum milestone voting period (1 day in milliseconds)
+    const DEFAULT_MIN_VOTING_DURATION: u64 = 86_400_000;
+
+    /// Maximum number of milestones per campaign
+    const MAX_MILESTONES: usize = 10;
+
+    /// Maximum number of tags per campaign
+    const MAX_TAGS_PER_CAMPAIGN: usize = 5;
+
+    /// Maximum length of a single campaign tag (in bytes)
+    const MAX_TAG_LENGTH: usize = 32;
+
+    /// Above this many campaigns in a round, donations skip the matching estimate event
+    /// (it scans every campaign in the round)
+    const MAX_ESTIMATE_EVENT_CAMPAIGNS: usize = 100;
+
+    /// Maximum number of donations a campaign can have to be merged into another
+    const MAX_MERGE_DONATIONS: u32 = 500;
+
+    /// Gas limit for a campaign's success hook, so a misbehaving hook can't eat the donation's gas
+    #[cfg_attr(test, allow(dead_code))]
+    const SUCCESS_HOOK_GAS_LIMIT: u64 = 5_000_000_000;
+
+    /// Maximum number of fee discount tiers
+    const MAX_FEE_DISCOUNT_TIERS: usize = 10;
+
+    /// Minimum delay between queuing a timelocked admin action and its ETA (2 days)
+    const MIN_ADMIN_ACTION_DELAY: u64 = 172_800_000;
+
+    /// Maximum number of donations archived by a single donation, so lowering the
+    /// history cap spreads the work over later donations
+    const MAX_ARCHIVE_PER_DONATION: u32 = 5;
+
+    /// Funding exponent of plain linear matching (1.0, in thousandths)
+    const FUNDING_EXPONENT_LINEAR: u32 = 1_000;
+
+    /// Funding exponent of quadratic funding (2.0, in thousandths), the default for new rounds
+    const FUNDING_EXPONENT_QUADRATIC: u32 = 2_000;
+
+    /// Maximum donor-breadth bonus per doubling of unique donors (100%)
+    const MAX_DONOR_BREADTH_BPS: u32 = 10_000;
+
+    /// `2^(2^-i)` for i = 1..=32 in Q62 fixed point, used by `exp2_fixed`
+    const EXP2_FRACTION_TABLE: [u128; 32] = [
+        6521908912666391106, 5484249825272419511,
+        5029079263719320435, 4815862801830788490,
+        4712668792719003883, 4661903986662671289,
+        4636727017470743990, 4624189567668517720,
+        4617933561212708776, 4614808732577250068,
+        4613247111281068008, 4612466498810092974,
+        4612076242109103707, 4611881126141011236,
+        4611783571252412753, 4611734794581956353,
+        4611710406440186475, 4611698212417665819,
+        4611692115418496524, 4611689066921934630,
+        4611687542674409371, 4611686780550835663,
+        4611686399489096040, 4611686208958238036,
+        4611686113692811986, 4611686066060099699,
+        4611686042243743740, 4611686030335565806,
+        4611686024381476851, 4611686021404432376,
+        4611686019915910140, 4611686019171649022,
+    ];
+
     impl DonationPlatformV2 {
         /// Creates a new instance of the donation platform contract V2.
         ///
@@ -318,11 +771,62 @@ mod donation_platform_v2 {
                 matching_rounds: Mapping::default(),
                 round_count: 0,
                 unique_donors: Mapping::default(),
+                donor_totals: Mapping::default(),
                 milestone_votes: Mapping::default(),
                 treasury_account: Self::env().caller(),
+                campaign_tags: Mapping::default(),
+                tag_index: Mapping::default(),
+                min_goal_for_milestones: 0,
+                refund_addresses: Mapping::default(),
+                total_fees_collected: 0,
+                subscription_manager: None,
+                milestone_voters: Mapping::default(),
+                milestone_voter_count: Mapping::default(),
+                refund_credits: Mapping::default(),
+                fee_bps: DEFAULT_FEE_BPS,
+                active_count: 0,
+                accrued_fees: 0,
+                min_voting_duration: DEFAULT_MIN_VOTING_DURATION,
+                campaign_creation_fee: 0,
+                donor_total_contributions: Mapping::default(),
+                deprecated: false,
+                paused: false,
+                queued_actions: Mapping::default(),
+                next_action_id: 0,
+                fee_discount_tiers: Vec::new(),
+                max_donation_history: 0,
+                archived_donor_totals: Mapping::default(),
+                initialized: true,
+                campaign_refunded: Mapping::default(),
+                pro_rata_refunds: Mapping::default(),
+                beneficiary_index: Mapping::default(),
+                campaign_liabilities: Mapping::default(),
+                total_campaign_liabilities: 0,
+                undistributed_round_pools: 0,
+                soulbound_receipt_campaigns: Mapping::default(),
+                archived_qf_roots: Mapping::default(),
+                archived_qf_donors: Mapping::default(),
+                archived_qf_donor_count: Mapping::default(),
+                late_donations: Mapping::default(),
             }
         }
 
+        /// Creates a contract whose admin and treasury are chosen later.
+        ///
+        /// For deployments where the final admin (e.g. a multisig) doesn't exist yet.
+        /// Until the deployer calls `initialize`, the deployer acts as admin and
+        /// treasury, and no one else can initialize the contract.
+        ///
+        /// # Returns
+        ///
+        /// A new instance of the `DonationPlatformV2` contract awaiting `initialize`.
+        #[ink(constructor)]
+        pub fn new_uninitialized() -> Self {
+            let mut contract = Self::new();
+            contract.initialized = false;
+            contract
+        }
+
         /// Migrates the contract from V1 to V2.
         ///
         /// This constructor is intended to be called by the proxy contract when upgrading
@@ -354,8 +858,43 @@ mod donation_platform_v2 {
                 matching_rounds: Mapping::default(),
                 round_count: 0,
                 unique_donors: Mapping::default(),
+                donor_totals: Mapping::default(),
                 milestone_votes: Mapping::default(),
                 treasury_account: Self::env().caller(),
+                campaign_tags: Mapping::default(),
+                tag_index: Mapping::default(),
+                min_goal_for_milestones: 0,
+                refund_addresses: Mapping::default(),
+                total_fees_collected: 0,
+                subscription_manager: None,
+                milestone_voters: Mapping::default(),
+                milestone_voter_count: Mapping::default(),
+                refund_credits: Mapping::default(),
+                fee_bps: DEFAULT_FEE_BPS,
+                active_count: 0,
+                accrued_fees: 0,
+                min_voting_duration: DEFAULT_MIN_VOTING_DURATION,
+                campaign_creation_fee: 0,
+                donor_total_contributions: Mapping::default(),
+                deprecated: false,
+                paused: false,
+                queued_actions: Mapping::default(),
+                next_action_id: 0,
+                fee_discount_tiers: Vec::new(),
+                max_donation_history: 0,
+                archived_donor_totals: Mapping::default(),
+                initialized: true,
+                campaign_refunded: Mapping::default(),
+                pro_rata_refunds: Mapping::default(),
+                beneficiary_index: Mapping::default(),
+                campaign_liabilities: Mapping::default(),
+                total_campaign_liabilities: 0,
+                undistributed_round_pools: 0,
+                soulbound_receipt_campaigns: Mapping::default(),
+                archived_qf_roots: Mapping::default(),
+                archived_qf_donors: Mapping::default(),
+                archived_qf_donor_count: Mapping::default(),
+                late_donations: Mapping::default(),
             }
         }
 
@@ -384,7 +923,8 @@ mod donation_platform_v2 {
         /// # Errors
         ///
         /// Returns `Error` if any of the input parameters are invalid (e.g., empty title,
-        /// zero goal, deadline in the past).
+        /// zero goal, deadline in the past), or `InsufficientFunds` if a creation fee is
+        /// set (use `create_campaign_paid` instead).
         #[ink(message)]
         pub fn create_campaign(
             &mut self,
@@ -393,6 +933,101 @@ mod donation_platform_v2 {
             goal: Balance,
             deadline: Timestamp,
             beneficiary: AccountId,
+        ) -> Result<u32, Error> {
+            if self.campaign_creation_fee > 0 {
+                return Err(Error::InsufficientFunds); // Reusing - means creation fee required
+            }
+            self.process_campaign_creation(title, description, goal, deadline, beneficiary)
+        }
+
+        /// Creates a new campaign whose success is reported to an integrator contract.
+        ///
+        /// When the campaign becomes `Successful`, `on_campaign_success(campaign_id)` is
+        /// called on `success_hook` with a bounded gas limit. The call is fire-and-forget:
+        /// if it fails, a `SuccessHookFailed` event is emitted instead.
+        ///
+        /// # Arguments
+        ///
+        /// The same as `create_campaign`, plus:
+        /// * `success_hook` - The contract to notify, if any.
+        ///
+        /// # Returns
+        ///
+        /// The same as `create_campaign`.
+        #[ink(message)]
+        pub fn create_campaign_with_hook(
+            &mut self,
+            title: String,
+            description: String,
+            goal: Balance,
+            deadline: Timestamp,
+            beneficiary: AccountId,
+            success_hook: Option<AccountId>,
+        ) -> Result<u32, Error> {
+            let campaign_id = self.create_campaign(title, description, goal, deadline, beneficiary)?;
+
+            if success_hook.is_some() {
+                let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+                campaign.success_hook = success_hook;
+                self.save_campaign(campaign_id, &campaign);
+            }
+
+            Ok(campaign_id)
+        }
+
+        /// Creates a new campaign, paying the campaign creation fee.
+        ///
+        /// The transferred value must equal `campaign_creation_fee` exactly. The fee
+        /// accrues for the treasury like donation fees. On success, `CampaignCreated` and
+        /// (for a non-zero fee) `CreationFeePaid` events are emitted.
+        ///
+        /// # Arguments
+        ///
+        /// The same as `create_campaign`.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(u32)`: The ID of the newly created campaign.
+        /// - `Err(Error)`: `InsufficientFunds` if the transferred value doesn't match the
+        ///   fee, or any error from `create_campaign`.
+        #[ink(message, payable)]
+        pub fn create_campaign_paid(
+            &mut self,
+            title: String,
+            description: String,
+            goal: Balance,
+            deadline: Timestamp,
+            beneficiary: AccountId,
+        ) -> Result<u32, Error> {
+            let fee = self.env().transferred_value();
+            if fee != self.campaign_creation_fee {
+                return Err(Error::InsufficientFunds);
+            }
+
+            let campaign_id = self.process_campaign_creation(title, description, goal, deadline, beneficiary)?;
+
+            if fee > 0 {
+                self.accrued_fees = self.accrued_fees.checked_add(fee).ok_or(Error::InsufficientFunds)?;
+                self.total_fees_collected = self.total_fees_collected.saturating_add(fee);
+
+                self.env().emit_event(CreationFeePaid {
+                    campaign_id,
+                    payer: self.env().caller(),
+                    amount: fee,
+                });
+            }
+
+            Ok(campaign_id)
+        }
+
+        /// The internal logic for creating a campaign, shared by the free and paid variants.
+        fn process_campaign_creation(
+            &mut self,
+            title: String,
+            description: String,
+            goal: Balance,
+            deadline: Timestamp,
+            beneficiary: AccountId,
         ) -> Result<u32, Error> {
             let caller = self.env().caller();
             let current_time = self.env().block_timestamp();
@@ -433,14 +1068,47 @@ mod donation_platform_v2 {
                 matching_amount: 0,
                 milestones: Vec::new(),
                 uses_milestones: false,
+                matching_withdrawn: false,
+                refundable: false,
+                auto_activate_first_milestone: false,
+                unique_donor_count: 0,
+                fees_paid: 0,
+                vesting_duration: 0,
+                vesting_start: 0,
+                vesting_total: 0,
+                vesting_withdrawn: 0,
+                withdrawal_cooldown: 0,
+                last_withdrawal_at: None,
+                donations_frozen: false,
+                soft_cap: 0,
+                merged_into: None,
+                success_hook: None,
+                archived_donation_count: 0,
+                archived_donation_total: 0,
+                max_donors: 0,
+                failed_at: None,
+                refund_window: 0,
+                refund_fallback: None,
             };
 
-            // Store campaign and initialize empty donations list
-            self.campaigns.insert(campaign_id, &campaign);
-            self.campaign_donations.insert(campaign_id, &Vec::<Donation>::new());
+            // Store campaign (donations are stored per index as they arrive)
+            self.save_campaign(campaign_id, &campaign);
+
+            let mut beneficiary_ids = self.beneficiary_index.get(beneficiary).unwrap_or_default();
+            beneficiary_ids.push(campaign_id);
+            self.beneficiary_index.insert(beneficiary, &beneficiary_ids);
+
+            // Enroll in the current matching round
+            if let Some(round_id) = self.current_round {
+                if let Some(mut round) = self.matching_rounds.get(round_id) {
+                    round.campaign_ids.push(campaign_id);
+                    self.matching_rounds.insert(round_id, &round);
+                }
+            }
 
-            // Increment campaign counter
+            // Increment campaign counters
             self.campaign_count += 1;
+            self.active_count += 1;
 
             // Emit event
             self.env().emit_event(CampaignCreated {
@@ -509,6 +1177,9 @@ mod donation_platform_v2 {
         /// a native token value. The donation is only accepted if the campaign is active
         /// and has not passed its deadline.
         ///
+        /// For campaigns with a soft cap, only the part up to the goal (the hard cap) is
+        /// accepted and the excess is sent back to the donor in the same call.
+        ///
         /// On successful donation, a `DonationReceived` event is emitted.
         ///
         /// # Arguments
@@ -517,14 +1188,14 @@ mod donation_platform_v2 {
         ///
         /// # Returns
         ///
-        /// - `Ok(())`: If the donation was successful.
+        /// - `Ok(u32)`: The index of the new donation within the campaign.
         /// - `Err(Error)`: An error variant indicating why the donation failed, such as
         ///   `CampaignNotFound`, `CampaignNotActive`, or `DeadlinePassed`.
         ///
         /// # Errors
         /// Returns `Error` if the campaign is not in a donatable state.
         #[ink(message, payable)]
-        pub fn donate(&mut self, campaign_id: u32) -> Result<(), Error> {
+        pub fn donate(&mut self, campaign_id: u32) -> Result<u32, Error> {
             // Check and acquire lock
             if self.locked {
                 return Err(Error::ReentrantCall);
@@ -534,7 +1205,7 @@ mod donation_platform_v2 {
             // Execute donation logic in a closure to ensure unlock happens
             let result = (|| {
                 let donation_amount = self.env().transferred_value();
-                self.process_donation(campaign_id, donation_amount)
+                self.process_donation(campaign_id, donation_amount).map(|(index, _)| index)
             })();
 
             // Always unlock before returning
@@ -542,6 +1213,79 @@ mod donation_platform_v2 {
             result
         }
 
+        /// Donates to a campaign and reports the donor's subscription status for a creator.
+        ///
+        /// The donation works exactly like `donate`. Afterwards the subscription manager's
+        /// `check_subscription` is queried (read-only, no payment) so the returned receipt
+        /// tells the UI whether the donor already subscribes to the campaign's creator.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The ID of the campaign to donate to.
+        /// * `creator` - The creator whose subscription status should be reported.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(DonationReceipt)`: If the donation was successful.
+        /// - `Err(Error)`: The same errors as `donate`.
+        #[ink(message, payable)]
+        pub fn donate_and_notify_subscription(
+            &mut self,
+            campaign_id: u32,
+            creator: AccountId,
+        ) -> Result<DonationReceipt, Error> {
+            // Check and acquire lock
+            if self.locked {
+                return Err(Error::ReentrantCall);
+            }
+            self.locked = true;
+
+            let donation_amount = self.env().transferred_value();
+            let result = self.process_donation(campaign_id, donation_amount);
+
+            // Always unlock before returning
+            self.locked = false;
+            let (_, accepted) = result?;
+
+            let donor = self.env().caller();
+            let is_subscriber = self
+                .subscription_manager
+                .and_then(|manager| self.query_subscription(manager, donor, creator));
+
+            Ok(DonationReceipt {
+                campaign_id,
+                donor,
+                amount: accepted,
+                is_subscriber,
+            })
+        }
+
+        /// Calls `check_subscription` on the subscription manager.
+        #[cfg(not(test))]
+        fn query_subscription(&self, manager: AccountId, subscriber: AccountId, creator: AccountId) -> Option<bool> {
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+
+            build_call::<ink::env::DefaultEnvironment>()
+                .call_v1(manager)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("check_subscription")))
+                        .push_arg(subscriber)
+                        .push_arg(creator)
+                )
+                .returns::<bool>()
+                .try_invoke()
+                .ok()
+                .and_then(|result| result.ok())
+        }
+
+        /// The off-chain test environment can't call other contracts, so tests use a mock.
+        #[cfg(test)]
+        fn query_subscription(&self, manager: AccountId, subscriber: AccountId, creator: AccountId) -> Option<bool> {
+            tests::mock_check_subscription(manager, subscriber, creator)
+        }
+
         /// The internal logic for processing a donation.
         ///
         /// This private function is called by `donate` and handles the core logic of
@@ -551,10 +1295,17 @@ mod donation_platform_v2 {
         /// # Arguments
         /// * `campaign_id` - The ID of the campaign.
         /// * `donation_amount` - The amount of the donation.
-        fn process_donation(&mut self, campaign_id: u32, donation_amount: Balance) -> Result<(), Error> {
+        ///
+        /// Returns the index of the new donation within the campaign and the amount
+        /// accepted, which is less than `donation_amount` when a hard cap was reached.
+        fn process_donation(&mut self, campaign_id: u32, donation_amount: Balance) -> Result<(u32, Balance), Error> {
             let caller = self.env().caller();
             let current_time = self.env().block_timestamp();
 
+            if self.paused {
+                return Err(Error::ContractPaused);
+            }
+
             // Input validation
             if donation_amount < MIN_DONATION {
                 return Err(Error::InvalidDonationAmount);
@@ -563,125 +1314,489 @@ mod donation_platform_v2 {
                 return Err(Error::InvalidDonationAmount);
             }
 
-            // Calculate fee (3%)
-            let fee = donation_amount.checked_mul(3).ok_or(Error::InvalidDonationAmount)?
-                .checked_div(100).ok_or(Error::InvalidDonationAmount)?;
-            
-            // Transfer fee to treasury
-            if fee > 0 {
-                if self.env().transfer(self.treasury_account, fee).is_err() {
-                    return Err(Error::TransferFailed);
-                }
-            }
-
-            // Get campaign
+            // Get campaign (before any value moves, so a bad ID costs nothing)
             let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
 
-            // Check campaign state
-            if campaign.state != CampaignState::Active {
+            // Check campaign state (a campaign past its soft cap keeps filling up to the goal)
+            let filling_to_hard_cap = campaign.state == CampaignState::Successful
+                && campaign.raised < campaign.goal;
+            if campaign.state != CampaignState::Active && !filling_to_hard_cap {
+                if campaign.state == CampaignState::Successful && campaign.soft_cap > 0 {
+                    return Err(Error::GoalReached);
+                }
                 return Err(Error::CampaignNotActive);
             }
+            if campaign.donations_frozen {
+                return Err(Error::DonationsFrozen);
+            }
 
             // Check deadline
             if current_time > campaign.deadline {
-                campaign.state = CampaignState::Failed;
-                self.campaigns.insert(campaign_id, &campaign);
+                if campaign.state == CampaignState::Active {
+                    self.set_state(&mut campaign, CampaignState::Failed);
+                    self.save_campaign(campaign_id, &campaign);
+                }
                 return Err(Error::DeadlinePassed);
             }
 
+            // Track unique donor for quadratic funding, within the campaign's donor cap
+            let donor_key = (campaign_id, caller);
+            let is_new_donor = !self.unique_donors.get(donor_key).unwrap_or(false);
+            if is_new_donor && campaign.max_donors > 0 && campaign.unique_donor_count >= campaign.max_donors {
+                return Err(Error::DonorLimitReached);
+            }
+
+            // Past a hard cap only the part up to it is accepted; the rest is returned below
+            let offered = donation_amount;
+            let donation_amount = if campaign.soft_cap > 0 {
+                offered.min(campaign.goal.saturating_sub(campaign.raised))
+            } else {
+                offered
+            };
+            let excess = offered - donation_amount;
+            // Don't record a dust donation just below the cap
+            if donation_amount < MIN_DONATION {
+                return Err(Error::GoalReached);
+            }
+
+            // Calculate fee (fee_bps basis points, discounted for repeat donors)
+            let fee = self.fee_for(caller, donation_amount);
+
+            // Accrue fee for the treasury (paid out via `collect_fees`)
+            if fee > 0 {
+                self.accrued_fees = self.accrued_fees.checked_add(fee).ok_or(Error::InvalidDonationAmount)?;
+                self.total_fees_collected = self.total_fees_collected.saturating_add(fee);
+            }
+
             // Record donation
             let donation = Donation {
                 donor: caller,
                 amount: donation_amount,
                 timestamp: current_time,
+                owner_contribution: false,
+                fee,
             };
 
             // Update campaign raised amount with overflow check
+            let donation_index = campaign.donation_count;
             campaign.raised = campaign.raised.checked_add(donation_amount)
                 .ok_or(Error::InvalidDonationAmount)?;
+            campaign.fees_paid = campaign.fees_paid.checked_add(fee)
+                .ok_or(Error::InvalidDonationAmount)?;
             campaign.donation_count = campaign.donation_count.checked_add(1)
                 .ok_or(Error::InvalidDonationAmount)?;
 
-            // Check if goal reached
-            if campaign.raised >= campaign.goal {
-                campaign.state = CampaignState::Successful;
+            if is_new_donor {
+                campaign.unique_donor_count = campaign.unique_donor_count.saturating_add(1);
             }
 
-            // Update campaign
-            self.campaigns.insert(campaign_id, &campaign);
+            self.archive_old_donations(campaign_id, &mut campaign);
 
-            // Add donation to campaign donations
-            let mut donations = self.campaign_donations.get(campaign_id).unwrap_or_default();
-            donations.push(donation);
-            self.campaign_donations.insert(campaign_id, &donations);
+            // Check if goal reached and update campaign
+            self.store_campaign_progress(campaign_id, &mut campaign, current_time);
 
-            // Track unique donor for quadratic funding
-            let donor_key = (campaign_id, caller);
-            if !self.unique_donors.get(donor_key).unwrap_or(false) {
+            // Store donation under its own index (O(1), no vector rewrite)
+            self.campaign_donations.insert((campaign_id, donation_index), &donation);
+
+            if is_new_donor {
                 self.unique_donors.insert(donor_key, &true);
             }
 
+            // Aggregate donor total so refunds and voting don't rescan donations
+            let donor_total = self.donor_totals.get(donor_key).unwrap_or(0)
+                .checked_add(donation_amount)
+                .ok_or(Error::InvalidDonationAmount)?;
+            self.donor_totals.insert(donor_key, &donor_total);
+            self.record_late_donation(campaign_id, &campaign, caller, donation_amount, current_time);
+
+            let grand_total = self.donor_total_contributions.get(caller).unwrap_or(0)
+                .saturating_add(donation_amount);
+            self.donor_total_contributions.insert(caller, &grand_total);
+
+            // Mint NFT receipt if NFT minting is enabled
+            let mut nft_token_id = None;
+            if self.nft_enabled {
+                if let Some(nft_address) = self.nft_contract {
+                    match self.mint_nft_receipt(nft_address, caller, campaign_id, &campaign, donation_amount, current_time) {
+                        Ok(token_id) => {
+                            nft_token_id = Some(token_id);
+                            self.env().emit_event(NftReceiptMinted {
+                                campaign_id,
+                                donor: caller,
+                                nft_token_id: token_id,
+                                amount: donation_amount,
+                            });
+                        }
+                        // Log if NFT minting fails, but don't fail the donation
+                        Err(error_code) => {
+                            self.env().emit_event(NftMintingFailed {
+                                campaign_id,
+                                donor: caller,
+                                error_code,
+                            });
+                        }
+                    }
+                }
+            }
+
             // Emit event
             self.env().emit_event(DonationReceived {
                 campaign_id,
                 donor: caller,
                 amount: donation_amount,
+                deadline: campaign.deadline,
+                donation_index,
+                nft_minted: nft_token_id.is_some(),
+                nft_token_id,
             });
 
-            // Mint NFT receipt if NFT minting is enabled
-            if self.nft_enabled {
-                if let Some(nft_address) = self.nft_contract {
-                    // Call NFT contract to mint donation receipt
-                    use ink::env::call::{build_call, ExecutionInput, Selector};
-                    
-                    let mint_result = build_call::<ink::env::DefaultEnvironment>()
-                        .call_v1(nft_address)
-                        .gas_limit(0) // Use all available gas
-                        .transferred_value(0)
-                        .exec_input(
-                            ExecutionInput::new(Selector::new(ink::selector_bytes!("mint_donation_receipt")))
-                                .push_arg(caller) // to
-                                .push_arg(campaign_id) // campaign_id
-                                .push_arg(&campaign.title) // campaign_title
-                                .push_arg(donation_amount) // amount
-                                .push_arg(current_time) // timestamp
-                        )
-                        .returns::<Result<u128, u8>>()
-                        .try_invoke();
-
-                    // Log if NFT minting fails, but don't fail the donation
-                    if let Err(_e) = mint_result {
-                        self.env().emit_event(NftMintingFailed {
-                            campaign_id,
-                            donor: caller,
-                            error_code: 1,
-                        });
-                    }
+            self.emit_estimated_matching(campaign_id, campaign.matching_round);
+
+            if excess > 0 {
+                if self.env().transfer(caller, excess).is_err() {
+                    return Err(Error::WithdrawalFailed);
                 }
+                self.env().emit_event(DonationExcessRefunded {
+                    campaign_id,
+                    donor: caller,
+                    amount: excess,
+                });
             }
 
-            Ok(())
+            Ok((donation_index, donation_amount))
         }
 
-        /// Withdraws the funds from a successful or failed campaign.
-        /// This function can only be called by the campaign owner or the contract admin.
-        /// If the campaign was successful, the entire raised amount is transferred to the
-        /// beneficiary. If the campaign failed, this function does not transfer funds,
-        /// but marks the campaign as withdrawn.
-        ///
-        /// On successful withdrawal, a `FundsWithdrawn` event is emitted.
-        ///
-        /// # Arguments
+        /// Calls the NFT contract to mint a donation receipt, rated against the campaign goal.
         ///
-        /// * `campaign_id` - The ID of the campaign to withdraw funds from.
-        ///
-        /// # Returns
-        ///
-        /// - `Ok(())`: If the withdrawal process was completed successfully.
-        /// - `Err(Error)`: An error variant indicating failure, such as `NotCampaignOwner`,
-        ///   `GoalNotReached`, or `FundsAlreadyWithdrawn`.
+        /// Returns the new token ID, or an error code: the NFT contract's own code if it
+        /// rejected the mint, 1 if the call itself failed.
         ///
-        /// # Errors
+        /// Needs an NFT contract with `mint_donation_receipt_with_goal`. Upgrade a deployed
+        /// NFT contract before this platform, or every mint fails with code 1.
+        #[cfg(not(test))]
+        fn mint_nft_receipt(
+            &self,
+            nft_address: AccountId,
+            to: AccountId,
+            campaign_id: u32,
+            campaign: &Campaign,
+            amount: Balance,
+            timestamp: Timestamp,
+        ) -> Result<u128, u8> {
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+
+            let selector = if self.has_soulbound_receipts(campaign_id) {
+                ink::selector_bytes!("mint_soulbound_receipt")
+            } else {
+                ink::selector_bytes!("mint_donation_receipt_with_goal")
+            };
+            let mint_result = build_call::<ink::env::DefaultEnvironment>()
+                .call_v1(nft_address)
+                .gas_limit(0) // Use all available gas
+                .transferred_value(0)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(selector))
+                        .push_arg(to) // to
+                        .push_arg(campaign_id) // campaign_id
+                        .push_arg(&campaign.title) // campaign_title
+                        .push_arg(amount) // amount
+                        .push_arg(timestamp) // timestamp
+                        .push_arg(campaign.goal) // campaign_goal
+                )
+                .returns::<Result<u128, u8>>()
+                .try_invoke();
+
+            match mint_result {
+                Ok(Ok(result)) => result,
+                _ => Err(1),
+            }
+        }
+
+        /// The off-chain test environment can't call other contracts, so tests use a mock.
+        #[cfg(test)]
+        fn mint_nft_receipt(
+            &self,
+            nft_address: AccountId,
+            to: AccountId,
+            campaign_id: u32,
+            _campaign: &Campaign,
+            _amount: Balance,
+            _timestamp: Timestamp,
+        ) -> Result<u128, u8> {
+            tests::mock_mint_donation_receipt(nft_address, to, campaign_id, self.has_soulbound_receipts(campaign_id))
+        }
+
+        /// The amount a campaign must raise to succeed: its soft cap if set, else its goal.
+        fn success_threshold(campaign: &Campaign) -> Balance {
+            if campaign.soft_cap == 0 {
+                campaign.goal
+            } else {
+                campaign.soft_cap
+            }
+        }
+
+        /// Move the oldest donations out of detailed storage once a campaign keeps more
+        /// than `max_donation_history`, folding them into the campaign's and each
+        /// donor's archive aggregate. `raised` and `donor_totals` are untouched, so
+        /// totals and refunds stay exact, and the donation's QF root is kept in
+        /// `archived_qf_roots` so archiving can't push donors out of the QF score.
+        ///
+        /// Called after `donation_count` was bumped for a new donation that isn't
+        /// stored yet; it is never archived as long as the cap is at least 1.
+        fn archive_old_donations(&mut self, campaign_id: u32, campaign: &mut Campaign) {
+            if self.max_donation_history == 0 {
+                return;
+            }
+
+            let (exponent, min_qf_donation, start_time, _) = self.qf_round_params(campaign.matching_round);
+
+            for _ in 0..MAX_ARCHIVE_PER_DONATION {
+                let retained = campaign.donation_count.saturating_sub(campaign.archived_donation_count);
+                if retained <= self.max_donation_history {
+                    break;
+                }
+
+                let index = campaign.archived_donation_count;
+                campaign.archived_donation_count = campaign.archived_donation_count.saturating_add(1);
+                let Some(donation) = self.campaign_donations.get((campaign_id, index)) else {
+                    continue;
+                };
+                self.campaign_donations.remove((campaign_id, index));
+
+                campaign.archived_donation_total = campaign.archived_donation_total.saturating_add(donation.amount);
+                let donor_key = (campaign_id, donation.donor);
+                let (archived, _) = self.archived_donor_totals.get(donor_key).unwrap_or((0, 0));
+                self.archived_donor_totals.insert(
+                    donor_key,
+                    &(archived.saturating_add(donation.amount), donation.timestamp),
+                );
+
+                let root = Self::qf_root(&donation, exponent, min_qf_donation, start_time);
+                if root > 0 {
+                    let archived_roots = self.archived_qf_roots.get(campaign_id).unwrap_or(0);
+                    self.archived_qf_roots.insert(campaign_id, &archived_roots.saturating_add(root));
+                    if !self.archived_qf_donors.contains(donor_key) {
+                        self.archived_qf_donors.insert(donor_key, &true);
+                        let archived_donors = self.archived_qf_donor_count.get(campaign_id).unwrap_or(0);
+                        self.archived_qf_donor_count.insert(campaign_id, &archived_donors.saturating_add(1));
+                    }
+                }
+            }
+        }
+
+        /// Stores a campaign after its raised amount changed, marking it successful once
+        /// the success threshold is reached.
+        fn store_campaign_progress(&mut self, campaign_id: u32, campaign: &mut Campaign, current_time: Timestamp) {
+            let mut newly_funded = false;
+            let mut auto_activated = false;
+            if campaign.raised >= Self::success_threshold(campaign) {
+                if campaign.state != CampaignState::Successful {
+                    newly_funded = true;
+                    auto_activated = Self::auto_activate_first_milestone(campaign, current_time);
+                }
+                self.set_state(campaign, CampaignState::Successful);
+            }
+
+            self.save_campaign(campaign_id, campaign);
+
+            if auto_activated {
+                self.env().emit_event(MilestoneVotingActivated {
+                    campaign_id,
+                    milestone_index: 0,
+                });
+            }
+
+            if newly_funded {
+                self.notify_nft_campaign_funded(campaign_id);
+                self.notify_success_hook(campaign_id, campaign);
+            }
+        }
+
+        /// Calls the campaign's success hook, emitting `SuccessHookFailed` if it fails.
+        fn notify_success_hook(&self, campaign_id: u32, campaign: &Campaign) {
+            let Some(hook) = campaign.success_hook else {
+                return;
+            };
+
+            if !self.call_success_hook(hook, campaign_id) {
+                self.env().emit_event(SuccessHookFailed { campaign_id, hook });
+            }
+        }
+
+        /// Calls `on_campaign_success` on a hook contract.
+        #[cfg(not(test))]
+        fn call_success_hook(&self, hook: AccountId, campaign_id: u32) -> bool {
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+
+            matches!(
+                build_call::<ink::env::DefaultEnvironment>()
+                    .call_v1(hook)
+                    .gas_limit(SUCCESS_HOOK_GAS_LIMIT)
+                    .transferred_value(0)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("on_campaign_success")))
+                            .push_arg(campaign_id)
+                    )
+                    .returns::<()>()
+                    .try_invoke(),
+                Ok(Ok(()))
+            )
+        }
+
+        /// The off-chain test environment can't call other contracts, so tests use a mock.
+        #[cfg(test)]
+        fn call_success_hook(&self, hook: AccountId, campaign_id: u32) -> bool {
+            tests::mock_on_campaign_success(hook, campaign_id)
+        }
+
+        /// Tells the NFT contract a campaign reached its goal so its receipts show as funded.
+        ///
+        /// Best effort, like receipt minting: a failed call never blocks the donation.
+        fn notify_nft_campaign_funded(&self, campaign_id: u32) {
+            if !self.nft_enabled {
+                return;
+            }
+            let Some(nft_address) = self.nft_contract else {
+                return;
+            };
+
+            self.call_mark_campaign_funded(nft_address, campaign_id);
+        }
+
+        /// Calls `mark_campaign_funded` on the NFT contract, ignoring the outcome.
+        #[cfg(not(test))]
+        fn call_mark_campaign_funded(&self, nft_address: AccountId, campaign_id: u32) {
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+
+            let _ = build_call::<ink::env::DefaultEnvironment>()
+                .call_v1(nft_address)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("mark_campaign_funded")))
+                        .push_arg(campaign_id)
+                )
+                .returns::<Result<(), u8>>()
+                .try_invoke();
+        }
+
+        /// The off-chain test environment can't call other contracts, so tests use a mock.
+        #[cfg(test)]
+        fn call_mark_campaign_funded(&self, nft_address: AccountId, campaign_id: u32) {
+            tests::mock_mark_campaign_funded(nft_address, campaign_id)
+        }
+
+        /// Lets the campaign owner contribute to their own campaign, fee-free.
+        ///
+        /// The top-up counts towards the goal and is refundable like any donation, but it
+        /// is tagged as an owner contribution and excluded from quadratic funding so
+        /// owners can't farm matching with their own funds.
+        ///
+        /// On success, an `OwnerToppedUp` event is emitted.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The ID of the campaign to top up.
+        ///
+        /// # Errors
+        ///
+        /// Returns `Error::NotCampaignOwner` if the caller is not the owner, and the same
+        /// state and amount errors as `donate` otherwise.
+        #[ink(message, payable)]
+        pub fn owner_top_up(&mut self, campaign_id: u32) -> Result<(), Error> {
+            // Check and acquire lock
+            if self.locked {
+                return Err(Error::ReentrantCall);
+            }
+            self.locked = true;
+
+            let amount = self.env().transferred_value();
+            let result = self.process_owner_top_up(campaign_id, amount);
+
+            // Always unlock before returning
+            self.locked = false;
+            result
+        }
+
+        /// The internal logic for an owner top-up.
+        fn process_owner_top_up(&mut self, campaign_id: u32, amount: Balance) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let current_time = self.env().block_timestamp();
+
+            if amount < MIN_DONATION {
+                return Err(Error::InvalidDonationAmount);
+            }
+            if amount > 100_000_000_000_000 {
+                return Err(Error::InvalidDonationAmount);
+            }
+
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+
+            if caller != campaign.owner {
+                return Err(Error::NotCampaignOwner);
+            }
+            if campaign.state != CampaignState::Active {
+                return Err(Error::CampaignNotActive);
+            }
+            if current_time > campaign.deadline {
+                self.set_state(&mut campaign, CampaignState::Failed);
+                self.save_campaign(campaign_id, &campaign);
+                return Err(Error::DeadlinePassed);
+            }
+
+            let donation_index = campaign.donation_count;
+            campaign.raised = campaign.raised.checked_add(amount)
+                .ok_or(Error::InvalidDonationAmount)?;
+            campaign.donation_count = campaign.donation_count.checked_add(1)
+                .ok_or(Error::InvalidDonationAmount)?;
+
+            self.archive_old_donations(campaign_id, &mut campaign);
+            self.store_campaign_progress(campaign_id, &mut campaign, current_time);
+
+            self.campaign_donations.insert((campaign_id, donation_index), &Donation {
+                donor: caller,
+                amount,
+                timestamp: current_time,
+                owner_contribution: true,
+                fee: 0,
+            });
+
+            // Refundable like any other donation, but not a unique donor for QF
+            let donor_key = (campaign_id, caller);
+            let donor_total = self.donor_totals.get(donor_key).unwrap_or(0)
+                .checked_add(amount)
+                .ok_or(Error::InvalidDonationAmount)?;
+            self.donor_totals.insert(donor_key, &donor_total);
+            self.record_late_donation(campaign_id, &campaign, caller, amount, current_time);
+
+            self.env().emit_event(OwnerToppedUp {
+                campaign_id,
+                owner: caller,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Withdraws the funds from a successful or failed campaign.
+        /// This function can only be called by the campaign owner or the contract admin.
+        /// If the campaign was successful, the entire raised amount is transferred to the
+        /// beneficiary. If the campaign failed, this function does not transfer funds,
+        /// but marks the campaign as withdrawn.
+        ///
+        /// On successful withdrawal, a `FundsWithdrawn` event is emitted.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The ID of the campaign to withdraw funds from.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())`: If the withdrawal process was completed successfully.
+        /// - `Err(Error)`: An error variant indicating failure, such as `NotCampaignOwner`,
+        ///   `GoalNotReached`, or `FundsAlreadyWithdrawn`.
+        ///
+        /// # Errors
         /// Returns `Error` if the caller is not authorized or the campaign is not in a withdrawable state.
         #[ink(message)]
         pub fn withdraw_funds(&mut self, campaign_id: u32) -> Result<(), Error> {
@@ -722,6 +1837,13 @@ mod donation_platform_v2 {
                 return Err(Error::FundsAlreadyWithdrawn);
             }
 
+            // Funds of a force-refundable campaign belong to its donors
+            if campaign.refundable {
+                return Err(Error::CampaignFailed);
+            }
+
+            Self::check_withdrawal_cooldown(&campaign, current_time)?;
+
             // Check if campaign is successful or deadline has passed
             let is_successful = campaign.state == CampaignState::Successful;
             let deadline_passed = current_time > campaign.deadline;
@@ -732,23 +1854,42 @@ mod donation_platform_v2 {
 
             // Ensure there are funds to withdraw
             if campaign.raised == 0 && campaign.matching_amount == 0 {
-                campaign.state = CampaignState::Failed;
-                self.campaigns.insert(campaign_id, &campaign);
+                self.set_state(&mut campaign, CampaignState::Failed);
+                self.save_campaign(campaign_id, &campaign);
                 return Ok(());
             }
 
             // Calculate total to withdraw (donations + matching)
-            // Note: Donations already had 3% fee taken in real-time, but campaign.raised tracks GROSS.
-            // So we must subtract the fee from campaign.raised to get the NET amount available.
-            let fee_total = campaign.raised.checked_mul(3).ok_or(Error::WithdrawalFailed)?
-                .checked_div(100).ok_or(Error::WithdrawalFailed)?;
-            
-            let net_raised = campaign.raised.checked_sub(fee_total).ok_or(Error::WithdrawalFailed)?;
+            // Note: Donations already had the fee taken in real-time, but campaign.raised tracks GROSS.
+            // The fee rate can change over time, so subtract the fees actually paid to get the NET.
+            let net_raised = campaign.raised.checked_sub(campaign.fees_paid).ok_or(Error::WithdrawalFailed)?;
+
+            // Matching already paid out via `withdraw_matching` is not paid again
+            let matching_due = if campaign.matching_withdrawn { 0 } else { campaign.matching_amount };
 
             let total_amount = net_raised
-                .checked_add(campaign.matching_amount)
+                .checked_add(matching_due)
                 .ok_or(Error::WithdrawalFailed)?;
 
+            // Vesting campaigns lock the amount and release it via `withdraw_vested`
+            if campaign.vesting_duration > 0 {
+                self.set_state(&mut campaign, CampaignState::Withdrawn);
+                campaign.vesting_start = current_time;
+                campaign.vesting_total = total_amount;
+                if matching_due > 0 {
+                    campaign.matching_withdrawn = true;
+                }
+                self.save_campaign(campaign_id, &campaign);
+
+                self.env().emit_event(VestingStarted {
+                    campaign_id,
+                    amount: total_amount,
+                    duration: campaign.vesting_duration,
+                });
+
+                return Ok(());
+            }
+
             // Transfer funds to beneficiary (both donations and matching)
             if total_amount > 0 {
                 if self.env().transfer(campaign.beneficiary, total_amount).is_err() {
@@ -757,8 +1898,12 @@ mod donation_platform_v2 {
             }
 
             // Update campaign state
-            campaign.state = CampaignState::Withdrawn;
-            self.campaigns.insert(campaign_id, &campaign);
+            self.set_state(&mut campaign, CampaignState::Withdrawn);
+            if matching_due > 0 {
+                campaign.matching_withdrawn = true;
+            }
+            campaign.last_withdrawal_at = Some(current_time);
+            self.save_campaign(campaign_id, &campaign);
 
             // Emit event
             self.env().emit_event(FundsWithdrawn {
@@ -770,1636 +1915,8410 @@ mod donation_platform_v2 {
             Ok(())
         }
 
-        /// Withdraws funds from multiple campaigns in a single transaction.
-        /// Allows a user to withdraw funds from multiple owned campaigns in one batch,
-        /// saving on transaction fees.
-        ///
-        /// # Arguments
+        /// Sets a linear vesting period for the campaign's withdrawn funds (owner only).
         ///
-        /// * `campaign_ids` - A vector of campaign IDs to withdraw from.
+        /// With vesting, `withdraw_funds` doesn't pay the beneficiary at once: it starts
+        /// the schedule, and `withdraw_vested` pays out whatever has vested so far.
         ///
-        /// # Returns
+        /// # Arguments
         ///
-        /// - `Ok(BatchResult)`: A struct indicating the number of successful and failed
-        ///   withdrawals.
-        /// - `Err(Error)`: An error variant, such as `BatchSizeTooLarge`.
+        /// * `campaign_id` - The ID of the campaign.
+        /// * `duration` - The vesting period in milliseconds (0 disables vesting).
         ///
         /// # Errors
         ///
-        /// Returns `Error::BatchSizeTooLarge` if the input vector exceeds the
-        /// maximum allowed batch size. Each individual withdrawal may also fail with
-        /// errors reported in the `failed` count of the `BatchResult`.
+        /// Returns `NotCampaignOwner`, `CampaignNotActive` once the campaign is no longer
+        /// active, or `InvalidDeadline` if the duration exceeds `MAX_VESTING_DURATION`.
         #[ink(message)]
-        pub fn withdraw_funds_batch(&mut self, campaign_ids: Vec<u32>) -> Result<BatchResult, Error> {
-            if campaign_ids.len() > self.max_batch_size as usize {
-                return Err(Error::BatchSizeTooLarge);
-            }
+        pub fn set_vesting_duration(&mut self, campaign_id: u32, duration: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
 
-            // Check and acquire lock ONCE for the entire batch operation
-            if self.locked {
-                return Err(Error::ReentrantCall);
+            if caller != campaign.owner {
+                return Err(Error::NotCampaignOwner);
+            }
+            if campaign.state != CampaignState::Active {
+                return Err(Error::CampaignNotActive);
+            }
+            if duration > MAX_VESTING_DURATION {
+                return Err(Error::InvalidDeadline); // Reusing error - means invalid vesting period
             }
-            self.locked = true;
-
-            // Execute batch withdrawal logic
-            let result = (|| {
-                let mut successful = 0;
-                let mut failed = 0;
-                let mut success_ids = Vec::new();
-
-                for campaign_id in campaign_ids {
-                    // Call internal process_withdrawal to avoid double-locking
-                    match self.process_withdrawal(campaign_id) {
-                        Ok(_) => {
-                            successful += 1;
-                            success_ids.push(campaign_id);
-                        }
-                        Err(_) => {
-                            failed += 1;
-                        }
-                    }
-                }
 
-                Ok(BatchResult {
-                    successful,
-                    failed,
-                    success_ids,
-                })
-            })();
+            campaign.vesting_duration = duration;
+            self.save_campaign(campaign_id, &campaign);
 
-            // Always unlock before returning
-            self.locked = false;
-            result
+            Ok(())
         }
 
-        /// Cancels an active campaign.
-        ///
-        /// This function allows a campaign owner (or admin) to cancel their campaign before
-        /// the deadline. Once cancelled, the campaign state changes to `Failed`, and donors
-        /// can claim refunds.
+        /// Pays the beneficiary the vested-but-unwithdrawn part of a vesting campaign.
         ///
-        /// On success, a `CampaignCancelled` event is emitted.
+        /// Can be called by the campaign owner or the admin. On success, a
+        /// `FundsWithdrawn` event is emitted with the amount paid.
         ///
         /// # Arguments
         ///
-        /// * `campaign_id` - The ID of the campaign to cancel.
-        ///
-        /// # Returns
-        ///
-        /// - `Ok(())`: If the cancellation was successful.
-        /// - `Err(Error)`: If the caller is not authorized or the campaign cannot be cancelled.
+        /// * `campaign_id` - The ID of the campaign.
         ///
         /// # Errors
         ///
-        /// Returns `Error::NotCampaignOwner` if the caller is not the owner or admin,
-        /// or `Error::CampaignNotActive` if the campaign is not in an active state.
+        /// Returns `NotCampaignOwner`, `GoalNotReached` if vesting hasn't started, or
+        /// `InsufficientFunds` if nothing new has vested.
         #[ink(message)]
-        pub fn cancel_campaign(&mut self, campaign_id: u32) -> Result<(), Error> {
+        pub fn withdraw_vested(&mut self, campaign_id: u32) -> Result<(), Error> {
+            // Check and acquire lock
+            if self.locked {
+                return Err(Error::ReentrantCall);
+            }
+            self.locked = true;
+
+            let result = self.process_vested_withdrawal(campaign_id);
+
+            // Always unlock before returning
+            self.locked = false;
+            result
+        }
+
+        /// The internal logic for paying out vested funds.
+        fn process_vested_withdrawal(&mut self, campaign_id: u32) -> Result<(), Error> {
             let caller = self.env().caller();
             let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
 
-            // Only owner or admin can cancel
             if caller != campaign.owner && caller != self.admin {
                 return Err(Error::NotCampaignOwner);
             }
 
-            // Can only cancel active campaigns
-            if campaign.state != CampaignState::Active {
-                return Err(Error::CampaignNotActive);
+            // Vesting starts when `withdraw_funds` is called
+            if campaign.vesting_duration == 0 || campaign.state != CampaignState::Withdrawn {
+                return Err(Error::GoalNotReached); // Reusing error - means vesting not started
             }
 
-            // Mark campaign as failed
-            campaign.state = CampaignState::Failed;
-            self.campaigns.insert(campaign_id, &campaign);
+            let current_time = self.env().block_timestamp();
+            Self::check_withdrawal_cooldown(&campaign, current_time)?;
 
-            // Emit event
-            self.env().emit_event(CampaignCancelled {
-                campaign_id,
-                cancelled_by: caller,
-            });
+            let due = Self::vested_amount(&campaign, current_time)
+                .saturating_sub(campaign.vesting_withdrawn);
+            if due == 0 {
+                return Err(Error::InsufficientFunds);
+            }
 
-            Ok(())
-        }
+            if self.env().transfer(campaign.beneficiary, due).is_err() {
+                return Err(Error::WithdrawalFailed);
+            }
 
-        /// Claims a refund for donations made to a failed campaign.
-        ///
-        /// When a campaign fails (either by missing its deadline or being cancelled),
-        /// donors can call this function to receive a full refund of their contributions.
-        /// Each donor can only claim their refund once.
+            campaign.vesting_withdrawn = campaign.vesting_withdrawn.saturating_add(due);
+            campaign.last_withdrawal_at = Some(current_time);
+            self.save_campaign(campaign_id, &campaign);
+
+            self.env().emit_event(FundsWithdrawn {
+                campaign_id,
+                beneficiary: campaign.beneficiary,
+                amount: due,
+            });
+
+            Ok(())
+        }
+
+        /// Sets the minimum time between payouts to the beneficiary (owner only).
         ///
-        /// On success, a `RefundClaimed` event is emitted.
+        /// Applies to `withdraw_funds`, `withdraw_vested` and milestone releases, as a
+        /// brake on repeated payouts from a compromised owner account. It can only be
+        /// changed while the campaign is active.
         ///
         /// # Arguments
         ///
-        /// * `campaign_id` - The ID of the failed campaign to claim a refund from.
-        ///
-        /// # Returns
-        ///
-        /// - `Ok(())`: If the refund was successfully processed.
-        /// - `Err(Error)`: If the refund cannot be claimed.
+        /// * `campaign_id` - The ID of the campaign.
+        /// * `cooldown` - The cooldown in milliseconds (0 disables it).
         ///
         /// # Errors
         ///
-        /// Returns `Error::CampaignNotFailed` if the campaign is not in a failed state,
-        /// `Error::NoDonationFound` if the caller has no donations,
-        /// or `Error::RefundAlreadyClaimed` if the refund was already claimed.
+        /// Returns `NotCampaignOwner`, or `CampaignNotActive` once the campaign is no
+        /// longer active.
         #[ink(message)]
-        pub fn claim_refund(&mut self, campaign_id: u32) -> Result<(), Error> {
-            // Check and acquire lock
-            if self.locked {
-                return Err(Error::ReentrantCall);
-            }
-            self.locked = true;
-
-            // Execute refund logic in a closure to ensure unlock happens
-            let result = (|| {
-                let caller = self.env().caller();
-                let campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
-
-                // Only allow refunds for failed campaigns
-                if campaign.state != CampaignState::Failed {
-                    return Err(Error::CampaignFailed);
-                }
-
-                // Check if already claimed
-                if self.refund_claimed.get((campaign_id, caller)).unwrap_or(false) {
-                    return Err(Error::RefundAlreadyClaimed);
-                }
-
-                // Calculate total donation amount for this donor
-                let donations = self.campaign_donations.get(campaign_id).unwrap_or_default();
-                let mut refund_amount: Balance = 0;
-                
-                for donation in &donations {
-                    if donation.donor == caller {
-                        refund_amount = refund_amount.checked_add(donation.amount)
-                            .ok_or(Error::InvalidDonationAmount)?;
-                    }
-                }
-
-                if refund_amount == 0 {
-                    return Err(Error::NoDonationFound);
-                }
-
-                // Mark as claimed
-                self.refund_claimed.insert((campaign_id, caller), &true);
-
-                // Transfer refund to donor
-                if self.env().transfer(caller, refund_amount).is_err() {
-                    // Revert the claimed status if transfer fails
-                    self.refund_claimed.insert((campaign_id, caller), &false);
-                    return Err(Error::TransferFailed);
-                }
+        pub fn set_withdrawal_cooldown(&mut self, campaign_id: u32, cooldown: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
 
-                // Emit event
-                self.env().emit_event(RefundClaimed {
-                    campaign_id,
-                    donor: caller,
-                    amount: refund_amount,
-                });
+            if caller != campaign.owner {
+                return Err(Error::NotCampaignOwner);
+            }
+            if campaign.state != CampaignState::Active {
+                return Err(Error::CampaignNotActive);
+            }
 
-                Ok(())
-            })();
+            campaign.withdrawal_cooldown = cooldown;
+            self.save_campaign(campaign_id, &campaign);
 
-            // Always unlock before returning
-            self.locked = false;
-            result
+            Ok(())
         }
 
-        /// Retrieves a campaign by its ID.
+        /// Sets a soft cap below the campaign's goal (owner only).
+        ///
+        /// The campaign becomes `Successful` once the soft cap is raised but keeps
+        /// accepting donations until the goal (the hard cap) is reached or the funds are
+        /// withdrawn. If the soft cap isn't met by the deadline the campaign fails and
+        /// donors can claim refunds as usual. The soft cap is fixed once donations
+        /// arrive, so donors know the success threshold they are giving under.
         ///
         /// # Arguments
         ///
-        /// * `campaign_id` - The ID of the campaign to retrieve.
+        /// * `campaign_id` - The ID of the campaign.
+        /// * `soft_cap` - The minimum amount to succeed (0 disables it).
         ///
-        /// # Returns
+        /// # Errors
         ///
-        /// - `Some(Campaign)`: The campaign data if found.
-        /// - `None`: If no campaign with the given ID exists.
+        /// Returns `NotCampaignOwner`, `CampaignNotActive` once the campaign is no longer
+        /// active, `CampaignHasDonations` once it has donations, or `InvalidGoal` if the
+        /// soft cap exceeds the goal or is below the amount raised.
         #[ink(message)]
-        pub fn get_campaign(&self, campaign_id: u32) -> Option<Campaign> {
-            self.campaigns.get(campaign_id)
+        pub fn set_soft_cap(&mut self, campaign_id: u32, soft_cap: Balance) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let current_time = self.env().block_timestamp();
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+
+            if caller != campaign.owner {
+                return Err(Error::NotCampaignOwner);
+            }
+            if campaign.state != CampaignState::Active {
+                return Err(Error::CampaignNotActive);
+            }
+            if campaign.donation_count > 0 {
+                return Err(Error::CampaignHasDonations);
+            }
+            if soft_cap > campaign.goal || (soft_cap > 0 && soft_cap < campaign.raised) {
+                return Err(Error::InvalidGoal);
+            }
+
+            campaign.soft_cap = soft_cap;
+            self.store_campaign_progress(campaign_id, &mut campaign, current_time);
+
+            Ok(())
         }
 
-        /// Retrieves the details of a campaign, including paginated donations.
+        /// Caps how many distinct donors a campaign accepts (owner only).
+        ///
+        /// Bounds the donation list and QF work on viral campaigns. Once the cap is
+        /// reached new donors get `DonorLimitReached`, while existing donors can still
+        /// donate. A cap below the current donor count only stops new donors.
         ///
         /// # Arguments
         ///
-        /// * `campaign_id` - The ID of the campaign to retrieve details for.
-        /// * `offset` - The starting index for the donation pagination.
-        /// * `limit` - The maximum number of donations to return.
+        /// * `campaign_id` - The ID of the campaign.
+        /// * `max_donors` - The maximum number of distinct donors (0 disables it).
         ///
-        /// # Returns
+        /// # Errors
         ///
-        /// - `Some(CampaignDetails)`: The campaign details if the campaign is found.
-        /// - `None`: If the campaign does not exist.
+        /// Returns `NotCampaignOwner`, or `CampaignNotActive` once the campaign is no
+        /// longer active.
         #[ink(message)]
-        pub fn get_campaign_details(&self, campaign_id: u32, offset: u32, limit: u32) -> Option<CampaignDetails> {
-            let campaign = self.campaigns.get(campaign_id)?;
-            let all_donations = self.campaign_donations.get(campaign_id).unwrap_or_default();
-            
-            let start = offset as usize;
-            let end = (offset as usize + limit as usize).min(all_donations.len());
-            let donations = all_donations[start..end].to_vec();
+        pub fn set_max_donors(&mut self, campaign_id: u32, max_donors: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
 
-            Some(CampaignDetails {
-                campaign,
-                donations,
-                total_donations: u32::try_from(all_donations.len()).unwrap_or(0),
-            })
+            if caller != campaign.owner {
+                return Err(Error::NotCampaignOwner);
+            }
+            if campaign.state != CampaignState::Active {
+                return Err(Error::CampaignNotActive);
+            }
+
+            campaign.max_donors = max_donors;
+            self.save_campaign(campaign_id, &campaign);
+
+            Ok(())
         }
 
-        /// Retrieves a paginated list of all campaigns.
+        /// Limits how long refunds stay claimable once the campaign fails (owner only).
+        ///
+        /// Donors can claim within `refund_window` of the failure; afterwards the owner
+        /// or admin can sweep what is left to `fallback` with `sweep_unclaimed_refunds`.
+        /// The window can only be set before the first donation, so donors always know
+        /// the terms, and the fallback can't be the owner or beneficiary.
+        ///
+        /// On success, a `RefundWindowSet` event is emitted.
         ///
         /// # Arguments
         ///
-        /// * `offset` - The starting index for the campaign pagination.
-        /// * `limit` - The maximum number of campaigns to return.
+        /// * `campaign_id` - The ID of the campaign.
+        /// * `refund_window` - How long refunds stay open, in milliseconds (0 = forever,
+        ///   otherwise at least `MIN_REFUND_WINDOW`).
+        /// * `fallback` - The account that receives unclaimed refunds.
         ///
-        /// # Returns
+        /// # Errors
         ///
-        /// A vector of `Campaign` structs.
+        /// Returns `NotCampaignOwner`, `CampaignNotActive` once the campaign is no longer
+        /// active, `CampaignHasDonations` once it has donations, `InvalidRefundWindow`
+        /// for a window below the minimum, or `InvalidBeneficiary` for the zero address,
+        /// the owner or the beneficiary.
         #[ink(message)]
-        pub fn get_campaigns_paginated(&self, offset: u32, limit: u32) -> Vec<Campaign> {
-            let mut campaigns = Vec::new();
-            let start = offset;
-            let end = (offset + limit).min(self.campaign_count);
+        pub fn set_refund_window(&mut self, campaign_id: u32, refund_window: u64, fallback: AccountId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
 
-            for i in start..end {
-                if let Some(campaign) = self.campaigns.get(i) {
-                    campaigns.push(campaign);
-                }
+            if caller != campaign.owner {
+                return Err(Error::NotCampaignOwner);
+            }
+            if campaign.state != CampaignState::Active {
+                return Err(Error::CampaignNotActive);
+            }
+            if campaign.donation_count > 0 {
+                return Err(Error::CampaignHasDonations);
+            }
+            if refund_window > 0 && refund_window < MIN_REFUND_WINDOW {
+                return Err(Error::InvalidRefundWindow);
+            }
+            if fallback == AccountId::from([0; 32])
+                || fallback == campaign.owner
+                || fallback == campaign.beneficiary
+            {
+                return Err(Error::InvalidBeneficiary);
             }
 
-            campaigns
+            campaign.refund_window = refund_window;
+            campaign.refund_fallback = Some(fallback);
+            self.save_campaign(campaign_id, &campaign);
+
+            self.env().emit_event(RefundWindowSet {
+                campaign_id,
+                refund_window,
+                fallback,
+            });
+
+            Ok(())
         }
 
-        /// Retrieves all active campaigns (paginated).
+        /// Makes the campaign's NFT receipts soulbound, so donors can't transfer them
+        /// (owner only).
+        ///
+        /// Like the refund window this can only be chosen before the first donation,
+        /// so every receipt of a campaign follows the same rule.
         ///
         /// # Arguments
         ///
-        /// * `offset` - The starting index for the campaign pagination.
-        /// * `limit` - The maximum number of active campaigns to return.
+        /// * `campaign_id` - The ID of the campaign.
+        /// * `soulbound` - Whether receipts are minted soulbound.
         ///
-        /// # Returns
+        /// # Errors
         ///
-        /// A vector of active `Campaign` structs.
+        /// Returns `NotCampaignOwner`, `CampaignNotActive` once the campaign is no longer
+        /// active, or `CampaignHasDonations` once it has donations.
         #[ink(message)]
-        pub fn get_active_campaigns(&self, offset: u32, limit: u32) -> Vec<Campaign> {
-            let mut active_campaigns = Vec::new();
-            let mut count = 0;
-            let mut skipped = 0;
+        pub fn set_soulbound_receipts(&mut self, campaign_id: u32, soulbound: bool) -> Result<(), Error> {
+            let campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
 
-            for i in 0..self.campaign_count {
-                if let Some(campaign) = self.campaigns.get(i) {
-                    if campaign.state == CampaignState::Active {
-                        if skipped < offset {
-                            skipped += 1;
-                            continue;
-                        }
-                        if count >= limit {
-                            break;
-                        }
-                        active_campaigns.push(campaign);
-                        count += 1;
-                    }
-                }
+            if self.env().caller() != campaign.owner {
+                return Err(Error::NotCampaignOwner);
+            }
+            if campaign.state != CampaignState::Active {
+                return Err(Error::CampaignNotActive);
+            }
+            if campaign.donation_count > 0 {
+                return Err(Error::CampaignHasDonations);
             }
 
-            active_campaigns
-        }
+            if soulbound {
+                self.soulbound_receipt_campaigns.insert(campaign_id, &true);
+            } else {
+                self.soulbound_receipt_campaigns.remove(campaign_id);
+            }
 
-        /// Gets the contract version.
-        ///
-        /// # Returns
-        ///
-        /// The current version number of the contract logic.
-        #[ink(message)]
-        pub fn get_version(&self) -> u32 {
-            self.version
+            Ok(())
         }
 
-        /// Gets the total campaign count.
-        ///
-        /// # Returns
-        ///
-        /// The total number of campaigns ever created in the contract.
+        /// Whether the campaign's NFT receipts are minted soulbound.
         #[ink(message)]
-        pub fn get_campaign_count(&self) -> u32 {
-            self.campaign_count
+        pub fn has_soulbound_receipts(&self, campaign_id: u32) -> bool {
+            self.soulbound_receipt_campaigns.get(campaign_id).unwrap_or(false)
         }
 
-        /// Updates the maximum batch size (admin only).
-        ///
-        /// # Arguments
+        /// Stops a campaign from accepting donations (owner or admin).
         ///
-        /// * `size` - The new maximum batch size.
+        /// Unlike pausing the contract this only affects one campaign and leaves its
+        /// state untouched, so milestone voting carries on with a fixed set of donors.
+        /// Freezing is permanent.
         ///
-        /// # Returns
+        /// # Arguments
         ///
-        /// - `Ok(())` on success.
-        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
+        /// * `campaign_id` - The ID of the campaign.
         #[ink(message)]
-        pub fn set_max_batch_size(&mut self, size: u32) -> Result<(), Error> {
-            if self.env().caller() != self.admin {
-                return Err(Error::NotCampaignOwner); // Reusing error
+        pub fn freeze_donations(&mut self, campaign_id: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+
+            if caller != campaign.owner && caller != self.admin {
+                return Err(Error::NotCampaignOwner);
             }
-            self.max_batch_size = size;
+
+            campaign.donations_frozen = true;
+            self.save_campaign(campaign_id, &campaign);
+
             Ok(())
         }
 
-        /// Gets the maximum batch size.
-        ///
-        /// # Returns
-        ///
-        /// The maximum number of operations allowed in a single batch transaction.
+        /// Whether a campaign's donations are frozen.
         #[ink(message)]
-        pub fn get_max_batch_size(&self) -> u32 {
-            self.max_batch_size
+        pub fn is_donations_frozen(&self, campaign_id: u32) -> bool {
+            self.campaigns.get(campaign_id).map(|c| c.donations_frozen).unwrap_or(false)
         }
 
-        /// Sets the NFT contract address (admin only).
-        ///
-        /// # Arguments
-        ///
-        /// * `nft_contract` - The address of the NFT contract.
-        ///
-        /// # Returns
-        ///
-        /// - `Ok(())` on success.
-        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
-        #[ink(message)]
-        pub fn set_nft_contract(&mut self, nft_contract: AccountId) -> Result<(), Error> {
-            if self.env().caller() != self.admin {
-                return Err(Error::NotCampaignOwner);
+        /// Fails with `WithdrawalCooldown` if the last payout was less than the
+        /// campaign's cooldown ago.
+        fn check_withdrawal_cooldown(campaign: &Campaign, now: Timestamp) -> Result<(), Error> {
+            if let Some(last) = campaign.last_withdrawal_at {
+                if now < last.saturating_add(campaign.withdrawal_cooldown) {
+                    return Err(Error::WithdrawalCooldown);
+                }
             }
-            self.nft_contract = Some(nft_contract);
             Ok(())
         }
 
-        /// Gets the NFT contract address.
+        /// Amount of `vesting_total` unlocked at `now` (linear over `vesting_duration`).
+        fn vested_amount(campaign: &Campaign, now: Timestamp) -> Balance {
+            if campaign.vesting_duration == 0 || campaign.state != CampaignState::Withdrawn {
+                return 0;
+            }
+
+            let elapsed = now.saturating_sub(campaign.vesting_start);
+            if elapsed >= campaign.vesting_duration {
+                return campaign.vesting_total;
+            }
+
+            campaign.vesting_total
+                .saturating_mul(Balance::from(elapsed))
+                / Balance::from(campaign.vesting_duration)
+        }
+
+        /// Gets the vesting status of a campaign.
         ///
         /// # Returns
         ///
-        /// The address of the NFT contract if set.
+        /// `(vesting_total, vested_so_far, withdrawn_so_far)`, or `None` if the campaign
+        /// doesn't exist.
         #[ink(message)]
-        pub fn get_nft_contract(&self) -> Option<AccountId> {
-            self.nft_contract
+        pub fn get_vesting_status(&self, campaign_id: u32) -> Option<(Balance, Balance, Balance)> {
+            let campaign = self.campaigns.get(campaign_id)?;
+            let vested = Self::vested_amount(&campaign, self.env().block_timestamp());
+            Some((campaign.vesting_total, vested, campaign.vesting_withdrawn))
         }
 
-        /// Enables or disables NFT minting for donations (admin only).
+        /// Withdraws only the quadratic funding match of a campaign.
         ///
-        /// # Arguments
+        /// This lets a campaign withdraw its donations at the deadline and collect its
+        /// matching later, once the round has been distributed. The matching amount is
+        /// tracked separately so it can never be paid out twice, whether it is taken here
+        /// or as part of `withdraw_funds`.
         ///
-        /// * `enabled` - Whether to enable NFT minting.
+        /// On success, a `MatchingWithdrawn` event is emitted.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The ID of the campaign to withdraw matching for.
         ///
         /// # Returns
         ///
-        /// - `Ok(())` on success.
-        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
+        /// - `Ok(())`: If the matching was transferred to the beneficiary.
+        /// - `Err(Error)`: If the caller is not authorized or no matching is withdrawable.
+        ///
+        /// # Errors
+        ///
+        /// Returns `Error::NotCampaignOwner` if the caller is not the owner or admin,
+        /// `Error::MatchingNotDistributed` if the round has not been distributed,
+        /// `Error::FundsAlreadyWithdrawn` if the matching was already paid out,
+        /// or `Error::InsufficientFunds` if the campaign received no matching.
         #[ink(message)]
-        pub fn set_nft_enabled(&mut self, enabled: bool) -> Result<(), Error> {
-            if self.env().caller() != self.admin {
-                return Err(Error::NotCampaignOwner);
+        pub fn withdraw_matching(&mut self, campaign_id: u32) -> Result<(), Error> {
+            // Check and acquire lock
+            if self.locked {
+                return Err(Error::ReentrantCall);
             }
-            self.nft_enabled = enabled;
-            Ok(())
+            self.locked = true;
+
+            let result = self.process_matching_withdrawal(campaign_id);
+
+            // Always unlock before returning
+            self.locked = false;
+            result
         }
 
-        /// Gets whether NFT minting is enabled.
+        /// The internal logic for withdrawing a campaign's matching amount.
         ///
-        /// # Returns
+        /// # Arguments
         ///
-        /// True if NFT minting is enabled.
-        #[ink(message)]
-        pub fn is_nft_enabled(&self) -> bool {
-            self.nft_enabled
-        }
+        /// * `campaign_id` - The ID of the campaign to process.
+        fn process_matching_withdrawal(&mut self, campaign_id: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
 
-        // ==================== Quadratic Funding Functions ====================
+            // Check if caller is campaign owner or admin
+            if caller != campaign.owner && caller != self.admin {
+                return Err(Error::NotCampaignOwner);
+            }
 
-        /// Fund the matching pool (admin or anyone can contribute).
-        ///
-        /// # Returns
-        ///
-        /// - `Ok(())` on success.
-        #[ink(message, payable)]
-        pub fn fund_matching_pool(&mut self) -> Result<(), Error> {
-            let amount = self.env().transferred_value();
+            if campaign.state == CampaignState::Failed || campaign.refundable {
+                return Err(Error::CampaignFailed);
+            }
+
+            // Matching is only final once the round has been distributed
+            let round_id = campaign.matching_round.ok_or(Error::NoActiveRound)?;
+            let round = self.matching_rounds.get(round_id).ok_or(Error::NoActiveRound)?;
+            if !round.distributed {
+                return Err(Error::MatchingNotDistributed);
+            }
+
+            if campaign.matching_withdrawn {
+                return Err(Error::FundsAlreadyWithdrawn);
+            }
+
+            let amount = campaign.matching_amount;
             if amount == 0 {
-                return Err(Error::InvalidDonationAmount);
+                return Err(Error::InsufficientFunds);
             }
 
-            self.matching_pool_balance = self.matching_pool_balance
-                .checked_add(amount)
-                .ok_or(Error::InvalidDonationAmount)?;
+            if self.env().transfer(campaign.beneficiary, amount).is_err() {
+                return Err(Error::WithdrawalFailed);
+            }
 
-            self.env().emit_event(MatchingPoolFunded {
-                funder: self.env().caller(),
+            campaign.matching_withdrawn = true;
+            self.save_campaign(campaign_id, &campaign);
+
+            self.env().emit_event(MatchingWithdrawn {
+                campaign_id,
+                beneficiary: campaign.beneficiary,
                 amount,
-                total_pool: self.matching_pool_balance,
             });
 
             Ok(())
         }
 
-        /// Create a new matching round (admin only).
+        /// Withdraws funds from multiple campaigns in a single transaction.
+        /// Allows a user to withdraw funds from multiple owned campaigns in one batch,
+        /// saving on transaction fees.
         ///
         /// # Arguments
         ///
-        /// * `pool_amount` - Amount from matching pool to allocate to this round.
-        /// * `duration` - How long the round lasts (in milliseconds).
+        /// * `campaign_ids` - A vector of campaign IDs to withdraw from.
         ///
         /// # Returns
         ///
-        /// - `Ok(u32)`: The round ID.
-        /// - `Err(Error)`: If insufficient pool or not admin.
+        /// - `Ok(BatchResult)`: A struct indicating the number of successful and failed
+        ///   withdrawals.
+        /// - `Err(Error)`: An error variant, such as `BatchSizeTooLarge`.
+        ///
+        /// # Errors
+        ///
+        /// Returns `Error::BatchSizeTooLarge` if the input vector exceeds the
+        /// maximum allowed batch size. Each individual withdrawal may also fail with
+        /// errors reported in the `failed` count of the `BatchResult`.
         #[ink(message)]
-        pub fn create_matching_round(&mut self, pool_amount: Balance, duration: u64) -> Result<u32, Error> {
-            if self.env().caller() != self.admin {
-                return Err(Error::NotCampaignOwner);
+        pub fn withdraw_funds_batch(&mut self, campaign_ids: Vec<u32>) -> Result<BatchResult, Error> {
+            if campaign_ids.len() > self.max_batch_size as usize {
+                return Err(Error::BatchSizeTooLarge);
             }
 
-            if pool_amount > self.matching_pool_balance {
-                return Err(Error::InsufficientMatchingPool);
+            // Check and acquire lock ONCE for the entire batch operation
+            if self.locked {
+                return Err(Error::ReentrantCall);
             }
+            self.locked = true;
 
-            let round_id = self.round_count;
-            let end_time = self.env().block_timestamp() + duration;
-
-            let round = MatchingRound {
-                id: round_id,
-                pool_amount,
-                end_time,
-                distributed: false,
-                campaign_ids: Vec::new(),
-            };
-
-            self.matching_rounds.insert(round_id, &round);
-            self.current_round = Some(round_id);
-            self.round_count += 1;
+            // Execute batch withdrawal logic
+            let result = (|| {
+                let mut successful = 0;
+                let mut failed = 0;
+                let mut success_ids = Vec::new();
 
-            // Deduct from available pool
-            self.matching_pool_balance = self.matching_pool_balance
-                .checked_sub(pool_amount)
-                .ok_or(Error::InsufficientMatchingPool)?;
+                for campaign_id in campaign_ids {
+                    // Call internal process_withdrawal to avoid double-locking
+                    match self.process_withdrawal(campaign_id) {
+                        Ok(_) => {
+                            successful += 1;
+                            success_ids.push(campaign_id);
+                        }
+                        Err(_) => {
+                            failed += 1;
+                        }
+                    }
+                }
 
-            self.env().emit_event(MatchingRoundCreated {
-                round_id,
-                pool_amount,
-                end_time,
-            });
+                Ok(BatchResult {
+                    successful,
+                    failed,
+                    success_ids,
+                })
+            })();
 
-            Ok(round_id)
+            // Always unlock before returning
+            self.locked = false;
+            result
         }
 
-        /// Calculate quadratic funding matching for all campaigns in a round.
-        /// This uses the formula: matching ∝ (sum of √donation_amounts)²
+        /// Cancels an active campaign.
+        ///
+        /// This function allows a campaign owner (or admin) to cancel their campaign before
+        /// the deadline. Once cancelled, the campaign state changes to `Failed`, and donors
+        /// can claim refunds.
+        ///
+        /// On success, a `CampaignCancelled` event is emitted.
         ///
         /// # Arguments
         ///
-        /// * `round_id` - The round to calculate matching for.
+        /// * `campaign_id` - The ID of the campaign to cancel.
         ///
         /// # Returns
         ///
-        /// - `Ok(())` on success.
-        /// - `Err(Error)`: If round not found or already distributed.
+        /// - `Ok(())`: If the cancellation was successful.
+        /// - `Err(Error)`: If the caller is not authorized or the campaign cannot be cancelled.
+        ///
+        /// # Errors
+        ///
+        /// Returns `Error::NotCampaignOwner` if the caller is not the owner or admin,
+        /// or `Error::CampaignNotActive` if the campaign is not in an active state.
         #[ink(message)]
-        pub fn calculate_and_distribute_matching(&mut self, round_id: u32) -> Result<(), Error> {
-            if self.env().caller() != self.admin {
+        pub fn cancel_campaign(&mut self, campaign_id: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+
+            // Only owner or admin can cancel
+            if caller != campaign.owner && caller != self.admin {
                 return Err(Error::NotCampaignOwner);
             }
 
-            let mut round = self.matching_rounds.get(round_id).ok_or(Error::CampaignNotFound)?;
-            
-            if round.distributed {
-                return Err(Error::FundsAlreadyWithdrawn);
+            // Can only cancel active campaigns
+            if campaign.state != CampaignState::Active {
+                return Err(Error::CampaignNotActive);
             }
 
-            let current_time = self.env().block_timestamp();
-            if current_time < round.end_time {
-                return Err(Error::DeadlinePassed); // Reusing error - means "round not ended yet"
+            // Mark campaign as failed
+            self.set_state(&mut campaign, CampaignState::Failed);
+            self.save_campaign(campaign_id, &campaign);
+
+            // Emit event
+            self.env().emit_event(CampaignCancelled {
+                campaign_id,
+                cancelled_by: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Merges a duplicate campaign into another one (admin only).
+        ///
+        /// The source's donations, raised amount and per-donor records move to the
+        /// target, so donors keep their refund rights and vote weight there. The source
+        /// ends in the `Merged` state, and refund calls and donor queries on it are
+        /// redirected to the target. Both campaigns must be active. Emits a
+        /// `CampaignsMerged` event.
+        ///
+        /// # Arguments
+        ///
+        /// * `source_id` - The duplicate campaign to merge away.
+        /// * `target_id` - The campaign to keep.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotCampaignOwner`, `CampaignNotFound`, `CampaignNotActive`,
+        /// `BatchOperationFailed` if both IDs are the same, or `BatchSizeTooLarge` if
+        /// the source has more than `MAX_MERGE_DONATIONS` or any archived donations.
+        #[ink(message)]
+        pub fn merge_campaigns(&mut self, source_id: u32, target_id: u32) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+            if source_id == target_id {
+                return Err(Error::BatchOperationFailed); // Reusing error
             }
 
-            // Calculate quadratic scores for all campaigns in current round
-            let mut total_qf_score: u128 = 0;
-            let mut campaign_scores: Vec<(u32, u128)> = Vec::new();
+            let mut source = self.campaigns.get(source_id).ok_or(Error::CampaignNotFound)?;
+            let mut target = self.campaigns.get(target_id).ok_or(Error::CampaignNotFound)?;
 
-            // Iterate through all campaigns to find those in this round
-            for campaign_id in 0..self.campaign_count {
-                if let Some(campaign) = self.campaigns.get(campaign_id) {
-                    if campaign.matching_round == Some(round_id) && campaign.state != CampaignState::Failed {
-                        let qf_score = self.calculate_qf_score(campaign_id);
-                        if qf_score > 0 {
-                            campaign_scores.push((campaign_id, qf_score));
-                            total_qf_score = total_qf_score.saturating_add(qf_score);
-                        }
-                    }
-                }
+            if source.state != CampaignState::Active || target.state != CampaignState::Active {
+                return Err(Error::CampaignNotActive);
+            }
+            // Archived donations can't be moved one by one, so their donors' records would be lost
+            if source.donation_count > MAX_MERGE_DONATIONS || source.archived_donation_count > 0 {
+                return Err(Error::BatchSizeTooLarge);
             }
 
-            // Distribute matching proportionally based on QF scores
-            if total_qf_score > 0 {
-                for (campaign_id, qf_score) in campaign_scores {
-                    let matching_share = ((qf_score as u128) * (round.pool_amount as u128) / total_qf_score) as Balance;
-                    
-                    if let Some(mut campaign) = self.campaigns.get(campaign_id) {
-                        campaign.matching_amount = matching_share;
-                        self.campaigns.insert(campaign_id, &campaign);
-
-                        self.env().emit_event(MatchingDistributed {
-                            campaign_id,
-                            matching_amount: matching_share,
-                            round_id,
-                        });
+            // Move donations to the end of the target's list
+            for index in 0..source.donation_count {
+                let Some(donation) = self.campaign_donations.get((source_id, index)) else {
+                    continue;
+                };
+                self.campaign_donations.remove((source_id, index));
+                self.campaign_donations.insert((target_id, target.donation_count), &donation);
+                target.donation_count = target.donation_count.saturating_add(1);
+
+                // Per-donor records move with the donor's first donation
+                let donor = donation.donor;
+                if let Some(total) = self.donor_totals.get((source_id, donor)) {
+                    self.donor_totals.remove((source_id, donor));
+                    let merged = self.donor_totals.get((target_id, donor)).unwrap_or(0).saturating_add(total);
+                    self.donor_totals.insert((target_id, donor), &merged);
+                }
+                if self.unique_donors.get((source_id, donor)).unwrap_or(false) {
+                    self.unique_donors.remove((source_id, donor));
+                    if !self.unique_donors.get((target_id, donor)).unwrap_or(false) {
+                        self.unique_donors.insert((target_id, donor), &true);
+                        target.unique_donor_count = target.unique_donor_count.saturating_add(1);
+                    }
+                }
+                if let Some(address) = self.refund_addresses.get((source_id, donor)) {
+                    self.refund_addresses.remove((source_id, donor));
+                    if !self.refund_addresses.contains((target_id, donor)) {
+                        self.refund_addresses.insert((target_id, donor), &address);
                     }
                 }
             }
 
-            // Mark round as distributed
-            round.distributed = true;
-            self.matching_rounds.insert(round_id, &round);
+            let amount = source.raised;
+            target.raised = target.raised.saturating_add(source.raised);
+            target.fees_paid = target.fees_paid.saturating_add(source.fees_paid);
 
-            // Close the current round
-            if self.current_round == Some(round_id) {
-                self.current_round = None;
-            }
+            source.raised = 0;
+            source.fees_paid = 0;
+            source.donation_count = 0;
+            source.unique_donor_count = 0;
+            source.merged_into = Some(target_id);
+            self.set_state(&mut source, CampaignState::Merged);
+            self.save_campaign(source_id, &source);
+
+            let current_time = self.env().block_timestamp();
+            self.store_campaign_progress(target_id, &mut target, current_time);
+
+            self.env().emit_event(CampaignsMerged {
+                source_id,
+                target_id,
+                amount,
+            });
 
             Ok(())
         }
 
-        /// Integer square root using binary search (Babylonian method).
-        /// Required for quadratic funding calculations.
-        fn sqrt(n: u128) -> u128 {
-            if n == 0 {
-                return 0;
-            }
-            
-            let mut x = n;
-            let mut y = (x + 1) / 2;
-            
-            while y < x {
-                x = y;
-                y = (x + n / x) / 2;
-            }
-            
-            x
+        /// Gets the campaign a merged campaign now lives in, or `None` if it wasn't merged.
+        #[ink(message)]
+        pub fn get_merged_into(&self, campaign_id: u32) -> Option<u32> {
+            self.campaigns.get(campaign_id)?.merged_into
         }
 
-        /// Calculate the quadratic funding score for a campaign.
-        /// Formula: (√donation₁ + √donation₂ + ... + √donationₙ)²
-        ///
-        /// This rewards campaigns with many small donors over few large donors.
-        fn calculate_qf_score(&self, campaign_id: u32) -> u128 {
-            let donations = match self.campaign_donations.get(campaign_id) {
-                Some(d) => d,
-                None => return 0,
-            };
-
-            let mut sum_of_square_roots: u128 = 0;
-
-            for donation in donations.iter() {
-                // Convert Balance to u128 for calculation
-                let amount_u128 = donation.amount as u128;
-                let sqrt_amount = Self::sqrt(amount_u128);
-                sum_of_square_roots = sum_of_square_roots.saturating_add(sqrt_amount);
+        /// Follows merges to the campaign that holds a campaign's donations now.
+        fn resolve_campaign_id(&self, campaign_id: u32) -> u32 {
+            let mut current = campaign_id;
+            while let Some(next) = self.get_merged_into(current) {
+                current = next;
             }
-
-            // Square the sum: (√a + √b + √c)²
-            sum_of_square_roots.saturating_mul(sum_of_square_roots)
+            current
         }
 
-        /// Get estimated matching for a campaign (read-only, for UI display).
+        /// Claims a refund for donations made to a failed campaign.
+        ///
+        /// When a campaign fails (either by missing its deadline or being cancelled),
+        /// donors can call this function to receive a full refund of their contributions.
+        /// Each donor can only claim their refund once.
+        ///
+        /// On success, a `RefundClaimed` event is emitted. If the transfer itself fails
+        /// (e.g. the destination is a contract that rejects it), the refund is still
+        /// marked as claimed but credited to the destination instead, to be pulled later
+        /// with `withdraw_refund_credit`; a `RefundCredited` event is emitted in that case.
         ///
         /// # Arguments
         ///
-        /// * `campaign_id` - The campaign to estimate matching for.
+        /// * `campaign_id` - The ID of the failed campaign to claim a refund from.
         ///
         /// # Returns
         ///
-        /// Estimated matching amount based on current donations and round pool.
+        /// - `Ok(())`: If the refund was successfully processed.
+        /// - `Err(Error)`: If the refund cannot be claimed.
+        ///
+        /// # Errors
+        ///
+        /// Returns `Error::CampaignNotFailed` if the campaign is not in a failed state,
+        /// `Error::NoDonationFound` if the caller has no donations,
+        /// `Error::RefundAlreadyClaimed` if the refund was already claimed,
+        /// or `Error::InsufficientFunds` if the caller's pro-rata share rounds to 0.
         #[ink(message)]
-        pub fn get_estimated_matching(&self, campaign_id: u32) -> Balance {
-            let campaign = match self.campaigns.get(campaign_id) {
-                Some(c) => c,
-                None => return 0,
-            };
-
-            let round_id = match campaign.matching_round {
-                Some(r) => r,
-                None => return 0,
-            };
-
-            let round = match self.matching_rounds.get(round_id) {
-                Some(r) => r,
-                None => return 0,
-            };
+        pub fn claim_refund(&mut self, campaign_id: u32) -> Result<(), Error> {
+            let campaign_id = self.resolve_campaign_id(campaign_id);
 
-            if round.distributed {
-                return campaign.matching_amount;
+            // Check and acquire lock
+            if self.locked {
+                return Err(Error::ReentrantCall);
             }
+            self.locked = true;
 
-            // Calculate this campaign's QF score
-            let campaign_score = self.calculate_qf_score(campaign_id);
-            if campaign_score == 0 {
-                return 0;
-            }
+            // Execute refund logic in a closure to ensure unlock happens
+            let result = (|| {
+                let caller = self.env().caller();
+                let campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
 
-            // Calculate total QF score for all campaigns in round
-            let mut total_score: u128 = 0;
-            for id in 0..self.campaign_count {
-                if let Some(c) = self.campaigns.get(id) {
-                    if c.matching_round == Some(round_id) {
-                        total_score = total_score.saturating_add(self.calculate_qf_score(id));
-                    }
+                // Only allow refunds for failed or force-refundable campaigns
+                if campaign.state != CampaignState::Failed && !campaign.refundable {
+                    return Err(Error::CampaignFailed);
+                }
+                if !self.refund_window_open(&campaign) {
+                    return Err(Error::RefundWindowClosed);
                 }
-            }
 
-            if total_score == 0 {
-                return 0;
-            }
+                // Check if already claimed
+                if self.refund_claimed.get((campaign_id, caller)).unwrap_or(false) {
+                    return Err(Error::RefundAlreadyClaimed);
+                }
 
-            // Estimate share
-            ((campaign_score as u128) * (round.pool_amount as u128) / total_score) as Balance
-        }
+                // Total donation amount for this donor
+                let refund_amount = self.donor_totals.get((campaign_id, caller)).unwrap_or(0);
 
-        /// Get matching pool balance.
-        #[ink(message)]
-        pub fn get_matching_pool_balance(&self) -> Balance {
-            self.matching_pool_balance
-        }
+                if refund_amount == 0 {
+                    return Err(Error::NoDonationFound);
+                }
 
-        /// Get current active round ID.
-        #[ink(message)]
-        pub fn get_current_round(&self) -> Option<u32> {
-            self.current_round
-        }
+                // A pro-rata share that rounds to nothing leaves the claim open
+                let owed = refund_amount;
+                let refund_amount = self.refund_payout(campaign_id, owed);
+                if refund_amount == 0 {
+                    return Err(Error::InsufficientFunds);
+                }
 
-        /// Get round details.
-        #[ink(message)]
-        pub fn get_round(&self, round_id: u32) -> Option<MatchingRound> {
-            self.matching_rounds.get(round_id)
-        }
+                // Mark as claimed
+                self.refund_claimed.insert((campaign_id, caller), &true);
+                self.deduct_donor_contributions(caller, owed);
+                self.record_refund(campaign_id, owed, refund_amount);
+
+                // Transfer refund to donor (or the destination they chose)
+                let destination = self.refund_addresses.get((campaign_id, caller)).unwrap_or(caller);
+                if self.env().transfer(destination, refund_amount).is_err() {
+                    // Keep the claim and credit the refund so it can be pulled later
+                    let credit = self.refund_credits.get(destination).unwrap_or(0)
+                        .checked_add(refund_amount)
+                        .ok_or(Error::InvalidDonationAmount)?;
+                    self.refund_credits.insert(destination, &credit);
+
+                    self.env().emit_event(RefundCredited {
+                        campaign_id,
+                        account: destination,
+                        amount: refund_amount,
+                    });
+                    return Ok(());
+                }
 
-        /// Get count of unique donors for a campaign.
-        #[ink(message)]
-        pub fn get_unique_donor_count(&self, campaign_id: u32) -> u32 {
-            let donations = match self.campaign_donations.get(campaign_id) {
-                Some(d) => d,
-                None => return 0,
-            };
+                // Emit event
+                self.env().emit_event(RefundClaimed {
+                    campaign_id,
+                    donor: caller,
+                    amount: refund_amount,
+                });
 
-            let mut unique_count = 0;
-            for donation in donations.iter() {
-                let donor_key = (campaign_id, donation.donor);
-                if self.unique_donors.get(donor_key).unwrap_or(false) {
-                    unique_count += 1;
-                }
-            }
+                Ok(())
+            })();
 
-            unique_count
+            // Always unlock before returning
+            self.locked = false;
+            result
         }
 
-        // ==================== DAO Milestone Voting Functions ====================
-
-        /// Add milestones to a campaign (owner only, before campaign is successful).
+        /// Retries the transfer of refunds that were credited after a failed transfer.
+        ///
+        /// Anyone may call this on behalf of the credited account; the funds always go
+        /// to that account. If the transfer fails again the credit is kept.
         ///
         /// # Arguments
         ///
-        /// * `campaign_id` - The campaign to add milestones to.
-        /// * `milestones_data` - Vec of (description, percentage, days_from_now).
+        /// * `account` - The account holding the refund credit.
         ///
         /// # Returns
         ///
-        /// - `Ok(())` on success.
-        /// - `Err(Error)`: If not owner or campaign already successful.
+        /// - `Ok(Balance)`: The amount transferred.
+        /// - `Err(Error)`: `NoDonationFound` if there is no credit, or `TransferFailed`.
         #[ink(message)]
-        pub fn add_milestones(
-            &mut self,
-            campaign_id: u32,
-            milestones_data: Vec<(String, u32, u64)>,
-        ) -> Result<(), Error> {
-            let caller = self.env().caller();
-            let current_time = self.env().block_timestamp();
-
-            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
-
-            // Only owner can add milestones
-            if caller != campaign.owner {
-                return Err(Error::NotCampaignOwner);
+        pub fn withdraw_refund_credit(&mut self, account: AccountId) -> Result<Balance, Error> {
+            // Check and acquire lock
+            if self.locked {
+                return Err(Error::ReentrantCall);
             }
+            self.locked = true;
 
-            // Can't add milestones to completed campaigns
-            if campaign.state != CampaignState::Active {
-                return Err(Error::CampaignNotActive);
-            }
+            let result = self.process_refund_credit_withdrawal(account);
 
-            // Validate percentages sum to 100 (10000 basis points)
-            let total_percentage: u32 = milestones_data.iter().map(|(_, p, _)| p).sum();
-            if total_percentage != 10000 {
-                return Err(Error::InvalidGoal); // Reusing error - means invalid percentage
-            }
+            // Always unlock before returning
+            self.locked = false;
+            result
+        }
 
-            // Create milestones
-            let mut milestones = Vec::new();
-            for (description, percentage, days) in milestones_data {
-                if description.is_empty() || description.len() > 200 {
-                    return Err(Error::InvalidDescription);
-                }
-                
-                let milestone_deadline = current_time + (days * 24 * 60 * 60 * 1000);
-                
-                milestones.push(Milestone {
-                    description,
-                    percentage,
-                    deadline: milestone_deadline,
-                    votes_for: 0,
-                    votes_against: 0,
-                    released: false,
-                    voting_active: false,
-                });
+        /// The internal logic for paying out a refund credit.
+        fn process_refund_credit_withdrawal(&mut self, account: AccountId) -> Result<Balance, Error> {
+            let credit = self.refund_credits.get(account).unwrap_or(0);
+            if credit == 0 {
+                return Err(Error::NoDonationFound);
             }
 
-            campaign.milestones = milestones;
-            campaign.uses_milestones = true;
-            self.campaigns.insert(campaign_id, &campaign);
+            self.refund_credits.remove(account);
+            if self.env().transfer(account, credit).is_err() {
+                self.refund_credits.insert(account, &credit);
+                return Err(Error::TransferFailed);
+            }
 
-            self.env().emit_event(MilestonesAdded {
-                campaign_id,
-                milestone_count: u32::try_from(campaign.milestones.len()).unwrap_or(0),
-            });
+            Ok(credit)
+        }
 
-            Ok(())
+        /// Gets the refund credit waiting to be withdrawn by an account.
+        #[ink(message)]
+        pub fn get_refund_credit(&self, account: AccountId) -> Balance {
+            self.refund_credits.get(account).unwrap_or(0)
         }
 
-        /// Activate voting for a milestone (owner requests release).
+        /// Sets where the caller's refund for a campaign should be sent.
+        ///
+        /// Useful to send refunds to a cold wallet instead of the donating account.
+        /// If unset, refunds go to the donor.
         ///
         /// # Arguments
         ///
-        /// * `campaign_id` - The campaign.
-        /// * `milestone_index` - Which milestone to activate voting for.
+        /// * `campaign_id` - The ID of the campaign.
+        /// * `destination` - The account that should receive the refund.
         ///
-        /// # Returns
+        /// # Errors
         ///
-        /// - `Ok(())` on success.
+        /// Returns `Error::InvalidBeneficiary` for the zero address.
         #[ink(message)]
-        pub fn activate_milestone_voting(
-            &mut self,
-            campaign_id: u32,
-            milestone_index: u32,
-        ) -> Result<(), Error> {
+        pub fn set_refund_address(&mut self, campaign_id: u32, destination: AccountId) -> Result<(), Error> {
             let caller = self.env().caller();
-            let current_time = self.env().block_timestamp();
-
-            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
-
-            // Only owner can activate voting
-            if caller != campaign.owner {
-                return Err(Error::NotCampaignOwner);
-            }
-
-            // Campaign must be successful
-            if campaign.state != CampaignState::Successful && campaign.state != CampaignState::Withdrawn {
-                return Err(Error::GoalNotReached);
-            }
-
-            let idx = milestone_index as usize;
-            if idx >= campaign.milestones.len() {
-                return Err(Error::CampaignNotFound); // Reusing - means milestone not found
-            }
 
-            // Check if previous milestones are released (must be sequential)
-            if idx > 0 && !campaign.milestones[idx - 1].released {
-                return Err(Error::GoalNotReached); // Reusing - means previous milestone not done
+            if !self.campaigns.contains(campaign_id) {
+                return Err(Error::CampaignNotFound);
             }
-
-            // Check deadline hasn't passed
-            if current_time > campaign.milestones[idx].deadline {
-                return Err(Error::DeadlinePassed);
+            if destination == AccountId::from([0; 32]) {
+                return Err(Error::InvalidBeneficiary);
             }
 
-            campaign.milestones[idx].voting_active = true;
-            self.campaigns.insert(campaign_id, &campaign);
-
-            self.env().emit_event(MilestoneVotingActivated {
-                campaign_id,
-                milestone_index,
-            });
-
+            self.refund_addresses.insert((campaign_id, caller), &destination);
             Ok(())
         }
 
-        /// Vote on a milestone (donors only, weighted by donation amount).
+        /// Gets where a donor's refund for a campaign will be sent.
+        #[ink(message)]
+        pub fn get_refund_address(&self, campaign_id: u32, donor: AccountId) -> AccountId {
+            self.refund_addresses.get((campaign_id, donor)).unwrap_or(donor)
+        }
+
+        /// Checks whether a donor could successfully call `claim_refund` right now.
+        ///
+        /// Combines the campaign state, donation and already-claimed checks so wallets
+        /// can decide whether to show a refund button without a failing transaction.
         ///
         /// # Arguments
         ///
-        /// * `campaign_id` - The campaign.
-        /// * `milestone_index` - Which milestone to vote on.
-        /// * `approve` - true to approve, false to reject.
+        /// * `campaign_id` - The ID of the campaign.
+        /// * `donor` - The donor to check.
         ///
         /// # Returns
         ///
-        /// - `Ok(())` on success.
+        /// `true` if the campaign is failed (or force-refundable), the donor has donated,
+        /// and the refund hasn't been claimed yet.
         #[ink(message)]
-        pub fn vote_on_milestone(
-            &mut self,
-            campaign_id: u32,
-            milestone_index: u32,
-            approve: bool,
-        ) -> Result<(), Error> {
-            let caller = self.env().caller();
+        pub fn can_claim_refund(&self, campaign_id: u32, donor: AccountId) -> bool {
+            let campaign_id = self.resolve_campaign_id(campaign_id);
+            self.campaigns
+                .get(campaign_id)
+                .is_some_and(|campaign| self.refund_claimable(campaign_id, &campaign, donor))
+        }
 
-            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+        /// `can_claim_refund` for an already loaded campaign, without following merges.
+        fn refund_claimable(&self, campaign_id: u32, campaign: &Campaign, donor: AccountId) -> bool {
+            (campaign.state == CampaignState::Failed || campaign.refundable)
+                && self.refund_window_open(campaign)
+                && self.donor_totals.get((campaign_id, donor)).unwrap_or(0) > 0
+                && !self.refund_claimed.get((campaign_id, donor)).unwrap_or(false)
+        }
 
-            let idx = milestone_index as usize;
-            if idx >= campaign.milestones.len() {
-                return Err(Error::CampaignNotFound);
-            }
+        /// Lists the campaigns a donor can currently claim a refund from (cursor-paginated).
+        ///
+        /// # Arguments
+        ///
+        /// * `donor` - The donor to check.
+        /// * `cursor` - Scan campaigns with an ID above this; `None` starts at the beginning.
+        /// * `limit` - The maximum number of entries to return (capped at `MAX_QUERY_LIMIT`).
+        ///
+        /// # Returns
+        ///
+        /// `(campaign_id, refund_amount)` pairs for every campaign where `can_claim_refund`
+        /// holds for the donor, and the cursor to resume from (`None` once every campaign
+        /// has been scanned). A call examines at most `MAX_CAMPAIGN_SCAN` campaigns.
+        #[ink(message)]
+        pub fn get_my_refundable_campaigns(
+            &self,
+            donor: AccountId,
+            cursor: Option<u32>,
+            limit: u32,
+        ) -> (Vec<(u32, Balance)>, Option<u32>) {
+            self.scan_campaigns(cursor, limit, |campaign_id, campaign| {
+                self.refund_claimable(campaign_id, &campaign, donor)
+                    .then(|| (campaign_id, self.donor_totals.get((campaign_id, donor)).unwrap_or(0)))
+            })
+        }
 
-            // Voting must be active
-            if !campaign.milestones[idx].voting_active {
-                return Err(Error::CampaignNotActive);
+        /// Donates the caller's refund from a failed campaign to another campaign.
+        ///
+        /// This saves donors a refund transfer followed by a separate donation. The refund
+        /// amount goes through the normal donation path (fee and validation included) and
+        /// the refund on the original campaign is marked as claimed.
+        ///
+        /// On success, a `DonationReceived` and a `RefundRedirected` event are emitted.
+        ///
+        /// # Arguments
+        ///
+        /// * `from_campaign_id` - The failed campaign the caller is owed a refund from.
+        /// * `to_campaign_id` - The active campaign to donate the refund to.
+        ///
+        /// # Errors
+        ///
+        /// Returns the same errors as `claim_refund` for the source campaign, and the same
+        /// errors as `donate` for the target campaign.
+        #[ink(message)]
+        pub fn redirect_refund(&mut self, from_campaign_id: u32, to_campaign_id: u32) -> Result<(), Error> {
+            // Check and acquire lock
+            if self.locked {
+                return Err(Error::ReentrantCall);
             }
+            self.locked = true;
 
-            // Already released
-            if campaign.milestones[idx].released {
-                return Err(Error::FundsAlreadyWithdrawn);
+            let result = self.process_refund_redirect(from_campaign_id, to_campaign_id);
+
+            // Always unlock before returning
+            self.locked = false;
+            result
+        }
+
+        /// The internal logic for redirecting a refund into a new donation.
+        fn process_refund_redirect(&mut self, from_campaign_id: u32, to_campaign_id: u32) -> Result<(), Error> {
+            let from_campaign_id = self.resolve_campaign_id(from_campaign_id);
+            let caller = self.env().caller();
+            let campaign = self.campaigns.get(from_campaign_id).ok_or(Error::CampaignNotFound)?;
+
+            // Only allow refunds for failed or force-refundable campaigns
+            if campaign.state != CampaignState::Failed && !campaign.refundable {
+                return Err(Error::CampaignFailed);
+            }
+            if !self.refund_window_open(&campaign) {
+                return Err(Error::RefundWindowClosed);
             }
 
-            // Calculate voter's donation weight
-            let donations = self.campaign_donations.get(campaign_id).unwrap_or_default();
-            let mut voter_weight: Balance = 0;
-            for donation in donations.iter() {
-                if donation.donor == caller {
-                    voter_weight = voter_weight.saturating_add(donation.amount);
-                }
+            if self.refund_claimed.get((from_campaign_id, caller)).unwrap_or(false) {
+                return Err(Error::RefundAlreadyClaimed);
             }
 
-            if voter_weight == 0 {
+            let owed = self.donor_totals.get((from_campaign_id, caller)).unwrap_or(0);
+            if owed == 0 {
                 return Err(Error::NoDonationFound);
             }
+            let refund_amount = self.refund_payout(from_campaign_id, owed);
+            if refund_amount == 0 {
+                return Err(Error::InsufficientFunds);
+            }
 
-            // Check if already voted
-            let vote_key = (campaign_id, milestone_index, caller);
-            if self.milestone_votes.get(vote_key).is_some() {
-                return Err(Error::RefundAlreadyClaimed); // Reusing - means already voted
+            // Donate first so a rejected donation leaves the refund claimable
+            let (_, redirected) = self.process_donation(to_campaign_id, refund_amount)?;
+
+            self.refund_claimed.insert((from_campaign_id, caller), &true);
+            self.record_refund(from_campaign_id, owed, refund_amount);
+            self.deduct_donor_contributions(caller, owed);
+
+            self.env().emit_event(RefundRedirected {
+                from_campaign_id,
+                to_campaign_id,
+                donor: caller,
+                amount: redirected,
+            });
+
+            Ok(())
+        }
+
+        /// Whether refunds can still be claimed given the campaign's refund window.
+        fn refund_window_open(&self, campaign: &Campaign) -> bool {
+            match campaign.failed_at {
+                Some(failed_at) if campaign.refund_window > 0 => {
+                    self.env().block_timestamp() <= failed_at.saturating_add(campaign.refund_window)
+                }
+                _ => true,
             }
+        }
 
-            // Record vote
-            self.milestone_votes.insert(vote_key, &voter_weight);
+        /// The amount actually paid for a refund of `owed`.
+        ///
+        /// In pro-rata mode this is the donor's share of what is left of the available
+        /// balance; the last claimer receives the remainder so nothing is stranded.
+        fn refund_payout(&self, campaign_id: u32, owed: Balance) -> Balance {
+            match self.pro_rata_refunds.get(campaign_id) {
+                Some((available, remaining_owed)) if remaining_owed > owed => {
+                    available.saturating_mul(owed) / remaining_owed
+                }
+                Some((available, _)) => available,
+                None => owed,
+            }
+        }
 
-            // Update vote counts
-            if approve {
-                campaign.milestones[idx].votes_for = campaign.milestones[idx]
-                    .votes_for
-                    .saturating_add(voter_weight);
-            } else {
-                campaign.milestones[idx].votes_against = campaign.milestones[idx]
-                    .votes_against
-                    .saturating_add(voter_weight);
+        /// Takes a refunded donation back out of the donor's grand total.
+        fn deduct_donor_contributions(&mut self, donor: AccountId, amount: Balance) {
+            let total = self.donor_total_contributions.get(donor).unwrap_or(0).saturating_sub(amount);
+            self.donor_total_contributions.insert(donor, &total);
+        }
+
+        /// Records a refund of `owed` paid out as `paid`.
+        fn record_refund(&mut self, campaign_id: u32, owed: Balance, paid: Balance) {
+            let refunded = self.campaign_refunded.get(campaign_id).unwrap_or(0).saturating_add(owed);
+            self.campaign_refunded.insert(campaign_id, &refunded);
+
+            // The refunded amount is no longer owed
+            if let Some(campaign) = self.campaigns.get(campaign_id) {
+                self.save_campaign(campaign_id, &campaign);
             }
 
-            self.campaigns.insert(campaign_id, &campaign);
+            if let Some((available, remaining_owed)) = self.pro_rata_refunds.get(campaign_id) {
+                self.pro_rata_refunds.insert(
+                    campaign_id,
+                    &(available.saturating_sub(paid), remaining_owed.saturating_sub(owed)),
+                );
+            }
+        }
 
-            self.env().emit_event(MilestoneVoted {
+        /// Switches an insolvent refundable campaign to pro-rata refunds (admin only).
+        ///
+        /// If the contract holds less than the campaign still owes its donors, paying
+        /// refunds in full would leave the last claimers with nothing. In pro-rata mode
+        /// each remaining donor instead receives their share of what is available to
+        /// the campaign, in proportion to what they donated, so the claims sum to
+        /// exactly that amount. The available amount is the contract balance minus
+        /// everything else the contract owes (see `get_liabilities`).
+        ///
+        /// On success, a `ProRataRefundsEnabled` event is emitted.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The ID of the failed or refundable campaign.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())`: If pro-rata refunds are now active.
+        /// - `Err(Error)`: If the caller is not the admin or the campaign is not eligible.
+        ///
+        /// # Errors
+        ///
+        /// Returns `Error::NotCampaignOwner` if the caller is not the admin,
+        /// `Error::CampaignFailed` if the campaign is not open for refunds,
+        /// `Error::NotInsolvent` if the available amount covers the outstanding refunds,
+        /// or `Error::InsufficientFunds` if nothing at all is available.
+        #[ink(message)]
+        pub fn enable_pro_rata_refunds(&mut self, campaign_id: u32) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+
+            let campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+            if campaign.state != CampaignState::Failed && !campaign.refundable {
+                return Err(Error::CampaignFailed);
+            }
+
+            let owed = campaign.raised
+                .saturating_sub(self.campaign_refunded.get(campaign_id).unwrap_or(0));
+            // Only what isn't owed to other campaigns, rounds, the pool or the treasury
+            let owed_elsewhere = self.get_liabilities()
+                .saturating_sub(self.campaign_liabilities.get(campaign_id).unwrap_or(0));
+            let available = self.env().balance().saturating_sub(owed_elsewhere);
+            if available >= owed {
+                return Err(Error::NotInsolvent);
+            }
+            if available == 0 {
+                return Err(Error::InsufficientFunds);
+            }
+
+            self.pro_rata_refunds.insert(campaign_id, &(available, owed));
+
+            self.env().emit_event(ProRataRefundsEnabled {
                 campaign_id,
-                milestone_index,
-                voter: caller,
-                approve,
-                weight: voter_weight,
+                available,
+                owed,
             });
 
             Ok(())
         }
 
-        /// Release milestone funds if voting passes (owner or admin).
+        /// Returns the pro-rata refund state of a campaign.
         ///
-        /// Requires >66% approval (weighted by donation amount).
+        /// # Returns
+        ///
+        /// `Some((remaining_available, remaining_owed))` while pro-rata refunds are
+        /// active, or `None` if refunds are paid in full.
+        #[ink(message)]
+        pub fn get_pro_rata_refunds(&self, campaign_id: u32) -> Option<(Balance, Balance)> {
+            self.pro_rata_refunds.get(campaign_id)
+        }
+
+        /// Opens refunds on a campaign whose funds were never withdrawn (admin only).
+        ///
+        /// This is an emergency path for campaigns stuck in `Successful` (or `Active`)
+        /// because the owner disappeared. It can only be used once
+        /// `FORCE_REFUND_GRACE_PERIOD` has elapsed since the deadline. Afterwards
+        /// `claim_refund` works as for a failed campaign and the owner can no longer
+        /// withdraw.
+        ///
+        /// On success, a `CampaignForcedRefundable` event is emitted.
         ///
         /// # Arguments
         ///
-        /// * `campaign_id` - The campaign.
-        /// * `milestone_index` - Which milestone to release.
+        /// * `campaign_id` - The ID of the campaign to open refunds for.
         ///
         /// # Returns
         ///
-        /// - `Ok(())` on success.
+        /// - `Ok(())`: If the campaign is now refundable.
+        /// - `Err(Error)`: If the caller is not the admin or the campaign is not eligible.
+        ///
+        /// # Errors
+        ///
+        /// Returns `Error::NotCampaignOwner` if the caller is not the admin,
+        /// `Error::FundsAlreadyWithdrawn` if the campaign was withdrawn, or
+        /// `Error::GracePeriodActive` if the grace period has not elapsed yet.
         #[ink(message)]
-        pub fn release_milestone_funds(
-            &mut self,
-            campaign_id: u32,
-            milestone_index: u32,
-        ) -> Result<(), Error> {
+        pub fn force_refundable(&mut self, campaign_id: u32) -> Result<(), Error> {
             let caller = self.env().caller();
-
-            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
-
-            // Only owner or admin can trigger release
-            if caller != campaign.owner && caller != self.admin {
+            if caller != self.admin {
                 return Err(Error::NotCampaignOwner);
             }
 
-            let idx = milestone_index as usize;
-            if idx >= campaign.milestones.len() {
-                return Err(Error::CampaignNotFound);
-            }
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
 
-            // Already released
-            if campaign.milestones[idx].released {
+            if campaign.state == CampaignState::Withdrawn
+                || campaign.matching_withdrawn
+                || campaign.milestones.iter().any(|m| m.released)
+            {
                 return Err(Error::FundsAlreadyWithdrawn);
             }
 
-            // Voting must be active
-            if !campaign.milestones[idx].voting_active {
-                return Err(Error::CampaignNotActive);
+            let grace_end = campaign.deadline.saturating_add(FORCE_REFUND_GRACE_PERIOD);
+            if self.env().block_timestamp() <= grace_end {
+                return Err(Error::GracePeriodActive);
             }
 
-            // Check approval threshold (66%)
-            let total_votes = campaign.milestones[idx].votes_for + campaign.milestones[idx].votes_against;
-            if total_votes == 0 {
-                return Err(Error::InsufficientFunds); // Reusing - means no votes yet
-            }
+            campaign.refundable = true;
+            campaign.failed_at.get_or_insert(self.env().block_timestamp());
+            self.save_campaign(campaign_id, &campaign);
 
-            let approval_percentage = (campaign.milestones[idx].votes_for as u128 * 100) / (total_votes as u128);
-            if approval_percentage < 66 {
-                return Err(Error::GoalNotReached); // Reusing - means not enough approval
+            self.env().emit_event(CampaignForcedRefundable {
+                campaign_id,
+                forced_by: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Sends the unclaimed refunds of a campaign to its fallback (owner or admin).
+        ///
+        /// Only possible once the campaign's refund window has closed. Everything the
+        /// campaign still owes its donors goes to the fallback account (in pro-rata mode,
+        /// what is left of the available balance), after which no refunds remain.
+        ///
+        /// On success, an `UnclaimedRefundsSwept` event is emitted.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The ID of the failed or refundable campaign.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(Balance)`: The amount sent to the fallback.
+        /// - `Err(Error)`: If the caller is not authorized or nothing can be swept.
+        ///
+        /// # Errors
+        ///
+        /// Returns `Error::NotCampaignOwner` if the caller is not the owner or admin,
+        /// `Error::CampaignFailed` if the campaign is not open for refunds,
+        /// `Error::RefundWindowOpen` if the window has no end or hasn't closed yet,
+        /// `Error::NoDonationFound` if nothing is left, or `Error::WithdrawalFailed`
+        /// if the transfer fails.
+        #[ink(message)]
+        pub fn sweep_unclaimed_refunds(&mut self, campaign_id: u32) -> Result<Balance, Error> {
+            // Check and acquire lock
+            if self.locked {
+                return Err(Error::ReentrantCall);
             }
+            self.locked = true;
 
-            // Calculate amount to release (percentage of total raised + matching)
-            let total_campaign_funds = campaign.raised.saturating_add(campaign.matching_amount);
-            let milestone_amount = ((total_campaign_funds as u128) * (campaign.milestones[idx].percentage as u128) / 10000) as Balance;
+            let result = (|| {
+                let caller = self.env().caller();
+                let campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
 
-            // Transfer funds to beneficiary
-            if milestone_amount > 0 {
-                if self.env().transfer(campaign.beneficiary, milestone_amount).is_err() {
+                if caller != campaign.owner && caller != self.admin {
+                    return Err(Error::NotCampaignOwner);
+                }
+                if campaign.state != CampaignState::Failed && !campaign.refundable {
+                    return Err(Error::CampaignFailed);
+                }
+                let fallback = match campaign.refund_fallback {
+                    Some(fallback) if campaign.refund_window > 0 && !self.refund_window_open(&campaign) => fallback,
+                    _ => return Err(Error::RefundWindowOpen),
+                };
+
+                let owed = campaign.raised
+                    .saturating_sub(self.campaign_refunded.get(campaign_id).unwrap_or(0));
+                let amount = self.refund_payout(campaign_id, owed);
+                if owed == 0 || amount == 0 {
+                    return Err(Error::NoDonationFound);
+                }
+
+                self.record_refund(campaign_id, owed, amount);
+                if self.env().transfer(fallback, amount).is_err() {
                     return Err(Error::WithdrawalFailed);
                 }
-            }
 
-            // Mark as released
-            campaign.milestones[idx].released = true;
-            campaign.milestones[idx].voting_active = false;
+                self.env().emit_event(UnclaimedRefundsSwept {
+                    campaign_id,
+                    fallback,
+                    amount,
+                });
 
-            // If all milestones released, mark campaign as withdrawn
-            let all_released = campaign.milestones.iter().all(|m| m.released);
-            if all_released {
-                campaign.state = CampaignState::Withdrawn;
-            }
+                Ok(amount)
+            })();
 
-            self.campaigns.insert(campaign_id, &campaign);
+            // Always unlock before returning
+            self.locked = false;
+            result
+        }
 
-            self.env().emit_event(MilestoneFundsReleased {
-                campaign_id,
-                milestone_index,
-                amount: milestone_amount,
-                beneficiary: campaign.beneficiary,
-            });
+        /// Retrieves a campaign by its ID.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The ID of the campaign to retrieve.
+        ///
+        /// # Returns
+        ///
+        /// - `Some(Campaign)`: The campaign data if found.
+        /// - `None`: If no campaign with the given ID exists.
+        #[ink(message)]
+        pub fn get_campaign(&self, campaign_id: u32) -> Option<Campaign> {
+            self.campaigns.get(campaign_id)
+        }
 
-            Ok(())
+        /// Gets only the state of a campaign.
+        ///
+        /// Lets other contracts (e.g. the NFT contract) check a campaign without
+        /// decoding the full `Campaign` struct.
+        #[ink(message)]
+        pub fn get_campaign_state(&self, campaign_id: u32) -> Option<CampaignState> {
+            self.campaigns.get(campaign_id).map(|c| c.state)
         }
 
-        /// Get milestone details for a campaign.
+        /// Checks whether a campaign has raised at least its goal.
+        ///
+        /// Returns `false` for unknown campaigns.
         #[ink(message)]
-        pub fn get_milestones(&self, campaign_id: u32) -> Option<Vec<Milestone>> {
-            let campaign = self.campaigns.get(campaign_id)?;
-            Some(campaign.milestones)
+        pub fn is_goal_reached(&self, campaign_id: u32) -> bool {
+            self.campaigns
+                .get(campaign_id)
+                .map(|c| c.raised >= c.goal)
+                .unwrap_or(false)
         }
 
-        /// Check if a donor has voted on a milestone.
+        /// Gets the amount still needed for a campaign to reach its goal.
+        ///
+        /// Saturates at 0 for fully funded or overfunded campaigns, and returns 0
+        /// for unknown campaigns.
         #[ink(message)]
-        pub fn has_voted_on_milestone(
-            &self,
-            campaign_id: u32,
-            milestone_index: u32,
-            voter: AccountId,
-        ) -> bool {
-            let vote_key = (campaign_id, milestone_index, voter);
-            self.milestone_votes.get(vote_key).is_some()
+        pub fn remaining_to_goal(&self, campaign_id: u32) -> Balance {
+            self.campaigns
+                .get(campaign_id)
+                .map(|c| c.goal.saturating_sub(c.raised))
+                .unwrap_or(0)
         }
 
-        /// Get voter's weight on a milestone.
+        /// Retrieves the details of a campaign, including paginated donations.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The ID of the campaign to retrieve details for.
+        /// * `offset` - The starting index for the donation pagination.
+        /// * `limit` - The maximum number of donations to return.
+        ///
+        /// # Returns
+        ///
+        /// - `Some(CampaignDetails)`: The campaign details if the campaign is found.
+        /// - `None`: If the campaign does not exist.
         #[ink(message)]
-        pub fn get_vote_weight(
-            &self,
-            campaign_id: u32,
-            milestone_index: u32,
-            voter: AccountId,
-        ) -> Balance {
-            let vote_key = (campaign_id, milestone_index, voter);
-            self.milestone_votes.get(vote_key).unwrap_or(0)
+        pub fn get_campaign_details(&self, campaign_id: u32, offset: u32, limit: u32) -> Option<CampaignDetails> {
+            let campaign = self.campaigns.get(campaign_id)?;
+            let total_donations = campaign.donation_count;
+            let end = offset.saturating_add(limit).min(total_donations);
+
+            let mut donations = Vec::new();
+            for index in offset..end {
+                if let Some(donation) = self.campaign_donations.get((campaign_id, index)) {
+                    donations.push(donation);
+                }
+            }
+
+            Some(CampaignDetails {
+                campaign,
+                donations,
+                total_donations,
+            })
         }
-    }
 
-    // Events
-    /// Emitted when a new campaign is created.
+        /// Cursor pagination over a campaign's donations, for incremental indexer sync.
+        ///
+        /// Donation indices are assigned sequentially from `donation_count` and never
+        /// reused, so an index is a stable cursor. Archived donations are skipped.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The campaign.
+        /// * `cursor` - Return donations with an index above this; `None` starts at the beginning.
+        /// * `limit` - Maximum donations to return (capped at `MAX_QUERY_LIMIT`).
+        ///
+        /// # Returns
+        ///
+        /// `(index, donation)` pairs in index order, and the cursor for the next page
+        /// (`None` once the last donation has been returned).
+        #[ink(message)]
+        pub fn get_donations_after(
+            &self,
+            campaign_id: u32,
+            cursor: Option<u32>,
+            limit: u32,
+        ) -> (Vec<(u32, Donation)>, Option<u32>) {
+            let Some(campaign) = self.campaigns.get(campaign_id) else {
+                return (Vec::new(), None);
+            };
+
+            let start = cursor
+                .map_or(0, |c| c.saturating_add(1))
+                .max(campaign.archived_donation_count);
+            let end = start
+                .saturating_add(limit.min(MAX_QUERY_LIMIT))
+                .min(campaign.donation_count);
+
+            let mut donations = Vec::new();
+            for index in start..end {
+                if let Some(donation) = self.campaign_donations.get((campaign_id, index)) {
+                    donations.push((index, donation));
+                }
+            }
+
+            let next_cursor = if end < campaign.donation_count && end > start {
+                Some(end - 1)
+            } else {
+                None
+            };
+
+            (donations, next_cursor)
+        }
+
+        /// Retrieves a paginated list of all campaigns.
+        ///
+        /// # Arguments
+        ///
+        /// * `offset` - The starting index for the campaign pagination.
+        /// * `limit` - The maximum number of campaigns to return.
+        ///
+        /// # Returns
+        ///
+        /// A vector of `Campaign` structs.
+        #[ink(message)]
+        pub fn get_campaigns_paginated(&self, offset: u32, limit: u32) -> Vec<Campaign> {
+            let mut campaigns = Vec::new();
+            // Saturate so a huge offset + limit can't wrap around to a small `end`
+            let start = offset.min(self.campaign_count);
+            let end = offset.saturating_add(limit).min(self.campaign_count);
+
+            for i in start..end {
+                if let Some(campaign) = self.campaigns.get(i) {
+                    campaigns.push(campaign);
+                }
+            }
+
+            campaigns
+        }
+
+        /// Retrieves all active campaigns (paginated).
+        ///
+        /// # Arguments
+        ///
+        /// * `offset` - The campaign ID to start scanning from.
+        /// * `limit` - The maximum number of active campaigns to return (capped at `MAX_QUERY_LIMIT`).
+        ///
+        /// # Returns
+        ///
+        /// A vector of active `Campaign` structs, from at most `MAX_CAMPAIGN_SCAN` campaigns;
+        /// use `get_campaigns_by_state` to resume past them.
+        #[ink(message)]
+        pub fn get_active_campaigns(&self, offset: u32, limit: u32) -> Vec<Campaign> {
+            self.get_campaigns_by_state(CampaignState::Active, offset.checked_sub(1), limit).0
+        }
+
+        /// Retrieves campaigns in a given state (cursor-paginated).
+        ///
+        /// # Arguments
+        ///
+        /// * `state` - The state to filter by.
+        /// * `cursor` - Scan campaigns with an ID above this; `None` starts at the beginning.
+        /// * `limit` - The maximum number of campaigns to return (capped at `MAX_QUERY_LIMIT`).
+        ///
+        /// # Returns
+        ///
+        /// `Campaign` structs in `state`, in ID order, and the cursor to resume from
+        /// (`None` once every campaign has been scanned). A call examines at most
+        /// `MAX_CAMPAIGN_SCAN` campaigns, so a page may be short or empty while the
+        /// cursor is still `Some`.
+        #[ink(message)]
+        pub fn get_campaigns_by_state(
+            &self,
+            state: CampaignState,
+            cursor: Option<u32>,
+            limit: u32,
+        ) -> (Vec<Campaign>, Option<u32>) {
+            self.scan_campaigns(cursor, limit, |_, campaign| {
+                (campaign.state == state).then_some(campaign)
+            })
+        }
+
+        /// Scans campaigns with an ID above `cursor`, at most `MAX_CAMPAIGN_SCAN` of
+        /// them, collecting what `select` returns until `limit` results are found.
+        ///
+        /// Returns the results and the cursor to resume from (`None` once every
+        /// campaign has been scanned).
+        fn scan_campaigns<T>(
+            &self,
+            cursor: Option<u32>,
+            limit: u32,
+            mut select: impl FnMut(u32, Campaign) -> Option<T>,
+        ) -> (Vec<T>, Option<u32>) {
+            let limit = limit.min(MAX_QUERY_LIMIT) as usize;
+            let start = cursor.map_or(0, |c| c.saturating_add(1));
+            let end = start.saturating_add(MAX_CAMPAIGN_SCAN).min(self.campaign_count);
+
+            let mut results = Vec::new();
+            let mut id = start;
+            while id < end && results.len() < limit {
+                if let Some(item) = self.campaigns.get(id).and_then(|campaign| select(id, campaign)) {
+                    results.push(item);
+                }
+                id += 1;
+            }
+
+            let next_cursor = if id < self.campaign_count && id > start {
+                Some(id - 1)
+            } else {
+                None
+            };
+
+            (results, next_cursor)
+        }
+
+        /// Retrieves active campaigns whose deadline falls within a time window (cursor-paginated).
+        ///
+        /// # Arguments
+        ///
+        /// * `within_ms` - The window, in milliseconds from now.
+        /// * `cursor` - Scan campaigns with an ID above this; `None` starts at the beginning.
+        /// * `limit` - The maximum number of campaigns to return (capped at `MAX_QUERY_LIMIT`).
+        ///
+        /// # Returns
+        ///
+        /// Active `Campaign` structs with `now < deadline <= now + within_ms`, and the
+        /// cursor to resume from (`None` once every campaign has been scanned). A call
+        /// examines at most `MAX_CAMPAIGN_SCAN` campaigns.
+        #[ink(message)]
+        pub fn get_campaigns_ending_soon(
+            &self,
+            within_ms: u64,
+            cursor: Option<u32>,
+            limit: u32,
+        ) -> (Vec<Campaign>, Option<u32>) {
+            let current_time = self.env().block_timestamp();
+            let window_end = current_time.saturating_add(within_ms);
+
+            self.scan_campaigns(cursor, limit, |_, campaign| {
+                (campaign.state == CampaignState::Active
+                    && campaign.deadline > current_time
+                    && campaign.deadline <= window_end)
+                    .then_some(campaign)
+            })
+        }
+
+        /// Retrieves campaigns that use milestone governance (cursor-paginated).
+        ///
+        /// # Arguments
+        ///
+        /// * `cursor` - Scan campaigns with an ID above this; `None` starts at the beginning.
+        /// * `limit` - The maximum number of campaigns to return (capped at `MAX_QUERY_LIMIT`).
+        ///
+        /// # Returns
+        ///
+        /// `Campaign` structs with `uses_milestones` set, in any state, and the cursor to
+        /// resume from (`None` once every campaign has been scanned). A call examines at
+        /// most `MAX_CAMPAIGN_SCAN` campaigns.
+        #[ink(message)]
+        pub fn get_milestone_campaigns(&self, cursor: Option<u32>, limit: u32) -> (Vec<Campaign>, Option<u32>) {
+            self.scan_campaigns(cursor, limit, |_, campaign| campaign.uses_milestones.then_some(campaign))
+        }
+
+        /// Sets the free-form tags of a campaign (owner only), replacing any previous tags.
+        ///
+        /// Tags are matched exactly by `get_campaigns_by_tag`.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The ID of the campaign.
+        /// * `tags` - Up to `MAX_TAGS_PER_CAMPAIGN` distinct, non-empty tags of at most
+        ///   `MAX_TAG_LENGTH` bytes each.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error)`: `NotCampaignOwner` or `InvalidTag`.
+        #[ink(message)]
+        pub fn set_campaign_tags(&mut self, campaign_id: u32, tags: Vec<String>) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+
+            if caller != campaign.owner {
+                return Err(Error::NotCampaignOwner);
+            }
+
+            if tags.len() > MAX_TAGS_PER_CAMPAIGN {
+                return Err(Error::InvalidTag);
+            }
+            for (i, tag) in tags.iter().enumerate() {
+                if tag.is_empty() || tag.len() > MAX_TAG_LENGTH || tags[..i].contains(tag) {
+                    return Err(Error::InvalidTag);
+                }
+            }
+
+            // Drop the campaign from the index of its old tags
+            for tag in self.campaign_tags.get(campaign_id).unwrap_or_default() {
+                let mut ids = self.tag_index.get(&tag).unwrap_or_default();
+                ids.retain(|id| *id != campaign_id);
+                if ids.is_empty() {
+                    self.tag_index.remove(&tag);
+                } else {
+                    self.tag_index.insert(&tag, &ids);
+                }
+            }
+
+            for tag in &tags {
+                let mut ids = self.tag_index.get(tag).unwrap_or_default();
+                ids.push(campaign_id);
+                self.tag_index.insert(tag, &ids);
+            }
+
+            if tags.is_empty() {
+                self.campaign_tags.remove(campaign_id);
+            } else {
+                self.campaign_tags.insert(campaign_id, &tags);
+            }
+
+            Ok(())
+        }
+
+        /// Gets the tags of a campaign.
+        #[ink(message)]
+        pub fn get_campaign_tags(&self, campaign_id: u32) -> Vec<String> {
+            self.campaign_tags.get(campaign_id).unwrap_or_default()
+        }
+
+        /// Retrieves the IDs of campaigns carrying an exact tag (paginated).
+        ///
+        /// # Arguments
+        ///
+        /// * `tag` - The tag to look up.
+        /// * `offset` - The number of matching campaigns to skip.
+        /// * `limit` - The maximum number of IDs to return (capped at `MAX_QUERY_LIMIT`).
+        ///
+        /// # Returns
+        ///
+        /// A vector of campaign IDs, in tagging order.
+        #[ink(message)]
+        pub fn get_campaigns_by_tag(&self, tag: String, offset: u32, limit: u32) -> Vec<u32> {
+            let limit = limit.min(MAX_QUERY_LIMIT) as usize;
+
+            self.tag_index
+                .get(&tag)
+                .unwrap_or_default()
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit)
+                .collect()
+        }
+
+        /// Retrieves the IDs of campaigns paying out to a beneficiary (paginated).
+        ///
+        /// The beneficiary need not be the campaign owner.
+        ///
+        /// # Arguments
+        ///
+        /// * `beneficiary` - The account receiving the campaign funds.
+        /// * `offset` - The number of matching campaigns to skip.
+        /// * `limit` - The maximum number of IDs to return (capped at `MAX_QUERY_LIMIT`).
+        ///
+        /// # Returns
+        ///
+        /// A vector of campaign IDs, in creation order.
+        #[ink(message)]
+        pub fn get_campaigns_by_beneficiary(&self, beneficiary: AccountId, offset: u32, limit: u32) -> Vec<u32> {
+            let limit = limit.min(MAX_QUERY_LIMIT) as usize;
+
+            self.beneficiary_index
+                .get(beneficiary)
+                .unwrap_or_default()
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit)
+                .collect()
+        }
+
+        /// Gets the contract version.
+        ///
+        /// # Returns
+        ///
+        /// The current version number of the contract logic.
+        #[ink(message)]
+        pub fn get_version(&self) -> u32 {
+            self.version
+        }
+
+        /// Gets the contract-wide settings in one call, so frontends can bootstrap
+        /// without querying each getter separately.
+        #[ink(message)]
+        pub fn get_config(&self) -> PlatformConfig {
+            PlatformConfig {
+                version: self.version,
+                max_batch_size: self.max_batch_size,
+                nft_enabled: self.nft_enabled,
+                nft_contract: self.nft_contract,
+                matching_pool_balance: self.matching_pool_balance,
+                current_round: self.current_round,
+                fee_bps: self.fee_bps,
+                paused: self.paused,
+                treasury: self.treasury_account,
+            }
+        }
+
+        /// Gets the total campaign count.
+        ///
+        /// # Returns
+        ///
+        /// The total number of campaigns ever created in the contract.
+        #[ink(message)]
+        pub fn get_campaign_count(&self) -> u32 {
+            self.campaign_count
+        }
+
+        /// Gets the number of campaigns currently accepting donations.
+        ///
+        /// Maintained as a counter, so no scan is needed. A campaign whose deadline
+        /// passed but that nobody has touched since still counts as active.
+        #[ink(message)]
+        pub fn get_active_campaign_count(&self) -> u32 {
+            self.active_count
+        }
+
+        /// Summarizes a range of campaigns so an indexer can reconcile its state without
+        /// replaying every event.
+        ///
+        /// # Arguments
+        ///
+        /// * `from_campaign` - The first campaign ID (inclusive).
+        /// * `to_campaign` - The last campaign ID (exclusive). The range is clamped to
+        ///   existing campaigns and to at most `MAX_QUERY_LIMIT` campaigns.
+        ///
+        /// # Returns
+        ///
+        /// An `IndexerSnapshot` with the current block and a `CampaignSnapshot` per campaign.
+        #[ink(message)]
+        pub fn get_events_summary(&self, from_campaign: u32, to_campaign: u32) -> IndexerSnapshot {
+            let end = to_campaign
+                .min(self.campaign_count)
+                .min(from_campaign.saturating_add(MAX_QUERY_LIMIT));
+
+            let campaigns = (from_campaign..end)
+                .filter_map(|id| self.campaigns.get(id))
+                .map(|campaign| CampaignSnapshot {
+                    id: campaign.id,
+                    state: campaign.state,
+                    raised: campaign.raised,
+                    donation_count: campaign.donation_count,
+                })
+                .collect();
+
+            IndexerSnapshot {
+                block_number: self.env().block_number(),
+                campaign_count: self.campaign_count,
+                campaigns,
+            }
+        }
+
+        /// Marks the contract as deprecated, or clears the mark (admin only).
+        ///
+        /// Deprecation only unlocks `emergency_withdraw`; everything else keeps working
+        /// so donors can still claim refunds and beneficiaries can withdraw.
+        #[ink(message)]
+        pub fn set_deprecated(&mut self, deprecated: bool) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+
+            self.deprecated = deprecated;
+            Ok(())
+        }
+
+        /// Whether the contract is marked as deprecated.
+        #[ink(message)]
+        pub fn is_deprecated(&self) -> bool {
+            self.deprecated
+        }
+
+        /// Transfers residual balance out of a deprecated contract (admin only).
+        ///
+        /// Only the free balance, i.e. the contract balance minus `get_liabilities`,
+        /// can be withdrawn, so funds owed to donors, beneficiaries, matching and the
+        /// treasury stay put. Emits an `EmergencyWithdrawal` event.
+        ///
+        /// # Arguments
+        ///
+        /// * `amount` - The amount to withdraw.
+        /// * `to` - The destination account.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotCampaignOwner`, `NotDeprecated`, `InsufficientFunds` if `amount`
+        /// exceeds the free balance, or `TransferFailed`.
+        #[ink(message)]
+        pub fn emergency_withdraw(&mut self, amount: Balance, to: AccountId) -> Result<(), Error> {
+            // Check and acquire lock
+            if self.locked {
+                return Err(Error::ReentrantCall);
+            }
+            self.locked = true;
+
+            let result = self.process_emergency_withdrawal(amount, to);
+
+            // Always unlock before returning
+            self.locked = false;
+            result
+        }
+
+        /// The internal logic for an emergency withdrawal.
+        fn process_emergency_withdrawal(&mut self, amount: Balance, to: AccountId) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+            if !self.deprecated {
+                return Err(Error::NotDeprecated);
+            }
+            if amount == 0 || amount > self.get_free_balance() {
+                return Err(Error::InsufficientFunds);
+            }
+
+            if self.env().transfer(to, amount).is_err() {
+                return Err(Error::TransferFailed);
+            }
+
+            self.env().emit_event(EmergencyWithdrawal { to, amount });
+
+            Ok(())
+        }
+
+        /// Gets an upper bound of the funds the contract owes.
+        ///
+        /// Sums what campaigns still hold (including unclaimed refunds, unvested amounts
+        /// and matching not yet withdrawn), undistributed round pools, the matching pool
+        /// and accrued fees. Refunds already claimed from failed campaigns are no longer
+        /// counted. The totals are kept up to date as campaigns and rounds change.
+        #[ink(message)]
+        pub fn get_liabilities(&self) -> Balance {
+            self.matching_pool_balance
+                .saturating_add(self.accrued_fees)
+                .saturating_add(self.total_campaign_liabilities)
+                .saturating_add(self.undistributed_round_pools)
+        }
+
+        /// Stores a campaign and updates its share of `get_liabilities`.
+        ///
+        /// Every write of a campaign goes through here so the running total stays exact.
+        fn save_campaign(&mut self, campaign_id: u32, campaign: &Campaign) {
+            self.campaigns.insert(campaign_id, campaign);
+
+            let previous = self.campaign_liabilities.get(campaign_id).unwrap_or(0);
+            let current = self.campaign_liability(campaign_id, campaign);
+            if previous != current {
+                self.total_campaign_liabilities = self.total_campaign_liabilities
+                    .saturating_sub(previous)
+                    .saturating_add(current);
+                self.campaign_liabilities.insert(campaign_id, &current);
+            }
+        }
+
+        /// Funds a campaign still holds for its beneficiary or donors.
+        fn campaign_liability(&self, campaign_id: u32, campaign: &Campaign) -> Balance {
+            let matching_due = if campaign.matching_withdrawn { 0 } else { campaign.matching_amount };
+
+            // Matching distributed after the withdrawal is still owed
+            if campaign.state == CampaignState::Withdrawn {
+                return campaign.vesting_total
+                    .saturating_sub(campaign.vesting_withdrawn)
+                    .saturating_add(matching_due);
+            }
+
+            let total_campaign_funds = campaign.raised.saturating_add(campaign.matching_amount);
+            let released: Balance = campaign.milestones
+                .iter()
+                .filter(|m| m.released)
+                .map(|m| total_campaign_funds.saturating_mul(Balance::from(m.percentage)) / 10000)
+                .sum();
+
+            let refunded = self.campaign_refunded.get(campaign_id).unwrap_or(0);
+            campaign.raised
+                .saturating_sub(refunded)
+                .saturating_add(matching_due)
+                .saturating_sub(released)
+        }
+
+        /// Gets the contract balance not owed to anyone (see `get_liabilities`).
+        #[ink(message)]
+        pub fn get_free_balance(&self) -> Balance {
+            self.env().balance().saturating_sub(self.get_liabilities())
+        }
+
+        /// Clears the reentrancy lock (admin only).
+        ///
+        /// Last-resort operational tool: if a call ever traps between taking and
+        /// releasing the lock, every locked message would fail with `ReentrantCall`
+        /// forever. Emits a `LockForceReset` event.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
+        #[ink(message)]
+        pub fn force_unlock(&mut self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if caller != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+
+            let was_locked = self.locked;
+            self.locked = false;
+
+            self.env().emit_event(LockForceReset {
+                admin: caller,
+                was_locked,
+            });
+
+            Ok(())
+        }
+
+        /// Recalculates a campaign's state from its data and corrects it (admin only).
+        ///
+        /// Operational fix for campaigns left in a stale state. The expected state is
+        /// `Successful` if the goal (or soft cap) is met, otherwise `Failed` once the
+        /// deadline has passed, otherwise `Active`. `Failed`, `Withdrawn` and `Merged`
+        /// campaigns are never reopened, since refunds or payouts may already have
+        /// happened. Emits a
+        /// `CampaignStateChanged` event if the state changes.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The campaign to fix.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(CampaignState)`: The campaign's state after the recalculation.
+        /// - `Err(Error)`: `NotCampaignOwner` if the caller is not the admin, or `CampaignNotFound`.
+        #[ink(message)]
+        pub fn recompute_campaign_state(&mut self, campaign_id: u32) -> Result<CampaignState, Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+            let old_state = campaign.state;
+            if matches!(old_state, CampaignState::Failed | CampaignState::Withdrawn | CampaignState::Merged) {
+                return Ok(old_state);
+            }
+
+            let new_state = if campaign.raised >= Self::success_threshold(&campaign) {
+                CampaignState::Successful
+            } else if self.env().block_timestamp() > campaign.deadline {
+                CampaignState::Failed
+            } else {
+                CampaignState::Active
+            };
+
+            if new_state != old_state {
+                if new_state == CampaignState::Successful {
+                    // Same path as a donation reaching the goal, so the NFT contract,
+                    // milestone voting and the success hook all see it
+                    let current_time = self.env().block_timestamp();
+                    self.store_campaign_progress(campaign_id, &mut campaign, current_time);
+                } else {
+                    self.set_state(&mut campaign, new_state);
+                    self.save_campaign(campaign_id, &campaign);
+                }
+
+                self.env().emit_event(CampaignStateChanged {
+                    campaign_id,
+                    old_state,
+                    new_state,
+                });
+            }
+
+            Ok(new_state)
+        }
+
+        /// Sets the minimum campaign goal required to add milestones (admin only).
+        ///
+        /// # Arguments
+        ///
+        /// * `min_goal` - The threshold; 0 disables the check.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
+        #[ink(message)]
+        pub fn set_min_goal_for_milestones(&mut self, min_goal: Balance) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+            self.min_goal_for_milestones = min_goal;
+            Ok(())
+        }
+
+        /// Gets the minimum campaign goal required to add milestones.
+        #[ink(message)]
+        pub fn get_min_goal_for_milestones(&self) -> Balance {
+            self.min_goal_for_milestones
+        }
+
+        /// Sets how long milestone voting must stay open before a release (admin only).
+        ///
+        /// # Arguments
+        ///
+        /// * `duration` - The minimum voting period in milliseconds; 0 disables the check.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
+        #[ink(message)]
+        pub fn set_min_voting_duration(&mut self, duration: u64) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+            self.min_voting_duration = duration;
+            Ok(())
+        }
+
+        /// Gets the minimum milestone voting period in milliseconds.
+        #[ink(message)]
+        pub fn get_min_voting_duration(&self) -> u64 {
+            self.min_voting_duration
+        }
+
+        /// Lowers the platform fee taken from each donation (admin only).
+        ///
+        /// The new rate applies to future donations; fees already taken are unaffected.
+        /// Raising the fee has to go through the timelock (`queue_admin_action` with
+        /// `AdminAction::SetFee`) so donors get notice. Emits a `PlatformFeeChanged` event.
+        ///
+        /// # Arguments
+        ///
+        /// * `new_fee` - The fee in basis points, at most the current fee.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
+        /// - `Err(Error::InvalidFee)` if the fee is higher than the current one.
+        #[ink(message)]
+        pub fn set_platform_fee(&mut self, new_fee: u32) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+            if new_fee > self.fee_bps {
+                return Err(Error::InvalidFee);
+            }
+
+            let old_fee = self.fee_bps;
+            self.fee_bps = new_fee;
+
+            self.env().emit_event(PlatformFeeChanged { old_fee, new_fee });
+
+            Ok(())
+        }
+
+        /// Gets the platform fee in basis points.
+        #[ink(message)]
+        pub fn get_platform_fee(&self) -> u32 {
+            self.fee_bps
+        }
+
+        /// Splits a donation amount into the platform fee and what reaches the campaign,
+        /// at the fee rate `donor` currently pays (see `get_donor_fee_bps`).
+        ///
+        /// # Returns
+        ///
+        /// `(fee, net_to_campaign)`, which always add up to `amount`.
+        #[ink(message)]
+        pub fn quote_donation(&self, donor: AccountId, amount: Balance) -> (Balance, Balance) {
+            let fee = self.fee_for(donor, amount);
+            (fee, amount - fee)
+        }
+
+        /// The platform fee `donor` pays on `amount`, rounded down: `amount * fee_bps / 10000`
+        /// at their (possibly discounted) rate, computed without overflowing.
+        fn fee_for(&self, donor: AccountId, amount: Balance) -> Balance {
+            Self::fee_at(amount, self.get_donor_fee_bps(donor))
+        }
+
+        /// The fee on `amount` at `fee_bps` basis points, rounded down.
+        fn fee_at(amount: Balance, fee_bps: u32) -> Balance {
+            let bps = Balance::from(fee_bps);
+            (amount / 10000) * bps + (amount % 10000) * bps / 10000
+        }
+
+        /// Sets the fee discount tiers for repeat donors (admin only).
+        ///
+        /// A donor whose lifetime donations net of refunds (`get_donor_total_contributions`) reach a
+        /// tier's threshold pays that tier's fee instead of the platform fee, if lower.
+        /// The highest tier reached applies. An empty list disables discounts.
+        ///
+        /// # Arguments
+        ///
+        /// * `tiers` - `(lifetime_threshold, fee_bps)` pairs with strictly ascending
+        ///   thresholds, at most `MAX_FEE_DISCOUNT_TIERS`.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotCampaignOwner`, or `InvalidFee` for a malformed tier list.
+        #[ink(message)]
+        pub fn set_fee_discount_tiers(&mut self, tiers: Vec<(Balance, u32)>) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+            if tiers.len() > MAX_FEE_DISCOUNT_TIERS
+                || tiers.iter().any(|(_, fee_bps)| *fee_bps > MAX_FEE_BPS)
+                || tiers.windows(2).any(|pair| pair[0].0 >= pair[1].0)
+            {
+                return Err(Error::InvalidFee);
+            }
+
+            self.fee_discount_tiers = tiers;
+            Ok(())
+        }
+
+        /// Gets the fee discount tiers.
+        #[ink(message)]
+        pub fn get_fee_discount_tiers(&self) -> Vec<(Balance, u32)> {
+            self.fee_discount_tiers.clone()
+        }
+
+        /// Gets the fee (in basis points) a donor's next donation pays.
+        #[ink(message)]
+        pub fn get_donor_fee_bps(&self, donor: AccountId) -> u32 {
+            let lifetime = self.donor_total_contributions.get(donor).unwrap_or(0);
+            self.fee_discount_tiers
+                .iter()
+                .rev()
+                .find(|(threshold, _)| lifetime >= *threshold)
+                .map(|(_, fee_bps)| (*fee_bps).min(self.fee_bps))
+                .unwrap_or(self.fee_bps)
+        }
+
+        /// Queues a sensitive admin change behind the timelock (admin only).
+        ///
+        /// The action can be executed with `execute_admin_action` once `eta` has passed,
+        /// giving users time to react. Emits an `AdminActionQueued` event.
+        ///
+        /// # Arguments
+        ///
+        /// * `action` - The change to make.
+        /// * `eta` - The earliest execution time, at least `MIN_ADMIN_ACTION_DELAY` from now.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(u32)`: The ID of the queued action.
+        /// - `Err(Error)`: `NotCampaignOwner`, `InvalidDeadline` if the ETA is too soon,
+        ///   or `InvalidFee` for a fee above `MAX_FEE_BPS`.
+        #[ink(message)]
+        pub fn queue_admin_action(&mut self, action: AdminAction, eta: Timestamp) -> Result<u32, Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+            if eta < self.env().block_timestamp().saturating_add(MIN_ADMIN_ACTION_DELAY) {
+                return Err(Error::InvalidDeadline);
+            }
+            if let AdminAction::SetFee(fee) = action {
+                if fee > MAX_FEE_BPS {
+                    return Err(Error::InvalidFee);
+                }
+            }
+
+            let action_id = self.next_action_id;
+            self.queued_actions.insert(action_id, &(action.clone(), eta));
+            self.next_action_id = self.next_action_id.saturating_add(1);
+
+            self.env().emit_event(AdminActionQueued { action_id, action, eta });
+
+            Ok(action_id)
+        }
+
+        /// Executes a queued admin action once its ETA has passed (admin only).
+        ///
+        /// Emits an `AdminActionExecuted` event (plus `PlatformFeeChanged` for fee changes).
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotCampaignOwner`, `AdminActionNotFound`, or `TimelockNotExpired`.
+        #[ink(message)]
+        pub fn execute_admin_action(&mut self, action_id: u32) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+
+            let (action, eta) = self.queued_actions.get(action_id).ok_or(Error::AdminActionNotFound)?;
+            if self.env().block_timestamp() < eta {
+                return Err(Error::TimelockNotExpired);
+            }
+            self.queued_actions.remove(action_id);
+
+            match action {
+                AdminAction::SetFee(new_fee) => {
+                    let old_fee = self.fee_bps;
+                    self.fee_bps = new_fee;
+                    self.env().emit_event(PlatformFeeChanged { old_fee, new_fee });
+                }
+                AdminAction::SetTreasury(treasury) => self.treasury_account = treasury,
+                AdminAction::SetPaused(paused) => self.paused = paused,
+            }
+
+            self.env().emit_event(AdminActionExecuted { action_id, action });
+
+            Ok(())
+        }
+
+        /// Drops a queued admin action without executing it (admin only).
+        #[ink(message)]
+        pub fn cancel_admin_action(&mut self, action_id: u32) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+            if !self.queued_actions.contains(action_id) {
+                return Err(Error::AdminActionNotFound);
+            }
+
+            self.queued_actions.remove(action_id);
+            Ok(())
+        }
+
+        /// Gets a queued admin action and its ETA.
+        #[ink(message)]
+        pub fn get_queued_admin_action(&self, action_id: u32) -> Option<(AdminAction, Timestamp)> {
+            self.queued_actions.get(action_id)
+        }
+
+        /// Whether donations are paused.
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Sets the fee for creating a campaign (admin only).
+        ///
+        /// While the fee is non-zero, campaigns must be created with `create_campaign_paid`.
+        ///
+        /// # Arguments
+        ///
+        /// * `fee` - The creation fee; 0 makes creation free again.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
+        #[ink(message)]
+        pub fn set_campaign_creation_fee(&mut self, fee: Balance) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+            self.campaign_creation_fee = fee;
+            Ok(())
+        }
+
+        /// Gets the fee for creating a campaign.
+        #[ink(message)]
+        pub fn get_campaign_creation_fee(&self) -> Balance {
+            self.campaign_creation_fee
+        }
+
+        /// Updates the maximum batch size (admin only).
+        ///
+        /// # Arguments
+        ///
+        /// * `size` - The new maximum batch size.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
+        #[ink(message)]
+        pub fn set_max_batch_size(&mut self, size: u32) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+            self.max_batch_size = size;
+            Ok(())
+        }
+
+        /// Sets how many donations per campaign are kept in detail (admin only).
+        ///
+        /// Older donations are archived into a per-campaign and per-donor aggregate as
+        /// new ones arrive: `raised`, refunds and donor totals stay exact, but detailed
+        /// queries (and the QF score) only see the recent window.
+        ///
+        /// # Arguments
+        ///
+        /// * `max` - Donations to keep per campaign; 0 keeps all of them.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
+        #[ink(message)]
+        pub fn set_max_donation_history(&mut self, max: u32) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+            self.max_donation_history = max;
+            Ok(())
+        }
+
+        /// Gets how many donations per campaign are kept in detail (0 = unlimited).
+        #[ink(message)]
+        pub fn get_max_donation_history(&self) -> u32 {
+            self.max_donation_history
+        }
+
+        /// Gets a campaign's archived donations as `(count, total)`.
+        ///
+        /// Detailed records exist for indices `count..donation_count`.
+        #[ink(message)]
+        pub fn get_donation_archive(&self, campaign_id: u32) -> Option<(u32, Balance)> {
+            let campaign = self.campaigns.get(campaign_id)?;
+            Some((campaign.archived_donation_count, campaign.archived_donation_total))
+        }
+
+        /// Gets the maximum batch size.
+        ///
+        /// # Returns
+        ///
+        /// The maximum number of operations allowed in a single batch transaction.
+        #[ink(message)]
+        pub fn get_max_batch_size(&self) -> u32 {
+            self.max_batch_size
+        }
+
+        /// Gets the contract administrator.
+        ///
+        /// # Returns
+        ///
+        /// The account allowed to perform admin-only actions.
+        #[ink(message)]
+        pub fn get_admin(&self) -> AccountId {
+            self.admin
+        }
+
+        /// Hands a contract created with `new_uninitialized` to its admin and treasury
+        /// (deployer only, one-time).
+        ///
+        /// Contracts created with `new` or `migrate_from_v1` are already initialized.
+        ///
+        /// # Arguments
+        ///
+        /// * `admin` - The administrator account.
+        /// * `treasury` - The account receiving platform fees.
+        ///
+        /// # Errors
+        ///
+        /// Returns `AlreadyInitialized` if storage was already set up,
+        /// `NotCampaignOwner` if the caller isn't the deployer, or `ZeroAddress` if
+        /// either account is the zero address.
+        #[ink(message)]
+        pub fn initialize(&mut self, admin: AccountId, treasury: AccountId) -> Result<(), Error> {
+            if self.initialized {
+                return Err(Error::AlreadyInitialized);
+            }
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+            // There is no second chance to fix a bad address
+            let zero = AccountId::from([0; 32]);
+            if admin == zero || treasury == zero {
+                return Err(Error::ZeroAddress);
+            }
+
+            self.admin = admin;
+            self.treasury_account = treasury;
+            self.initialized = true;
+            Ok(())
+        }
+
+        /// Whether admin and treasury are set up (by `new`, `migrate_from_v1` or `initialize`).
+        #[ink(message)]
+        pub fn is_initialized(&self) -> bool {
+            self.initialized
+        }
+
+        /// Gets the caller's own permissions, so a UI can show or hide admin controls.
+        #[ink(message)]
+        pub fn get_my_permissions(&self) -> Permissions {
+            let is_admin = self.env().caller() == self.admin;
+            Permissions {
+                is_admin,
+                is_pauser: is_admin,
+                is_fee_manager: is_admin,
+            }
+        }
+
+        /// Gets the treasury account.
+        ///
+        /// # Returns
+        ///
+        /// The account that receives platform fees.
+        #[ink(message)]
+        pub fn get_treasury(&self) -> AccountId {
+            self.treasury_account
+        }
+
+        /// Gets the total platform fees collected to date.
+        ///
+        /// This is a running counter, independent of the treasury's current balance.
+        #[ink(message)]
+        pub fn get_treasury_earnings(&self) -> Balance {
+            self.total_fees_collected
+        }
+
+        /// Transfers all accrued platform fees to the treasury (admin only).
+        ///
+        /// Fees are held by the contract as donations arrive, so a failing treasury
+        /// transfer can never block a donation or withdrawal. If this transfer fails
+        /// the fees stay accrued. Emits a `FeesCollected` event.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(Balance)`: The amount transferred.
+        /// - `Err(Error)`: `NotCampaignOwner` if the caller is not the admin,
+        ///   `InsufficientFunds` if nothing has accrued, or `TransferFailed`.
+        #[ink(message)]
+        pub fn collect_fees(&mut self) -> Result<Balance, Error> {
+            // Check and acquire lock
+            if self.locked {
+                return Err(Error::ReentrantCall);
+            }
+            self.locked = true;
+
+            let result = self.process_fee_collection();
+
+            // Always unlock before returning
+            self.locked = false;
+            result
+        }
+
+        /// The internal logic for collecting accrued fees.
+        fn process_fee_collection(&mut self) -> Result<Balance, Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner); // Reusing error
+            }
+
+            let amount = self.accrued_fees;
+            if amount == 0 {
+                return Err(Error::InsufficientFunds);
+            }
+
+            self.accrued_fees = 0;
+            if self.env().transfer(self.treasury_account, amount).is_err() {
+                self.accrued_fees = amount;
+                return Err(Error::TransferFailed);
+            }
+
+            self.env().emit_event(FeesCollected {
+                treasury: self.treasury_account,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Gets the platform fees waiting to be collected.
+        #[ink(message)]
+        pub fn get_accrued_fees(&self) -> Balance {
+            self.accrued_fees
+        }
+
+        /// Checks whether an account is the contract administrator.
+        ///
+        /// # Arguments
+        ///
+        /// * `account` - The account to check.
+        ///
+        /// # Returns
+        ///
+        /// True if `account` is the admin.
+        #[ink(message)]
+        pub fn is_admin(&self, account: AccountId) -> bool {
+            account == self.admin
+        }
+
+        /// Sets the subscription manager contract address (admin only).
+        ///
+        /// # Arguments
+        ///
+        /// * `subscription_manager` - The address of the subscription manager contract.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
+        #[ink(message)]
+        pub fn set_subscription_manager(&mut self, subscription_manager: AccountId) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner);
+            }
+            self.subscription_manager = Some(subscription_manager);
+            Ok(())
+        }
+
+        /// Gets the subscription manager contract address.
+        #[ink(message)]
+        pub fn get_subscription_manager(&self) -> Option<AccountId> {
+            self.subscription_manager
+        }
+
+        /// Sets the NFT contract address (admin only).
+        ///
+        /// # Arguments
+        ///
+        /// * `nft_contract` - The address of the NFT contract.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
+        #[ink(message)]
+        pub fn set_nft_contract(&mut self, nft_contract: AccountId) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner);
+            }
+            self.nft_contract = Some(nft_contract);
+            Ok(())
+        }
+
+        /// Gets the NFT contract address.
+        ///
+        /// # Returns
+        ///
+        /// The address of the NFT contract if set.
+        #[ink(message)]
+        pub fn get_nft_contract(&self) -> Option<AccountId> {
+            self.nft_contract
+        }
+
+        /// Enables or disables NFT minting for donations (admin only).
+        ///
+        /// # Arguments
+        ///
+        /// * `enabled` - Whether to enable NFT minting.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error::NotCampaignOwner)` if the caller is not the admin.
+        #[ink(message)]
+        pub fn set_nft_enabled(&mut self, enabled: bool) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner);
+            }
+            self.nft_enabled = enabled;
+            Ok(())
+        }
+
+        /// Gets whether NFT minting is enabled.
+        ///
+        /// # Returns
+        ///
+        /// True if NFT minting is enabled.
+        #[ink(message)]
+        pub fn is_nft_enabled(&self) -> bool {
+            self.nft_enabled
+        }
+
+        // ==================== Quadratic Funding Functions ====================
+
+        /// Fund the matching pool (admin or anyone can contribute).
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        #[ink(message, payable)]
+        pub fn fund_matching_pool(&mut self) -> Result<(), Error> {
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err(Error::InvalidDonationAmount);
+            }
+
+            self.matching_pool_balance = self.matching_pool_balance
+                .checked_add(amount)
+                .ok_or(Error::InvalidDonationAmount)?;
+
+            self.env().emit_event(MatchingPoolFunded {
+                funder: self.env().caller(),
+                amount,
+                total_pool: self.matching_pool_balance,
+            });
+
+            Ok(())
+        }
+
+        /// Create a new matching round (admin only).
+        ///
+        /// # Arguments
+        ///
+        /// * `pool_amount` - Amount from matching pool to allocate to this round.
+        /// * `duration` - How long the round lasts (in milliseconds).
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(u32)`: The round ID.
+        /// - `Err(Error)`: If insufficient pool or not admin.
+        #[ink(message)]
+        pub fn create_matching_round(&mut self, pool_amount: Balance, duration: u64) -> Result<u32, Error> {
+            let now = self.env().block_timestamp();
+            self.create_scheduled_matching_round(pool_amount, now, duration)
+        }
+
+        /// Create a matching round that opens in the future (admin only).
+        ///
+        /// The round is visible and becomes the current round right away, so campaigns
+        /// created in the meantime enroll in it, but only donations made from
+        /// `scheduled_start` on count toward matching.
+        ///
+        /// # Arguments
+        ///
+        /// * `pool_amount` - Amount from matching pool to allocate to this round.
+        /// * `scheduled_start` - When the round opens; not in the past.
+        /// * `duration` - How long the round lasts once open (in milliseconds).
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(u32)`: The round ID.
+        /// - `Err(Error)`: If not admin, insufficient pool, or `InvalidDeadline` if
+        ///   `scheduled_start` is in the past.
+        #[ink(message)]
+        pub fn create_scheduled_matching_round(
+            &mut self,
+            pool_amount: Balance,
+            scheduled_start: Timestamp,
+            duration: u64,
+        ) -> Result<u32, Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner);
+            }
+
+            if pool_amount > self.matching_pool_balance {
+                return Err(Error::InsufficientMatchingPool);
+            }
+
+            if scheduled_start < self.env().block_timestamp() {
+                return Err(Error::InvalidDeadline);
+            }
+
+            let round_id = self.round_count;
+            let end_time = scheduled_start + duration;
+
+            let round = MatchingRound {
+                id: round_id,
+                pool_amount,
+                start_time: scheduled_start,
+                end_time,
+                distributed: false,
+                campaign_ids: Vec::new(),
+                min_unique_donors: 0,
+                emit_estimates: false,
+                funding_exponent: FUNDING_EXPONENT_QUADRATIC,
+                min_qf_donation: 0,
+                donor_breadth_bps: 0,
+            };
+
+            self.matching_rounds.insert(round_id, &round);
+            self.current_round = Some(round_id);
+            self.round_count += 1;
+
+            // Deduct from available pool
+            self.matching_pool_balance = self.matching_pool_balance
+                .checked_sub(pool_amount)
+                .ok_or(Error::InsufficientMatchingPool)?;
+            self.undistributed_round_pools = self.undistributed_round_pools.saturating_add(pool_amount);
+
+            self.env().emit_event(MatchingRoundCreated {
+                round_id,
+                pool_amount,
+                start_time: scheduled_start,
+                end_time,
+            });
+
+            Ok(round_id)
+        }
+
+        /// Set the minimum number of unique donors a campaign needs to receive matching
+        /// in a round (admin only). This keeps single-whale campaigns from farming matching.
+        ///
+        /// # Arguments
+        ///
+        /// * `round_id` - The round to configure.
+        /// * `min_unique_donors` - The threshold (0 disables it).
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error)`: If not admin, round not found or already distributed, or
+        ///   `RoundAlreadyStarted` once the round has opened.
+        #[ink(message)]
+        pub fn set_round_min_unique_donors(&mut self, round_id: u32, min_unique_donors: u32) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner);
+            }
+
+            let mut round = self.matching_rounds.get(round_id).ok_or(Error::NoActiveRound)?;
+
+            if round.distributed {
+                return Err(Error::FundsAlreadyWithdrawn);
+            }
+            if self.env().block_timestamp() >= round.start_time {
+                return Err(Error::RoundAlreadyStarted);
+            }
+
+            round.min_unique_donors = min_unique_donors;
+            self.matching_rounds.insert(round_id, &round);
+
+            Ok(())
+        }
+
+        /// Set the minimum donation that earns QF credit in a round (admin only).
+        ///
+        /// Smaller donations still count toward `raised`, but are left out of the
+        /// score so a flood of dust donations can't inflate the square-root sum.
+        ///
+        /// # Arguments
+        ///
+        /// * `round_id` - The round to configure.
+        /// * `min_qf_donation` - The threshold (0 disables it).
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error)`: If not admin, round not found or already distributed, or
+        ///   `RoundAlreadyStarted` once the round has opened.
+        #[ink(message)]
+        pub fn set_round_min_qf_donation(&mut self, round_id: u32, min_qf_donation: Balance) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner);
+            }
+
+            let mut round = self.matching_rounds.get(round_id).ok_or(Error::NoActiveRound)?;
+
+            if round.distributed {
+                return Err(Error::FundsAlreadyWithdrawn);
+            }
+            if self.env().block_timestamp() >= round.start_time {
+                return Err(Error::RoundAlreadyStarted);
+            }
+
+            round.min_qf_donation = min_qf_donation;
+            self.matching_rounds.insert(round_id, &round);
+
+            Ok(())
+        }
+
+        /// Set the donor-breadth weighting of a round (admin only).
+        ///
+        /// Each campaign's QF score is multiplied by
+        /// `1 + donor_breadth_bps / 10_000 × log2(unique donors)`, so at equal scores a
+        /// campaign backed by many donors is matched ahead of one backed by a few. Only
+        /// donors with a donation that earns QF credit count, so dust accounts can't
+        /// raise the bonus.
+        ///
+        /// # Arguments
+        ///
+        /// * `round_id` - The round to configure.
+        /// * `donor_breadth_bps` - Bonus per doubling of unique donors (0 disables it).
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error)`: If not admin, round not found or already distributed, the
+        ///   bonus exceeds `MAX_DONOR_BREADTH_BPS`, or `RoundAlreadyStarted` once the
+        ///   round has opened.
+        #[ink(message)]
+        pub fn set_round_donor_breadth(&mut self, round_id: u32, donor_breadth_bps: u32) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner);
+            }
+
+            if donor_breadth_bps > MAX_DONOR_BREADTH_BPS {
+                return Err(Error::InvalidDonorBreadth);
+            }
+
+            let mut round = self.matching_rounds.get(round_id).ok_or(Error::NoActiveRound)?;
+
+            if round.distributed {
+                return Err(Error::FundsAlreadyWithdrawn);
+            }
+            if self.env().block_timestamp() >= round.start_time {
+                return Err(Error::RoundAlreadyStarted);
+            }
+
+            round.donor_breadth_bps = donor_breadth_bps;
+            self.matching_rounds.insert(round_id, &round);
+
+            Ok(())
+        }
+
+        /// Set the funding exponent of a round (admin only).
+        ///
+        /// Each campaign's score is `(Σ donationᵢ^(1/p))^p` for exponent `p`, so 1.0
+        /// matches in proportion to the amount raised and 2.0 is quadratic funding.
+        /// Values in between trade off donor count against amount.
+        ///
+        /// # Arguments
+        ///
+        /// * `round_id` - The round to configure.
+        /// * `funding_exponent` - The exponent in thousandths, from 1000 (linear) to 2000 (quadratic).
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error)`: If not admin, round not found or already distributed,
+        ///   `InvalidFundingExponent` if out of range, or `RoundAlreadyStarted` once the
+        ///   round has opened.
+        #[ink(message)]
+        pub fn set_round_funding_exponent(&mut self, round_id: u32, funding_exponent: u32) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner);
+            }
+
+            if !(FUNDING_EXPONENT_LINEAR..=FUNDING_EXPONENT_QUADRATIC).contains(&funding_exponent) {
+                return Err(Error::InvalidFundingExponent);
+            }
+
+            let mut round = self.matching_rounds.get(round_id).ok_or(Error::NoActiveRound)?;
+
+            if round.distributed {
+                return Err(Error::FundsAlreadyWithdrawn);
+            }
+            if self.env().block_timestamp() >= round.start_time {
+                return Err(Error::RoundAlreadyStarted);
+            }
+
+            round.funding_exponent = funding_exponent;
+            self.matching_rounds.insert(round_id, &round);
+
+            Ok(())
+        }
+
+        /// Enable or disable `EstimatedMatchingUpdated` events for a round (admin only).
+        ///
+        /// Estimates are recomputed on every donation to an enrolled campaign, which
+        /// costs extra gas, so rounds opt in explicitly.
+        ///
+        /// # Arguments
+        ///
+        /// * `round_id` - The round to configure.
+        /// * `enabled` - Whether donations should emit estimate events.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error)`: If not admin or round not found.
+        #[ink(message)]
+        pub fn set_round_emit_estimates(&mut self, round_id: u32, enabled: bool) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner);
+            }
+
+            let mut round = self.matching_rounds.get(round_id).ok_or(Error::NoActiveRound)?;
+            round.emit_estimates = enabled;
+            self.matching_rounds.insert(round_id, &round);
+
+            Ok(())
+        }
+
+        /// Emit the campaign's updated matching estimate if its round opted in.
+        fn emit_estimated_matching(&self, campaign_id: u32, matching_round: Option<u32>) {
+            let Some(round_id) = matching_round else {
+                return;
+            };
+            let Some(round) = self.matching_rounds.get(round_id) else {
+                return;
+            };
+
+            // Bound the cost: the estimate scans every campaign in the round
+            if !round.emit_estimates || round.distributed || round.campaign_ids.len() > MAX_ESTIMATE_EVENT_CAMPAIGNS {
+                return;
+            }
+
+            self.env().emit_event(EstimatedMatchingUpdated {
+                campaign_id,
+                estimate: self.get_estimated_matching(campaign_id),
+                round_id,
+            });
+        }
+
+        /// Calculate quadratic funding matching for all campaigns in a round.
+        /// This uses the formula: matching ∝ (sum of √donation_amounts)²
+        ///
+        /// # Arguments
+        ///
+        /// * `round_id` - The round to calculate matching for.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error)`: If round not found or already distributed.
+        #[ink(message)]
+        pub fn calculate_and_distribute_matching(&mut self, round_id: u32) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotCampaignOwner);
+            }
+
+            let mut round = self.matching_rounds.get(round_id).ok_or(Error::CampaignNotFound)?;
+            
+            if round.distributed {
+                return Err(Error::FundsAlreadyWithdrawn);
+            }
+
+            let current_time = self.env().block_timestamp();
+            if current_time < round.end_time {
+                return Err(Error::DeadlinePassed); // Reusing error - means "round not ended yet"
+            }
+
+            // Distribute matching proportionally based on QF scores
+            let shares = self.round_matching_shares(round_id, &round);
+            if shares.is_empty() {
+                // Nobody qualified, so the pool isn't spent
+                self.matching_pool_balance = self.matching_pool_balance.saturating_add(round.pool_amount);
+            }
+
+            for (campaign_id, _, matching_share) in shares {
+                if let Some(mut campaign) = self.campaigns.get(campaign_id) {
+                    campaign.matching_amount = matching_share;
+                    self.save_campaign(campaign_id, &campaign);
+
+                    self.env().emit_event(MatchingDistributed {
+                        campaign_id,
+                        matching_amount: matching_share,
+                        round_id,
+                    });
+                }
+            }
+
+            // Mark round as distributed
+            round.distributed = true;
+            self.matching_rounds.insert(round_id, &round);
+            self.undistributed_round_pools = self.undistributed_round_pools.saturating_sub(round.pool_amount);
+
+            // Close the current round
+            if self.current_round == Some(round_id) {
+                self.current_round = None;
+            }
+
+            Ok(())
+        }
+
+        /// QF scores of the campaigns eligible for matching in a round, and their total.
+        ///
+        /// Failed campaigns, campaigns below the round's donor threshold and campaigns
+        /// with a zero score are left out.
+        fn round_qf_scores(&self, round_id: u32, round: &MatchingRound) -> (Vec<(u32, u128)>, u128) {
+            let mut total_qf_score: u128 = 0;
+            let mut campaign_scores: Vec<(u32, u128)> = Vec::new();
+
+            // Only the campaigns enrolled in this round need to be scanned
+            for &campaign_id in &round.campaign_ids {
+                if let Some(campaign) = self.campaigns.get(campaign_id) {
+                    if campaign.matching_round == Some(round_id)
+                        && campaign.state != CampaignState::Failed
+                        && campaign.unique_donor_count >= round.min_unique_donors
+                    {
+                        let qf_score = self.calculate_qf_score(campaign_id);
+                        if qf_score > 0 {
+                            campaign_scores.push((campaign_id, qf_score));
+                            total_qf_score = total_qf_score.saturating_add(qf_score);
+                        }
+                    }
+                }
+            }
+
+            (campaign_scores, total_qf_score)
+        }
+
+        /// Matching shares of a round as `(campaign_id, qf_score, share)`.
+        ///
+        /// Shares are rounded down and the remainder goes to the highest-scoring
+        /// campaign (lowest ID on ties), so they always add up to the round's pool.
+        /// Empty if no campaign is eligible.
+        fn round_matching_shares(&self, round_id: u32, round: &MatchingRound) -> Vec<(u32, u128, Balance)> {
+            let (campaign_scores, total_qf_score) = self.round_qf_scores(round_id, round);
+            if total_qf_score == 0 {
+                return Vec::new();
+            }
+
+            let mut shares: Vec<(u32, u128, Balance)> = campaign_scores
+                .into_iter()
+                .map(|(campaign_id, qf_score)| {
+                    (campaign_id, qf_score, qf_score.saturating_mul(round.pool_amount) / total_qf_score)
+                })
+                .collect();
+
+            let distributed: Balance = shares.iter().map(|(_, _, share)| share).sum();
+            let remainder = round.pool_amount.saturating_sub(distributed);
+            if let Some(top) = shares.iter_mut().max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0))) {
+                top.2 = top.2.saturating_add(remainder);
+            }
+
+            shares
+        }
+
+        /// Integer square root (rounded down) using the Babylonian method.
+        /// Required for quadratic funding calculations.
+        ///
+        /// Safe for every `u128`: the first estimate is `ceil(n / 2)`, so `x + n / x`
+        /// is at most `ceil(n / 2) + 2` there, and estimates only shrink afterwards.
+        fn sqrt(n: u128) -> u128 {
+            if n == 0 {
+                return 0;
+            }
+            
+            let mut x = n;
+            let mut y = n.div_ceil(2);
+            
+            while y < x {
+                x = y;
+                y = (x + n / x) / 2;
+            }
+            
+            x
+        }
+
+        /// Fixed-point log2 of `x` (must be non-zero), with 32 fractional bits.
+        fn log2_fixed(x: u128) -> u128 {
+            let integer_part = 127 - x.leading_zeros();
+
+            // Normalize x into [1, 2) as Q62
+            let mut mantissa = if integer_part <= 62 {
+                x << (62 - integer_part)
+            } else {
+                x >> (integer_part - 62)
+            };
+
+            // Each squaring of the mantissa yields one more fractional bit
+            let mut fraction: u128 = 0;
+            for bit in (0..32).rev() {
+                mantissa = (mantissa * mantissa) >> 62;
+                if mantissa >= 1 << 63 {
+                    mantissa >>= 1;
+                    fraction |= 1 << bit;
+                }
+            }
+
+            ((integer_part as u128) << 32) | fraction
+        }
+
+        /// Fixed-point 2^y for `y` with 32 fractional bits, rounded down and saturating at
+        /// `u128::MAX`.
+        fn exp2_fixed(y: u128) -> u128 {
+            let integer_part = y >> 32;
+            if integer_part >= 128 {
+                return u128::MAX;
+            }
+
+            // 2^fraction as Q62, one table factor per set fractional bit
+            let mut result: u128 = 1 << 62;
+            for (i, factor) in EXP2_FRACTION_TABLE.iter().enumerate() {
+                if y & (1 << (31 - i)) != 0 {
+                    result = (result * factor) >> 62;
+                }
+            }
+
+            if integer_part <= 62 {
+                return result >> (62 - integer_part);
+            }
+
+            // Saturate rather than shift set bits out of the top
+            let shift = (integer_part - 62) as u32;
+            if result.leading_zeros() < shift {
+                return u128::MAX;
+            }
+            result << shift
+        }
+
+        /// `x^(numerator / denominator)` in fixed-point integer math, rounded down.
+        fn pow_fixed(x: u128, numerator: u32, denominator: u32) -> u128 {
+            if x == 0 {
+                return 0;
+            }
+            Self::exp2_fixed(Self::log2_fixed(x) * numerator as u128 / denominator as u128)
+        }
+
+        /// The QF settings of a campaign's round: (funding exponent, min QF donation,
+        /// start time, donor breadth bps). Campaigns outside a round use plain QF.
+        fn qf_round_params(&self, matching_round: Option<u32>) -> (u32, Balance, Timestamp, u32) {
+            matching_round
+                .and_then(|round_id| self.matching_rounds.get(round_id))
+                .map(|round| (round.funding_exponent, round.min_qf_donation, round.start_time, round.donor_breadth_bps))
+                .unwrap_or((FUNDING_EXPONENT_QUADRATIC, 0, 0, 0))
+        }
+
+        /// A donation's contribution to the QF sum of roots, or 0 if it doesn't count.
+        fn qf_root(donation: &Donation, exponent: u32, min_qf_donation: Balance, start_time: Timestamp) -> u128 {
+            // Owner top-ups, dust and donations before the round opened don't attract matching
+            if donation.owner_contribution
+                || donation.amount < min_qf_donation
+                || donation.timestamp < start_time
+            {
+                return 0;
+            }
+            match exponent {
+                FUNDING_EXPONENT_LINEAR => donation.amount,
+                FUNDING_EXPONENT_QUADRATIC => Self::sqrt(donation.amount),
+                _ => Self::pow_fixed(donation.amount, FUNDING_EXPONENT_LINEAR, exponent),
+            }
+        }
+
+        /// Calculate the matching score for a campaign under its round's funding exponent.
+        /// Formula: (donation₁^(1/p) + donation₂^(1/p) + ... + donationₙ^(1/p))^p
+        ///
+        /// With the default p = 2 this is quadratic funding, which rewards campaigns with
+        /// many small donors over few large donors; p = 1 is the plain amount raised.
+        /// Both ends are computed exactly, exponents in between use fixed-point math.
+        /// Donations below the round's `min_qf_donation` or made before it opened are ignored.
+        fn calculate_qf_score(&self, campaign_id: u32) -> u128 {
+            let (donation_count, matching_round) = match self.campaigns.get(campaign_id) {
+                Some(c) => (c.donation_count, c.matching_round),
+                None => return 0,
+            };
+
+            let (exponent, min_qf_donation, start_time, donor_breadth_bps) = self.qf_round_params(matching_round);
+
+            // Archived donations were folded into these when they left detailed storage
+            let mut sum_of_roots: u128 = self.archived_qf_roots.get(campaign_id).unwrap_or(0);
+            let archived_donors = self.archived_qf_donor_count.get(campaign_id).unwrap_or(0);
+            // Only donors with a donation that counts for QF widen the breadth bonus
+            let mut qualifying_donors: BTreeSet<AccountId> = BTreeSet::new();
+
+            for index in 0..donation_count {
+                if let Some(donation) = self.campaign_donations.get((campaign_id, index)) {
+                    let root = Self::qf_root(&donation, exponent, min_qf_donation, start_time);
+                    if root == 0 {
+                        continue;
+                    }
+                    sum_of_roots = sum_of_roots.saturating_add(root);
+                    if donor_breadth_bps > 0
+                        && !qualifying_donors.contains(&donation.donor)
+                        && !self.archived_qf_donors.contains((campaign_id, donation.donor))
+                    {
+                        qualifying_donors.insert(donation.donor);
+                    }
+                }
+            }
+            let unique_donors = archived_donors.saturating_add(qualifying_donors.len() as u32);
+
+            // Raise the sum back to the exponent: (√a + √b + √c)² for quadratic funding
+            let score = match exponent {
+                FUNDING_EXPONENT_LINEAR => sum_of_roots,
+                FUNDING_EXPONENT_QUADRATIC => sum_of_roots.saturating_mul(sum_of_roots),
+                _ => Self::pow_fixed(sum_of_roots, exponent, FUNDING_EXPONENT_LINEAR),
+            };
+
+            // Reward breadth: a bonus for every doubling of unique donors
+            if donor_breadth_bps == 0 || unique_donors < 2 {
+                return score;
+            }
+            let bonus_bps = (donor_breadth_bps as u128).saturating_mul(unique_donors.ilog2() as u128);
+            score.saturating_mul(10_000u128.saturating_add(bonus_bps)) / 10_000
+        }
+
+        /// Get estimated matching for a campaign (read-only, for UI display).
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The campaign to estimate matching for.
+        ///
+        /// # Returns
+        ///
+        /// Estimated matching amount based on current donations and round pool.
+        #[ink(message)]
+        pub fn get_estimated_matching(&self, campaign_id: u32) -> Balance {
+            let campaign = match self.campaigns.get(campaign_id) {
+                Some(c) => c,
+                None => return 0,
+            };
+
+            let round_id = match campaign.matching_round {
+                Some(r) => r,
+                None => return 0,
+            };
+
+            let round = match self.matching_rounds.get(round_id) {
+                Some(r) => r,
+                None => return 0,
+            };
+
+            if round.distributed {
+                return campaign.matching_amount;
+            }
+
+            // Campaigns below the round's donor threshold won't be matched
+            if campaign.unique_donor_count < round.min_unique_donors {
+                return 0;
+            }
+
+            // Calculate this campaign's QF score
+            let campaign_score = self.calculate_qf_score(campaign_id);
+            if campaign_score == 0 {
+                return 0;
+            }
+
+            // Calculate total QF score for all campaigns in round
+            let mut total_score: u128 = 0;
+            for &id in &round.campaign_ids {
+                if let Some(c) = self.campaigns.get(id) {
+                    if c.unique_donor_count >= round.min_unique_donors {
+                        total_score = total_score.saturating_add(self.calculate_qf_score(id));
+                    }
+                }
+            }
+
+            if total_score == 0 {
+                return 0;
+            }
+
+            // Estimate share
+            ((campaign_score as u128) * (round.pool_amount as u128) / total_score) as Balance
+        }
+
+        /// Get the matching a campaign receives (or would receive) from a round.
+        ///
+        /// After distribution this is the stored `matching_amount`. Before it, the
+        /// distribution rules are applied to current data, including the exclusions
+        /// `get_estimated_matching` ignores, so UIs show what would actually be paid.
+        ///
+        /// # Returns
+        ///
+        /// `None` if the round or campaign doesn't exist or the campaign isn't in the round.
+        #[ink(message)]
+        pub fn get_final_matching(&self, round_id: u32, campaign_id: u32) -> Option<Balance> {
+            let campaign = self.campaigns.get(campaign_id)?;
+            if campaign.matching_round != Some(round_id) {
+                return None;
+            }
+            let round = self.matching_rounds.get(round_id)?;
+
+            if round.distributed {
+                return Some(campaign.matching_amount);
+            }
+
+            let share = self.round_matching_shares(round_id, &round)
+                .into_iter()
+                .find(|(id, _, _)| *id == campaign_id)
+                .map(|(_, _, share)| share)
+                .unwrap_or(0);
+            Some(share)
+        }
+
+        /// Get a round's campaigns ranked by QF score, highest first.
+        ///
+        /// # Arguments
+        ///
+        /// * `round_id` - The round to rank.
+        /// * `limit` - Maximum number of entries to return (capped at `MAX_QUERY_LIMIT`).
+        ///
+        /// # Returns
+        ///
+        /// `(campaign_id, qf_score, estimated_match)` tuples. Only campaigns eligible for
+        /// matching are included; the match is the one `get_final_matching` reports.
+        #[ink(message)]
+        pub fn get_round_leaderboard(&self, round_id: u32, limit: u32) -> Vec<(u32, u128, Balance)> {
+            let Some(round) = self.matching_rounds.get(round_id) else {
+                return Vec::new();
+            };
+
+            let mut shares = self.round_matching_shares(round_id, &round);
+            shares.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            shares.truncate(limit.min(MAX_QUERY_LIMIT) as usize);
+
+            if round.distributed {
+                for (campaign_id, _, estimated_match) in shares.iter_mut() {
+                    *estimated_match = self.campaigns.get(*campaign_id).map(|c| c.matching_amount).unwrap_or(0);
+                }
+            }
+
+            shares
+        }
+
+        /// Get matching pool balance.
+        #[ink(message)]
+        pub fn get_matching_pool_balance(&self) -> Balance {
+            self.matching_pool_balance
+        }
+
+        /// Get current active round ID.
+        #[ink(message)]
+        pub fn get_current_round(&self) -> Option<u32> {
+            self.current_round
+        }
+
+        /// Get round details.
+        #[ink(message)]
+        pub fn get_round(&self, round_id: u32) -> Option<MatchingRound> {
+            self.matching_rounds.get(round_id)
+        }
+
+        /// Get the phase a round is in at the current block timestamp.
+        #[ink(message)]
+        pub fn get_round_phase(&self, round_id: u32) -> Option<RoundPhase> {
+            let round = self.matching_rounds.get(round_id)?;
+            let now = self.env().block_timestamp();
+
+            Some(if round.distributed {
+                RoundPhase::Distributed
+            } else if now < round.start_time {
+                RoundPhase::Scheduled
+            } else if now < round.end_time {
+                RoundPhase::Active
+            } else {
+                RoundPhase::Ended
+            })
+        }
+
+        /// Get the total amount a donor has contributed to a campaign.
+        #[ink(message)]
+        pub fn get_donor_total(&self, campaign_id: u32, donor: AccountId) -> Balance {
+            self.donor_totals.get((self.resolve_campaign_id(campaign_id), donor)).unwrap_or(0)
+        }
+
+        /// Reconciles a campaign's stored `raised` against its donation records, one page
+        /// at a time.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The campaign to audit.
+        /// * `offset` - The first donation index of the page.
+        /// * `limit` - The page size (capped at `MAX_QUERY_LIMIT`).
+        ///
+        /// # Returns
+        ///
+        /// `(stored_raised, summed_donations_in_range, matches)`. `matches` is only `true`
+        /// when the page covers every donation and the sum equals `stored_raised`; for
+        /// longer campaigns auditors add up the pages themselves. The page starting at
+        /// 0 includes the archived donations' total. Unknown campaigns return `(0, 0, false)`.
+        #[ink(message)]
+        pub fn audit_campaign(&self, campaign_id: u32, offset: u32, limit: u32) -> (Balance, Balance, bool) {
+            let Some(campaign) = self.campaigns.get(campaign_id) else {
+                return (0, 0, false);
+            };
+
+            let end = offset
+                .saturating_add(limit.min(MAX_QUERY_LIMIT))
+                .min(campaign.donation_count);
+            let mut summed: Balance = (offset..end)
+                .filter_map(|index| self.campaign_donations.get((campaign_id, index)))
+                .map(|donation| donation.amount)
+                .sum();
+            // The first page stands in for the archived donations with their total
+            if offset == 0 {
+                summed = summed.saturating_add(campaign.archived_donation_total);
+            }
+
+            let covers_all = offset == 0 && end == campaign.donation_count;
+            (campaign.raised, summed, covers_all && summed == campaign.raised)
+        }
+
+        /// Get a structured receipt for a single donation.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The campaign that received the donation.
+        /// * `donor` - The donor; must match the recorded donation.
+        /// * `index` - The donation's index within the campaign (as returned by `donate`).
+        ///
+        /// # Returns
+        ///
+        /// `None` if the campaign or donation doesn't exist or was made by someone else.
+        #[ink(message)]
+        pub fn get_donation_receipt(&self, campaign_id: u32, donor: AccountId, index: u32) -> Option<TaxReceipt> {
+            let campaign = self.campaigns.get(campaign_id)?;
+            let donation = self.campaign_donations.get((campaign_id, index))?;
+            if donation.donor != donor {
+                return None;
+            }
+
+            Some(TaxReceipt {
+                campaign_id,
+                campaign_title: campaign.title,
+                donor,
+                donation_index: index,
+                amount: donation.amount,
+                net_amount: donation.amount.saturating_sub(donation.fee),
+                timestamp: donation.timestamp,
+            })
+        }
+
+        /// Get the total amount a donor has donated across all campaigns.
+        ///
+        /// Owner top-ups are not included, and refunded donations are taken back out, so
+        /// donating to a failing campaign and reclaiming it doesn't earn fee discounts.
+        #[ink(message)]
+        pub fn get_donor_total_contributions(&self, donor: AccountId) -> Balance {
+            self.donor_total_contributions.get(donor).unwrap_or(0)
+        }
+
+        /// Get count of unique donors for a campaign.
+        #[ink(message)]
+        pub fn get_unique_donor_count(&self, campaign_id: u32) -> u32 {
+            self.campaigns
+                .get(campaign_id)
+                .map(|c| c.unique_donor_count)
+                .unwrap_or(0)
+        }
+
+        // ==================== DAO Milestone Voting Functions ====================
+
+        /// Add milestones to a campaign (owner only, before campaign is successful).
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The campaign to add milestones to.
+        /// * `milestones_data` - Vec of (description, percentage, days_from_now).
+        ///
+        /// Milestone deadlines must fall after the campaign deadline and be strictly
+        /// increasing, at least `MIN_MILESTONE_GAP` apart.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error)`: If not owner, campaign already successful, goal below
+        ///   `min_goal_for_milestones` (`InvalidGoal`), or deadlines are out of order
+        ///   (`InvalidDeadline`).
+        #[ink(message)]
+        pub fn add_milestones(
+            &mut self,
+            campaign_id: u32,
+            milestones_data: Vec<(String, u32, u64)>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let current_time = self.env().block_timestamp();
+
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+
+            // Only owner can add milestones
+            if caller != campaign.owner {
+                return Err(Error::NotCampaignOwner);
+            }
+
+            // Can't add milestones to completed campaigns
+            if campaign.state != CampaignState::Active {
+                return Err(Error::CampaignNotActive);
+            }
+
+            // DAO governance isn't worth it for dust campaigns
+            if campaign.goal < self.min_goal_for_milestones {
+                return Err(Error::InvalidGoal);
+            }
+
+            if milestones_data.len() > MAX_MILESTONES {
+                return Err(Error::InvalidGoal); // Reusing error - means too many milestones
+            }
+
+            // Validate percentages sum to 100 (10000 basis points)
+            let total_percentage: u32 = milestones_data.iter().map(|(_, p, _)| p).sum();
+            if total_percentage != 10000 {
+                return Err(Error::InvalidGoal); // Reusing error - means invalid percentage
+            }
+
+            // Create milestones
+            let mut milestones = Vec::new();
+            let mut previous_deadline: Option<Timestamp> = None;
+            for (description, percentage, days) in milestones_data {
+                if description.is_empty() || description.len() > 200 {
+                    return Err(Error::InvalidDescription);
+                }
+
+                // Each release must pay out something at the current goal
+                let milestone_amount = campaign.goal
+                    .checked_mul(Balance::from(percentage))
+                    .ok_or(Error::InvalidGoal)?
+                    / 10000;
+                if milestone_amount == 0 {
+                    return Err(Error::InvalidGoal); // Reusing error - means split too granular
+                }
+                
+                let milestone_deadline = days
+                    .checked_mul(24 * 60 * 60 * 1000)
+                    .and_then(|offset| current_time.checked_add(offset))
+                    .ok_or(Error::InvalidDeadline)?;
+
+                // Milestones can only be worked on once the campaign is funded
+                if milestone_deadline <= campaign.deadline {
+                    return Err(Error::InvalidDeadline);
+                }
+                if let Some(previous) = previous_deadline {
+                    if milestone_deadline < previous.saturating_add(MIN_MILESTONE_GAP) {
+                        return Err(Error::InvalidDeadline);
+                    }
+                }
+                previous_deadline = Some(milestone_deadline);
+                
+                milestones.push(Milestone {
+                    description,
+                    percentage,
+                    deadline: milestone_deadline,
+                    votes_for: 0,
+                    votes_against: 0,
+                    released: false,
+                    voting_active: false,
+                    activated_at: 0,
+                });
+            }
+
+            campaign.milestones = milestones;
+            campaign.uses_milestones = true;
+            self.save_campaign(campaign_id, &campaign);
+
+            self.env().emit_event(MilestonesAdded {
+                campaign_id,
+                milestone_count: u32::try_from(campaign.milestones.len()).unwrap_or(0),
+            });
+
+            Ok(())
+        }
+
+        /// Opt a campaign in or out of automatic first-milestone voting (owner only).
+        ///
+        /// When enabled, voting on milestone 0 opens as soon as the campaign reaches its
+        /// goal, so the owner doesn't have to call `activate_milestone_voting` for it.
+        /// Later milestones still activate manually once the previous one is released.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The campaign to configure.
+        /// * `enabled` - Whether milestone 0 should activate on success.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        /// - `Err(Error)`: If not owner or campaign no longer active.
+        #[ink(message)]
+        pub fn set_auto_activate_first_milestone(
+            &mut self,
+            campaign_id: u32,
+            enabled: bool,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+
+            if caller != campaign.owner {
+                return Err(Error::NotCampaignOwner);
+            }
+
+            if campaign.state != CampaignState::Active {
+                return Err(Error::CampaignNotActive);
+            }
+
+            campaign.auto_activate_first_milestone = enabled;
+            self.save_campaign(campaign_id, &campaign);
+
+            Ok(())
+        }
+
+        /// Moves a campaign to `state`, keeping `active_count` in sync.
+        ///
+        /// The caller is still responsible for storing the campaign.
+        fn set_state(&mut self, campaign: &mut Campaign, state: CampaignState) {
+            if campaign.state == CampaignState::Active && state != CampaignState::Active {
+                self.active_count = self.active_count.saturating_sub(1);
+            } else if campaign.state != CampaignState::Active && state == CampaignState::Active {
+                self.active_count = self.active_count.saturating_add(1);
+            }
+            if state == CampaignState::Failed && campaign.failed_at.is_none() {
+                campaign.failed_at = Some(self.env().block_timestamp());
+            }
+            campaign.state = state;
+        }
+
+        /// Open voting on milestone 0 if the campaign opted in and it is still open.
+        ///
+        /// Returns whether voting was activated, so the caller can emit the event
+        /// after the campaign has been stored.
+        fn auto_activate_first_milestone(campaign: &mut Campaign, current_time: Timestamp) -> bool {
+            if !campaign.auto_activate_first_milestone || !campaign.uses_milestones {
+                return false;
+            }
+
+            match campaign.milestones.first_mut() {
+                Some(first) if !first.released && current_time <= first.deadline => {
+                    first.voting_active = true;
+                    first.activated_at = current_time;
+                    true
+                }
+                _ => false,
+            }
+        }
+
+        /// Activate voting for a milestone (owner requests release).
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The campaign.
+        /// * `milestone_index` - Which milestone to activate voting for.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        #[ink(message)]
+        pub fn activate_milestone_voting(
+            &mut self,
+            campaign_id: u32,
+            milestone_index: u32,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let current_time = self.env().block_timestamp();
+
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+
+            // Only owner can activate voting
+            if caller != campaign.owner {
+                return Err(Error::NotCampaignOwner);
+            }
+
+            // Campaign must be successful
+            if campaign.state != CampaignState::Successful && campaign.state != CampaignState::Withdrawn {
+                return Err(Error::GoalNotReached);
+            }
+
+            let idx = milestone_index as usize;
+            if idx >= campaign.milestones.len() {
+                return Err(Error::CampaignNotFound); // Reusing - means milestone not found
+            }
+
+            // Check if previous milestones are released (must be sequential)
+            if idx > 0 && !campaign.milestones[idx - 1].released {
+                return Err(Error::GoalNotReached); // Reusing - means previous milestone not done
+            }
+
+            // Check deadline hasn't passed
+            if current_time > campaign.milestones[idx].deadline {
+                return Err(Error::DeadlinePassed);
+            }
+
+            campaign.milestones[idx].voting_active = true;
+            campaign.milestones[idx].activated_at = current_time;
+            self.save_campaign(campaign_id, &campaign);
+
+            self.env().emit_event(MilestoneVotingActivated {
+                campaign_id,
+                milestone_index,
+            });
+
+            Ok(())
+        }
+
+        /// Vote on a milestone (donors only, weighted by donation amount).
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The campaign.
+        /// * `milestone_index` - Which milestone to vote on.
+        /// * `approve` - true to approve, false to reject.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        #[ink(message)]
+        pub fn vote_on_milestone(
+            &mut self,
+            campaign_id: u32,
+            milestone_index: u32,
+            approve: bool,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let current_time = self.env().block_timestamp();
+
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+
+            let idx = milestone_index as usize;
+            if idx >= campaign.milestones.len() {
+                return Err(Error::CampaignNotFound);
+            }
+
+            // Voting must be active
+            if !campaign.milestones[idx].voting_active {
+                return Err(Error::CampaignNotActive);
+            }
+
+            // Already released
+            if campaign.milestones[idx].released {
+                return Err(Error::FundsAlreadyWithdrawn);
+            }
+
+            // Voter's weight is what they had donated when voting opened, so late
+            // donations can't swing an ongoing vote
+            let voter_weight = self.vote_weight_at(
+                campaign_id,
+                milestone_index,
+                caller,
+                campaign.milestones[idx].activated_at,
+            );
+
+            if voter_weight == 0 {
+                return Err(Error::NoDonationFound);
+            }
+
+            // Check if already voted
+            let vote_key = (campaign_id, milestone_index, caller);
+            if self.milestone_votes.get(vote_key).is_some() {
+                return Err(Error::RefundAlreadyClaimed); // Reusing - means already voted
+            }
+
+            // Record vote
+            self.milestone_votes.insert(vote_key, &(voter_weight, approve));
+            let vote_index = self.milestone_voter_count.get((campaign_id, milestone_index)).unwrap_or(0);
+            self.milestone_voters.insert((campaign_id, milestone_index, vote_index), &(caller, current_time));
+            self.milestone_voter_count.insert((campaign_id, milestone_index), &vote_index.saturating_add(1));
+
+            // Update vote counts
+            if approve {
+                campaign.milestones[idx].votes_for = campaign.milestones[idx]
+                    .votes_for
+                    .saturating_add(voter_weight);
+            } else {
+                campaign.milestones[idx].votes_against = campaign.milestones[idx]
+                    .votes_against
+                    .saturating_add(voter_weight);
+            }
+
+            self.save_campaign(campaign_id, &campaign);
+
+            self.env().emit_event(MilestoneVoted {
+                campaign_id,
+                milestone_index,
+                voter: caller,
+                approve,
+                weight: voter_weight,
+                timestamp: current_time,
+            });
+
+            Ok(())
+        }
+
+        /// Sum of a donor's donations to a campaign made at or before the milestone's
+        /// voting opened at `activated_at`: their total minus what `record_late_donation`
+        /// recorded since.
+        fn vote_weight_at(&self, campaign_id: u32, milestone_index: u32, voter: AccountId, activated_at: Timestamp) -> Balance {
+            let late = match self.late_donations.get((campaign_id, milestone_index, voter)) {
+                Some((at, amount)) if at == activated_at => amount,
+                _ => 0,
+            };
+            self.donor_totals.get((campaign_id, voter)).unwrap_or(0).saturating_sub(late)
+        }
+
+        /// Record a donation made while a milestone vote is open, so it doesn't add to
+        /// the donor's weight in that vote.
+        fn record_late_donation(
+            &mut self,
+            campaign_id: u32,
+            campaign: &Campaign,
+            donor: AccountId,
+            amount: Balance,
+            current_time: Timestamp,
+        ) {
+            let Some(milestone_index) = campaign.milestones.iter()
+                .position(|milestone| milestone.voting_active && !milestone.released)
+            else {
+                return;
+            };
+            let activated_at = campaign.milestones[milestone_index].activated_at;
+            if current_time <= activated_at {
+                return;
+            }
+
+            let key = (campaign_id, milestone_index as u32, donor);
+            let late = match self.late_donations.get(key) {
+                Some((at, late)) if at == activated_at => late,
+                _ => 0,
+            };
+            self.late_donations.insert(key, &(activated_at, late.saturating_add(amount)));
+        }
+
+        /// Release milestone funds if voting passes (owner or admin).
+        ///
+        /// Requires >66% approval (weighted by donation amount).
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The campaign.
+        /// * `milestone_index` - Which milestone to release.
+        ///
+        /// # Returns
+        ///
+        /// - `Ok(())` on success.
+        #[ink(message)]
+        pub fn release_milestone_funds(
+            &mut self,
+            campaign_id: u32,
+            milestone_index: u32,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let mut campaign = self.campaigns.get(campaign_id).ok_or(Error::CampaignNotFound)?;
+
+            // Only owner or admin can trigger release
+            if caller != campaign.owner && caller != self.admin {
+                return Err(Error::NotCampaignOwner);
+            }
+
+            let idx = milestone_index as usize;
+            if idx >= campaign.milestones.len() {
+                return Err(Error::CampaignNotFound);
+            }
+
+            // Already released
+            if campaign.milestones[idx].released {
+                return Err(Error::FundsAlreadyWithdrawn);
+            }
+
+            // Funds of a force-refundable campaign belong to its donors
+            if campaign.refundable {
+                return Err(Error::CampaignFailed);
+            }
+
+            // Voting must be active
+            if !campaign.milestones[idx].voting_active {
+                return Err(Error::CampaignNotActive);
+            }
+
+            // Give donors time to vote before an early majority can release funds
+            let current_time = self.env().block_timestamp();
+            let voting_ends = campaign.milestones[idx].activated_at.saturating_add(self.min_voting_duration);
+            if current_time < voting_ends {
+                return Err(Error::VotingStillOpen);
+            }
+
+            Self::check_withdrawal_cooldown(&campaign, current_time)?;
+
+            // Check approval threshold (66%)
+            let total_votes = campaign.milestones[idx].votes_for + campaign.milestones[idx].votes_against;
+            if total_votes == 0 {
+                return Err(Error::InsufficientFunds); // Reusing - means no votes yet
+            }
+
+            let approval_percentage = (campaign.milestones[idx].votes_for as u128 * 100) / (total_votes as u128);
+            if approval_percentage < 66 {
+                return Err(Error::GoalNotReached); // Reusing - means not enough approval
+            }
+
+            // Calculate amount to release (percentage of total raised + matching)
+            let total_campaign_funds = campaign.raised.saturating_add(campaign.matching_amount);
+            let milestone_amount = ((total_campaign_funds as u128) * (campaign.milestones[idx].percentage as u128) / 10000) as Balance;
+
+            // Transfer funds to beneficiary
+            if milestone_amount > 0 {
+                if self.env().transfer(campaign.beneficiary, milestone_amount).is_err() {
+                    return Err(Error::WithdrawalFailed);
+                }
+            }
+
+            // Mark as released
+            campaign.milestones[idx].released = true;
+            campaign.milestones[idx].voting_active = false;
+            campaign.last_withdrawal_at = Some(current_time);
+
+            // If all milestones released, mark campaign as withdrawn
+            let all_released = campaign.milestones.iter().all(|m| m.released);
+            if all_released {
+                self.set_state(&mut campaign, CampaignState::Withdrawn);
+            }
+
+            self.save_campaign(campaign_id, &campaign);
+
+            self.env().emit_event(MilestoneFundsReleased {
+                campaign_id,
+                milestone_index,
+                amount: milestone_amount,
+                beneficiary: campaign.beneficiary,
+            });
+
+            Ok(())
+        }
+
+        /// Get milestone details for a campaign.
+        #[ink(message)]
+        pub fn get_milestones(&self, campaign_id: u32) -> Option<Vec<Milestone>> {
+            let campaign = self.campaigns.get(campaign_id)?;
+            Some(campaign.milestones)
+        }
+
+        /// Get a milestone campaign's data, its milestones with derived status, and the
+        /// viewer's voting power and votes, in one call.
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The campaign.
+        /// * `viewer` - The account the page is shown to.
+        ///
+        /// # Returns
+        ///
+        /// `None` if the campaign doesn't exist.
+        #[ink(message)]
+        pub fn get_milestone_dashboard(&self, campaign_id: u32, viewer: AccountId) -> Option<MilestoneDashboard> {
+            let campaign = self.campaigns.get(campaign_id)?;
+            let current_time = self.env().block_timestamp();
+
+            let milestones = campaign.milestones
+                .iter()
+                .enumerate()
+                .map(|(index, milestone)| {
+                    let index = index as u32;
+                    let status = if milestone.released {
+                        MilestoneStatus::Released
+                    } else if milestone.voting_active {
+                        MilestoneStatus::Voting
+                    } else if current_time > milestone.deadline {
+                        MilestoneStatus::Overdue
+                    } else {
+                        MilestoneStatus::Pending
+                    };
+
+                    MilestoneView {
+                        milestone: milestone.clone(),
+                        status,
+                        viewer_voted: self.has_voted_on_milestone(campaign_id, index, viewer),
+                        viewer_vote_weight: self.get_vote_weight(campaign_id, index, viewer),
+                    }
+                })
+                .collect();
+
+            // Donations made after voting opened don't count toward the open vote
+            let viewer_voting_power = match campaign.milestones.iter()
+                .position(|milestone| milestone.voting_active && !milestone.released)
+            {
+                Some(index) => self.vote_weight_at(
+                    campaign_id,
+                    index as u32,
+                    viewer,
+                    campaign.milestones[index].activated_at,
+                ),
+                None => self.get_donor_total(campaign_id, viewer),
+            };
+
+            Some(MilestoneDashboard {
+                viewer_voting_power,
+                campaign,
+                milestones,
+            })
+        }
+
+        /// Check if a donor has voted on a milestone.
+        #[ink(message)]
+        pub fn has_voted_on_milestone(
+            &self,
+            campaign_id: u32,
+            milestone_index: u32,
+            voter: AccountId,
+        ) -> bool {
+            let vote_key = (campaign_id, milestone_index, voter);
+            self.milestone_votes.get(vote_key).is_some()
+        }
+
+        /// Get voter's weight on a milestone.
+        #[ink(message)]
+        pub fn get_vote_weight(
+            &self,
+            campaign_id: u32,
+            milestone_index: u32,
+            voter: AccountId,
+        ) -> Balance {
+            let vote_key = (campaign_id, milestone_index, voter);
+            self.milestone_votes.get(vote_key).map(|(weight, _)| weight).unwrap_or(0)
+        }
+
+        /// List who voted on a milestone, with their weight, choice and when they voted (paginated).
+        ///
+        /// # Arguments
+        ///
+        /// * `campaign_id` - The campaign.
+        /// * `milestone_index` - The milestone.
+        /// * `offset` - Number of voters to skip.
+        /// * `limit` - Maximum number of voters to return (capped at `MAX_QUERY_LIMIT`).
+        ///
+        /// # Returns
+        ///
+        /// A vector of `(voter, weight, approve, cast_at)` in voting order.
+        #[ink(message)]
+        pub fn get_milestone_voters(
+            &self,
+            campaign_id: u32,
+            milestone_index: u32,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(AccountId, Balance, bool, Timestamp)> {
+            let limit = limit.min(MAX_QUERY_LIMIT);
+            let count = self.milestone_voter_count.get((campaign_id, milestone_index)).unwrap_or(0);
+            let end = offset.saturating_add(limit).min(count);
+
+            (offset..end)
+                .filter_map(|vote_index| self.milestone_voters.get((campaign_id, milestone_index, vote_index)))
+                .filter_map(|(voter, cast_at)| {
+                    self.milestone_votes
+                        .get((campaign_id, milestone_index, voter))
+                        .map(|(weight, approve)| (voter, weight, approve, cast_at))
+                })
+                .collect()
+        }
+
+        /// List milestones that are open for voting and still need the donor's vote.
+        ///
+        /// # Arguments
+        ///
+        /// * `donor` - The donor to check.
+        /// * `cursor` - Scan campaigns with an ID above this; `None` starts at the beginning.
+        /// * `limit` - Maximum number of results (capped at `MAX_QUERY_LIMIT`).
+        ///
+        /// # Returns
+        ///
+        /// `(campaign_id, milestone_index)` pairs where voting is active, the donor has
+        /// voting weight, and the donor hasn't voted yet, and the cursor to resume from
+        /// (`None` once every campaign has been scanned). A call examines at most
+        /// `MAX_CAMPAIGN_SCAN` campaigns.
+        #[ink(message)]
+        pub fn get_campaigns_awaiting_my_vote(
+            &self,
+            donor: AccountId,
+            cursor: Option<u32>,
+            limit: u32,
+        ) -> (Vec<(u32, u32)>, Option<u32>) {
+            self.scan_campaigns(cursor, limit, |campaign_id, campaign| {
+                if !campaign.uses_milestones {
+                    return None;
+                }
+
+                // Milestones open in order, so at most one is being voted on
+                let idx = campaign.milestones.iter()
+                    .position(|milestone| milestone.voting_active && !milestone.released)?;
+                let milestone_index = idx as u32;
+                let weight = self.vote_weight_at(
+                    campaign_id,
+                    milestone_index,
+                    donor,
+                    campaign.milestones[idx].activated_at,
+                );
+                (weight > 0 && !self.has_voted_on_milestone(campaign_id, milestone_index, donor))
+                    .then_some((campaign_id, milestone_index))
+            })
+        }
+    }
+
+    // Events
+    /// Emitted when a new campaign is created.
+    #[ink(event)]
+    pub struct CampaignCreated {
+        /// The unique ID of the created campaign.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The account that owns the new campaign.
+        #[ink(topic)]
+        owner: AccountId,
+        /// The funding goal of the campaign.
+        goal: Balance,
+        /// The deadline of the campaign.
+        deadline: Timestamp,
+    }
+
+    /// Emitted when a donation is made to a campaign.
+    #[ink(event)]
+    pub struct DonationReceived {
+        /// The ID of the campaign that received the donation.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The account that made the donation.
+        #[ink(topic)]
+        donor: AccountId,
+        /// The amount of the donation.
+        amount: Balance,
+        /// The campaign deadline, so notifiers can schedule reminders without a query.
+        deadline: Timestamp,
+        /// The index of the donation within the campaign.
+        donation_index: u32,
+        /// Whether an NFT receipt was minted for the donation.
+        nft_minted: bool,
+        /// The minted receipt's token ID, if any.
+        nft_token_id: Option<u128>,
+    }
+
+    /// Emitted when funds are withdrawn from a campaign.
+    #[ink(event)]
+    pub struct FundsWithdrawn {
+        /// The ID of the campaign from which funds were withdrawn.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The account that received the funds.
+        #[ink(topic)]
+        beneficiary: AccountId,
+        /// The amount of funds withdrawn.
+        amount: Balance,
+    }
+
+    /// Emitted when a campaign's matching is withdrawn separately from its donations.
+    #[ink(event)]
+    pub struct MatchingWithdrawn {
+        /// The ID of the campaign.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The account that received the matching.
+        #[ink(topic)]
+        beneficiary: AccountId,
+        /// The matching amount withdrawn.
+        amount: Balance,
+    }
+
+    /// Emitted when a campaign is cancelled.
     #[ink(event)]
-    pub struct CampaignCreated {
-        /// The unique ID of the created campaign.
+    pub struct CampaignCancelled {
+        /// The ID of the cancelled campaign.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The account that cancelled the campaign.
+        #[ink(topic)]
+        cancelled_by: AccountId,
+    }
+
+    /// Emitted when a campaign's success hook call fails.
+    #[ink(event)]
+    pub struct SuccessHookFailed {
+        /// The campaign that succeeded.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The hook contract that couldn't be notified.
+        hook: AccountId,
+    }
+
+    /// Emitted when a duplicate campaign is merged into another.
+    #[ink(event)]
+    pub struct CampaignsMerged {
+        /// The campaign that was merged away.
+        #[ink(topic)]
+        source_id: u32,
+        /// The campaign that received the donations.
+        #[ink(topic)]
+        target_id: u32,
+        /// The raised amount moved to the target.
+        amount: Balance,
+    }
+
+    /// Emitted when an insolvent campaign switches to pro-rata refunds.
+    #[ink(event)]
+    pub struct ProRataRefundsEnabled {
+        /// The ID of the campaign.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The balance shared among the remaining donors.
+        available: Balance,
+        /// The refunds still outstanding at the time.
+        owed: Balance,
+    }
+
+    /// Emitted when a campaign owner sets the refund window.
+    #[ink(event)]
+    pub struct RefundWindowSet {
+        /// The ID of the campaign.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// How long refunds stay open after failure (0 = forever).
+        refund_window: u64,
+        /// The account that receives unclaimed refunds.
+        fallback: AccountId,
+    }
+
+    /// Emitted when unclaimed refunds go to a campaign's fallback account.
+    #[ink(event)]
+    pub struct UnclaimedRefundsSwept {
+        /// The ID of the campaign.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The account that received the funds.
+        #[ink(topic)]
+        fallback: AccountId,
+        /// The amount swept.
+        amount: Balance,
+    }
+
+    /// Emitted when the admin opens refunds on an abandoned campaign.
+    #[ink(event)]
+    pub struct CampaignForcedRefundable {
+        /// The ID of the campaign.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The admin that opened refunds.
+        #[ink(topic)]
+        forced_by: AccountId,
+    }
+
+    /// Emitted when a donor claims a refund for a failed campaign.
+    #[ink(event)]
+    pub struct RefundClaimed {
+        /// The ID of the campaign.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The donor who claimed the refund.
+        #[ink(topic)]
+        donor: AccountId,
+        /// The amount refunded.
+        amount: Balance,
+    }
+
+    /// Emitted when NFT minting fails after a donation.
+    #[ink(event)]
+    pub struct NftMintingFailed {
+        /// The ID of the campaign.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The donor who made the donation.
+        #[ink(topic)]
+        donor: AccountId,
+        /// Error code from NFT minting.
+        error_code: u8,
+    }
+
+    /// Emitted when a donation NFT receipt is minted.
+    #[ink(event)]
+    pub struct NftReceiptMinted {
+        /// The ID of the campaign.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The donor who received the NFT.
+        #[ink(topic)]
+        donor: AccountId,
+        /// The NFT token ID.
+        nft_token_id: u128,
+        /// The donation amount.
+        amount: Balance,
+    }
+
+    /// Emitted when funds are added to the matching pool.
+    #[ink(event)]
+    pub struct MatchingPoolFunded {
+        /// The account that funded the pool.
+        #[ink(topic)]
+        funder: AccountId,
+        /// The amount added to the pool.
+        amount: Balance,
+        /// The new total pool balance.
+        total_pool: Balance,
+    }
+
+    /// Emitted when a new matching round is created.
+    #[ink(event)]
+    pub struct MatchingRoundCreated {
+        /// The ID of the new round.
+        #[ink(topic)]
+        round_id: u32,
+        /// The pool amount allocated to this round.
+        pool_amount: Balance,
+        /// When the round opens.
+        start_time: Timestamp,
+        /// When the round ends.
+        end_time: Timestamp,
+    }
+
+    /// Emitted when matching funds are distributed to a campaign.
+    #[ink(event)]
+    pub struct MatchingDistributed {
+        /// The campaign that received matching.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The matching amount distributed.
+        matching_amount: Balance,
+        /// The round ID.
+        round_id: u32,
+    }
+
+    /// Emitted when accrued platform fees are sent to the treasury.
+    #[ink(event)]
+    pub struct FeesCollected {
+        /// The account that received the fees.
+        #[ink(topic)]
+        treasury: AccountId,
+        /// The amount transferred.
+        amount: Balance,
+    }
+
+    /// Emitted when the admin withdraws free balance from a deprecated contract.
+    #[ink(event)]
+    pub struct EmergencyWithdrawal {
+        /// The account that received the funds.
+        #[ink(topic)]
+        to: AccountId,
+        /// The amount transferred.
+        amount: Balance,
+    }
+
+    /// Emitted when a campaign creation fee is paid.
+    #[ink(event)]
+    pub struct CreationFeePaid {
+        /// The campaign that was created.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The account that paid the fee.
+        #[ink(topic)]
+        payer: AccountId,
+        /// The fee amount.
+        amount: Balance,
+    }
+
+    /// Emitted when the admin changes the platform fee.
+    #[ink(event)]
+    pub struct PlatformFeeChanged {
+        /// The previous fee in basis points.
+        old_fee: u32,
+        /// The new fee in basis points.
+        new_fee: u32,
+    }
+
+    /// Emitted when a timelocked admin action is queued.
+    #[ink(event)]
+    pub struct AdminActionQueued {
+        /// The ID of the queued action.
+        #[ink(topic)]
+        action_id: u32,
+        /// The queued change.
+        action: AdminAction,
+        /// The earliest execution time.
+        eta: Timestamp,
+    }
+
+    /// Emitted when a timelocked admin action is executed.
+    #[ink(event)]
+    pub struct AdminActionExecuted {
+        /// The ID of the executed action.
+        #[ink(topic)]
+        action_id: u32,
+        /// The change that was made.
+        action: AdminAction,
+    }
+
+    /// Emitted when the admin corrects a campaign's state.
+    #[ink(event)]
+    pub struct CampaignStateChanged {
+        /// The campaign that was corrected.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The stale state.
+        old_state: CampaignState,
+        /// The recalculated state.
+        new_state: CampaignState,
+    }
+
+    /// Emitted when the admin force-clears the reentrancy lock.
+    #[ink(event)]
+    pub struct LockForceReset {
+        /// The admin who cleared the lock.
+        #[ink(topic)]
+        admin: AccountId,
+        /// Whether the lock was actually held.
+        was_locked: bool,
+    }
+
+    /// Emitted after a donation when the campaign's estimated matching changes.
+    #[ink(event)]
+    pub struct EstimatedMatchingUpdated {
+        /// The campaign whose estimate changed.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The new estimated matching amount.
+        estimate: Balance,
+        /// The round ID.
+        round_id: u32,
+    }
+
+    /// Emitted when a withdrawal starts a campaign's vesting schedule.
+    #[ink(event)]
+    pub struct VestingStarted {
+        /// The ID of the campaign.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The total amount that will vest to the beneficiary.
+        amount: Balance,
+        /// The vesting period in milliseconds.
+        duration: u64,
+    }
+
+    /// Emitted when the part of a donation above a campaign's hard cap is sent back.
+    #[ink(event)]
+    pub struct DonationExcessRefunded {
+        /// The ID of the campaign that was donated to.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The donor who got the excess back.
+        #[ink(topic)]
+        donor: AccountId,
+        /// The amount sent back.
+        amount: Balance,
+    }
+
+    /// Emitted when a campaign owner tops up their own campaign.
+    #[ink(event)]
+    pub struct OwnerToppedUp {
+        /// The ID of the campaign that was topped up.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The campaign owner.
+        owner: AccountId,
+        /// The amount contributed.
+        amount: Balance,
+    }
+
+    /// Emitted when a refund transfer failed and the amount was credited instead.
+    #[ink(event)]
+    pub struct RefundCredited {
+        /// The ID of the campaign the refund came from.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The account that can withdraw the credit.
+        #[ink(topic)]
+        account: AccountId,
+        /// The credited amount.
+        amount: Balance,
+    }
+
+    /// Emitted when a donor's refund is donated to another campaign.
+    #[ink(event)]
+    pub struct RefundRedirected {
+        /// The failed campaign the refund came from.
+        #[ink(topic)]
+        from_campaign_id: u32,
+        /// The campaign that received the donation.
+        #[ink(topic)]
+        to_campaign_id: u32,
+        /// The donor whose refund was redirected.
+        donor: AccountId,
+        /// The amount donated to the target (any part past its hard cap went back to the donor).
+        amount: Balance,
+    }
+
+    /// Emitted when milestones are added to a campaign.
+    #[ink(event)]
+    pub struct MilestonesAdded {
+        /// The campaign ID.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// Number of milestones added.
+        milestone_count: u32,
+    }
+
+    /// Emitted when voting is activated for a milestone.
+    #[ink(event)]
+    pub struct MilestoneVotingActivated {
+        /// The campaign ID.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The milestone index.
+        milestone_index: u32,
+    }
+
+    /// Emitted when a donor votes on a milestone.
+    #[ink(event)]
+    pub struct MilestoneVoted {
+        /// The campaign ID.
+        #[ink(topic)]
+        campaign_id: u32,
+        /// The milestone index.
+        milestone_index: u32,
+        /// The voter.
+        #[ink(topic)]
+        voter: AccountId,
+        /// Whether they approved.
+        approve: bool,
+        /// The vote weight (donation amount).
+        weight: Balance,
+        /// When the vote was cast.
+        timestamp: Timestamp,
+    }
+
+    /// Emitted when milestone funds are released.
+    #[ink(event)]
+    pub struct MilestoneFundsReleased {
+        /// The campaign ID.
         #[ink(topic)]
         campaign_id: u32,
-        /// The account that owns the new campaign.
+        /// The milestone index.
+        milestone_index: u32,
+        /// The amount released.
+        amount: Balance,
+        /// The beneficiary who received funds.
         #[ink(topic)]
-        owner: AccountId,
-        /// The funding goal of the campaign.
-        goal: Balance,
-        /// The deadline of the campaign.
-        deadline: Timestamp,
+        beneficiary: AccountId,
     }
 
-    /// Emitted when a donation is made to a campaign.
-    #[ink(event)]
-    pub struct DonationReceived {
-        /// The ID of the campaign that received the donation.
-        #[ink(topic)]
-        campaign_id: u32,
-        /// The account that made the donation.
-        #[ink(topic)]
-        donor: AccountId,
-        /// The amount of the donation.
-        amount: Balance,
-    }
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{test, DefaultEnvironment};
+        use std::cell::RefCell;
+
+        thread_local! {
+            /// Active (manager, subscriber, creator) subscriptions seen by the mock manager.
+            static MOCK_SUBSCRIPTIONS: RefCell<Vec<(AccountId, AccountId, AccountId)>> = const { RefCell::new(Vec::new()) };
+        }
+
+        thread_local! {
+            /// (hook, campaign_id) callbacks received by mock hook contracts.
+            static MOCK_HOOK_CALLS: RefCell<Vec<(AccountId, u32)>> = const { RefCell::new(Vec::new()) };
+            /// Hook contracts whose callback fails.
+            static MOCK_FAILING_HOOKS: RefCell<Vec<AccountId>> = const { RefCell::new(Vec::new()) };
+        }
+
+        /// Mock of a hook contract's `on_campaign_success`.
+        pub(super) fn mock_on_campaign_success(hook: AccountId, campaign_id: u32) -> bool {
+            if MOCK_FAILING_HOOKS.with(|hooks| hooks.borrow().contains(&hook)) {
+                return false;
+            }
+            MOCK_HOOK_CALLS.with(|calls| calls.borrow_mut().push((hook, campaign_id)));
+            true
+        }
+
+        thread_local! {
+            /// (nft_contract, to, campaign_id, soulbound) receipts minted by the mock NFT contract.
+            static MOCK_NFT_MINTS: RefCell<Vec<(AccountId, AccountId, u32, bool)>> = const { RefCell::new(Vec::new()) };
+        }
+
+        /// Mock of the NFT contract's `mint_donation_receipt`; token IDs count up from 1.
+        pub(super) fn mock_mint_donation_receipt(nft_contract: AccountId, to: AccountId, campaign_id: u32, soulbound: bool) -> Result<u128, u8> {
+            MOCK_NFT_MINTS.with(|mints| {
+                let mut mints = mints.borrow_mut();
+                mints.push((nft_contract, to, campaign_id, soulbound));
+                Ok(mints.len() as u128)
+            })
+        }
+
+        thread_local! {
+            /// (nft_contract, campaign_id) funded notifications received by the mock NFT contract.
+            static MOCK_NFT_FUNDED: RefCell<Vec<(AccountId, u32)>> = const { RefCell::new(Vec::new()) };
+        }
+
+        /// Mock of the NFT contract's `mark_campaign_funded`.
+        pub(super) fn mock_mark_campaign_funded(nft_contract: AccountId, campaign_id: u32) {
+            MOCK_NFT_FUNDED.with(|funded| funded.borrow_mut().push((nft_contract, campaign_id)));
+        }
+
+        /// Mock of the subscription manager's `check_subscription`.
+        pub(super) fn mock_check_subscription(manager: AccountId, subscriber: AccountId, creator: AccountId) -> Option<bool> {
+            Some(MOCK_SUBSCRIPTIONS.with(|subs| subs.borrow().contains(&(manager, subscriber, creator))))
+        }
+
+        #[ink::test]
+        fn create_campaign_works() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let result = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                1000,
+                10_000_000,
+                accounts.bob,
+            );
+
+            assert!(result.is_ok());
+            assert_eq!(platform.get_campaign_count(), 1);
+        }
+
+        #[ink::test]
+        fn batch_create_campaigns_works() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaigns_data = vec![
+                (String::from("Campaign 1"), String::from("Desc 1"), 1000, 10_000_000, accounts.bob),
+                (String::from("Campaign 2"), String::from("Desc 2"), 2000, 10_000_000, accounts.bob),
+            ];
+
+            let result = platform.create_campaigns_batch(campaigns_data);
+            assert!(result.is_ok());
+
+            let batch_result = result.unwrap();
+            assert_eq!(batch_result.successful, 2);
+            assert_eq!(batch_result.failed, 0);
+            assert_eq!(platform.get_campaign_count(), 2);
+        }
+
+        #[ink::test]
+        fn version_tracking_works() {
+            let platform = DonationPlatformV2::new();
+            assert_eq!(platform.get_version(), 2);
+        }
+
+        #[ink::test]
+        fn invalid_campaign_title_fails() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            // Empty title
+            let result = platform.create_campaign(
+                String::from(""),
+                String::from("Description"),
+                1000,
+                10_000_000,
+                accounts.bob,
+            );
+            assert_eq!(result, Err(Error::InvalidTitle));
+
+            // Title too long (>100 chars)
+            let long_title = "a".repeat(101);
+            let result = platform.create_campaign(
+                long_title,
+                String::from("Description"),
+                1000,
+                10_000_000,
+                accounts.bob,
+            );
+            assert_eq!(result, Err(Error::InvalidTitle));
+        }
+
+        #[ink::test]
+        fn invalid_goal_fails() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            // Zero goal
+            let result = platform.create_campaign(
+                String::from("Test"),
+                String::from("Description"),
+                0,
+                10_000_000,
+                accounts.bob,
+            );
+            assert_eq!(result, Err(Error::InvalidGoal));
+
+            // Goal too large
+            let result = platform.create_campaign(
+                String::from("Test"),
+                String::from("Description"),
+                1_000_000_000_000_001,
+                10_000_000,
+                accounts.bob,
+            );
+            assert_eq!(result, Err(Error::InvalidGoal));
+        }
+
+        #[ink::test]
+        fn invalid_deadline_fails() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            // Deadline too soon
+            let result = platform.create_campaign(
+                String::from("Test"),
+                String::from("Description"),
+                1000,
+                1000, // Too soon
+                accounts.bob,
+            );
+            assert_eq!(result, Err(Error::InvalidDeadline));
+        }
+
+        #[ink::test]
+        fn cancel_campaign_works() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                1000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Cancel campaign
+            let result = platform.cancel_campaign(campaign_id);
+            assert!(result.is_ok());
+
+            // Verify state changed to Failed
+            let campaign = platform.get_campaign(campaign_id).unwrap();
+            assert_eq!(campaign.state, CampaignState::Failed);
+        }
+
+        #[ink::test]
+        fn non_owner_cannot_cancel() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                1000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Set caller to non-owner
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+
+            let result = platform.cancel_campaign(campaign_id);
+            assert_eq!(result, Err(Error::NotCampaignOwner));
+        }
+
+        #[ink::test]
+        fn minimum_donation_enforced() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                1000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Try donating below minimum
+            let result = platform.process_donation(campaign_id, MIN_DONATION - 1);
+            assert_eq!(result, Err(Error::InvalidDonationAmount));
+
+            // Donate at minimum should work
+            let result = platform.process_donation(campaign_id, MIN_DONATION);
+            assert!(result.is_ok());
+        }
+
+        #[ink::test]
+        fn donation_count_overflow_protection() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                1000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Get campaign and manually set donation_count near max
+            let mut campaign = platform.campaigns.get(campaign_id).unwrap();
+            campaign.donation_count = u32::MAX;
+            platform.save_campaign(campaign_id, &campaign);
+
+            // Try to donate - should fail with overflow protection
+            let result = platform.process_donation(campaign_id, MIN_DONATION);
+            assert_eq!(result, Err(Error::InvalidDonationAmount));
+        }
+
+        #[ink::test]
+        fn get_campaign_details_works() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Add some donations
+            platform.process_donation(campaign_id, MIN_DONATION).unwrap();
+            platform.process_donation(campaign_id, MIN_DONATION * 2).unwrap();
+
+            // Get details with pagination
+            let details = platform.get_campaign_details(campaign_id, 0, 10).unwrap();
+            assert_eq!(details.total_donations, 2);
+            assert_eq!(details.donations.len(), 2);
+        }
+
+        #[ink::test]
+        fn batch_operations_respect_max_size() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            // Try to create more campaigns than max_batch_size
+            let mut campaigns_data = Vec::new();
+            for _ in 0..51 {
+                campaigns_data.push((
+                    String::from("Campaign"),
+                    String::from("Desc"),
+                    1000,
+                    10_000_000,
+                    accounts.bob,
+                ));
+            }
+
+            let result = platform.create_campaigns_batch(campaigns_data);
+            assert_eq!(result, Err(Error::BatchSizeTooLarge));
+        }
+
+        #[ink::test]
+        fn set_max_batch_size_requires_admin() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            // Set caller to non-admin
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+
+            let result = platform.set_max_batch_size(100);
+            assert_eq!(result, Err(Error::NotCampaignOwner));
+        }
+
+        #[ink::test]
+        fn set_max_batch_size_works() {
+            let mut platform = DonationPlatformV2::new();
+
+            let result = platform.set_max_batch_size(100);
+            assert!(result.is_ok());
+            assert_eq!(platform.get_max_batch_size(), 100);
+        }
+
+        #[ink::test]
+        fn get_campaigns_paginated_works() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            // Create 5 campaigns
+            for i in 0..5 {
+                platform.create_campaign(
+                    format!("Campaign {}", i),
+                    String::from("Description"),
+                    1000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap();
+            }
+
+            // Get first 3
+            let campaigns = platform.get_campaigns_paginated(0, 3);
+            assert_eq!(campaigns.len(), 3);
+
+            // Get next 2
+            let campaigns = platform.get_campaigns_paginated(3, 3);
+            assert_eq!(campaigns.len(), 2);
+        }
+
+        #[ink::test]
+        fn get_campaigns_paginated_handles_overflow() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            for i in 0..5 {
+                platform.create_campaign(
+                    format!("Campaign {}", i),
+                    String::from("Description"),
+                    1000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap();
+            }
+
+            assert!(platform.get_campaigns_paginated(u32::MAX - 1, 10).is_empty());
+            assert!(platform.get_campaigns_paginated(5, u32::MAX).is_empty());
+            assert_eq!(platform.get_campaigns_paginated(2, u32::MAX).len(), 3);
+        }
+
+        #[ink::test]
+        fn migration_constructor_works() {
+            let platform = DonationPlatformV2::migrate_from_v1(42);
+            assert_eq!(platform.get_campaign_count(), 42);
+            assert_eq!(platform.get_version(), 2);
+        }
+
+        #[ink::test]
+        fn campaign_reaches_goal() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,  // Goal of 10M (10 DOT)
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Donate exactly the goal amount
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+
+            let campaign = platform.get_campaign(campaign_id).unwrap();
+            assert_eq!(campaign.state, CampaignState::Successful);
+            assert_eq!(campaign.raised, 10_000_000);
+        }
+
+        #[ink::test]
+        fn cannot_donate_to_inactive_campaign() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                1000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Cancel campaign
+            platform.cancel_campaign(campaign_id).unwrap();
+
+            // Try to donate
+            let result = platform.process_donation(campaign_id, MIN_DONATION);
+            assert_eq!(result, Err(Error::CampaignNotActive));
+        }
+
+        #[ink::test]
+        fn get_active_campaigns_filters_correctly() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            // Create 3 campaigns
+            for i in 0..3 {
+                platform.create_campaign(
+                    format!("Campaign {}", i),
+                    String::from("Description"),
+                    1000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap();
+            }
+
+            // Cancel one
+            platform.cancel_campaign(1).unwrap();
+
+            // Get active campaigns
+            let active = platform.get_active_campaigns(0, 10);
+            assert_eq!(active.len(), 2);
+        }
+        #[ink::test]
+        fn platform_fee_deducted() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                1000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Donate 10_000_000 (10 DOT)
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+
+            // Check campaign raised (should be gross 10_000_000)
+            let campaign = platform.get_campaign(campaign_id).unwrap();
+            assert_eq!(campaign.raised, 10_000_000);
+
+            // In a real environment, 3 would be sent to treasury.
+            // In unit tests, we can't easily check the transfer without mocking,
+            // but we can check the withdrawal amount later.
+        }
+
+        #[ink::test]
+        fn withdrawal_respects_fees() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                100, // Goal 100
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Donate 10_000_000 (10 DOT)
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+
+            // Campaign successful
+            let campaign = platform.get_campaign(campaign_id).unwrap();
+            assert_eq!(campaign.state, CampaignState::Successful);
+
+            // Withdraw
+            // We need to mock the contract having funds, otherwise transfer fails in test?
+            // ink! tests usually start with some balance.
+            // But we transferred fee OUT.
+            // Fee = 10_000_000 * 3 / 100 = 300_000.
+            // Net remaining = 9_700_000.
+            
+            // We need to set the contract balance to simulate the donation remaining amount.
+            // In ink! 5, we might need to import Env to call env() on the contract instance in tests
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 9_700_000);
+
+            // Set caller to owner (Alice created it)
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            
+            let result = platform.withdraw_funds(campaign_id);
+            assert_eq!(result, Ok(()));
+        }
+
+        #[ink::test]
+        fn donations_paginate_by_index() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            for i in 1..=5 {
+                platform.process_donation(campaign_id, MIN_DONATION * i).unwrap();
+            }
+
+            let page = platform.get_campaign_details(campaign_id, 1, 2).unwrap();
+            assert_eq!(page.total_donations, 5);
+            assert_eq!(page.donations.len(), 2);
+            assert_eq!(page.donations[0].amount, MIN_DONATION * 2);
+            assert_eq!(page.donations[1].amount, MIN_DONATION * 3);
+
+            // Offset past the end returns an empty page instead of panicking
+            let page = platform.get_campaign_details(campaign_id, 10, 5).unwrap();
+            assert!(page.donations.is_empty());
+        }
+
+        #[ink::test]
+        fn refund_sums_indexed_donations() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Bob donates twice, Charlie once
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, MIN_DONATION).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, MIN_DONATION * 5).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, MIN_DONATION * 2).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.cancel_campaign(campaign_id).unwrap();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            let bob_before = test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(platform.claim_refund(campaign_id), Ok(()));
+            let bob_after = test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(bob_after - bob_before, MIN_DONATION * 3);
+        }
+
+        #[ink::test]
+        fn many_donations_scale() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                1_000_000_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Each donation is a single indexed insert, so a long history stays cheap
+            for _ in 0..500 {
+                platform.process_donation(campaign_id, MIN_DONATION).unwrap();
+            }
+
+            let campaign = platform.get_campaign(campaign_id).unwrap();
+            assert_eq!(campaign.donation_count, 500);
+            assert_eq!(campaign.raised, MIN_DONATION * 500);
+
+            let page = platform.get_campaign_details(campaign_id, 490, 50).unwrap();
+            assert_eq!(page.total_donations, 500);
+            assert_eq!(page.donations.len(), 10);
+        }
+
+        #[ink::test]
+        fn donor_totals_aggregate_donations() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, MIN_DONATION).unwrap();
+            platform.process_donation(campaign_id, MIN_DONATION * 3).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, MIN_DONATION * 7).unwrap();
+
+            let details = platform.get_campaign_details(campaign_id, 0, 10).unwrap();
+            let bob_sum: Balance = details.donations.iter()
+                .filter(|d| d.donor == accounts.bob)
+                .map(|d| d.amount)
+                .sum();
+
+            assert_eq!(platform.get_donor_total(campaign_id, accounts.bob), bob_sum);
+            assert_eq!(platform.get_donor_total(campaign_id, accounts.charlie), MIN_DONATION * 7);
+            assert_eq!(platform.get_donor_total(campaign_id, accounts.django), 0);
+        }
+
+        #[ink::test]
+        fn vote_weight_uses_donor_total() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.add_milestones(campaign_id, vec![(String::from("M1"), 10000, 30)]).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 4_000_000).unwrap();
+            platform.process_donation(campaign_id, 6_000_000).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.activate_milestone_voting(campaign_id, 0).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.vote_on_milestone(campaign_id, 0, true).unwrap();
+
+            assert_eq!(platform.get_vote_weight(campaign_id, 0, accounts.bob), 10_000_000);
+            assert_eq!(
+                platform.get_vote_weight(campaign_id, 0, accounts.bob),
+                platform.get_donor_total(campaign_id, accounts.bob),
+            );
+        }
+
+        #[ink::test]
+        fn withdraw_matching_after_donations() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            // Fund the pool and open a round before the campaign is created
+            test::set_value_transferred::<DefaultEnvironment>(5_000_000);
+            platform.fund_matching_pool().unwrap();
+            let round_id = platform.create_matching_round(5_000_000, 1_000).unwrap();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.django,
+            ).unwrap();
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+
+            // Donations first: matching isn't distributed yet so only the net is paid
+            platform.withdraw_funds(campaign_id).unwrap();
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.django), Ok(9_700_000));
+            assert_eq!(platform.withdraw_matching(campaign_id), Err(Error::MatchingNotDistributed));
+            // Accrued fee and the undistributed pool
+            assert_eq!(platform.get_liabilities(), 300_000 + 5_000_000);
+
+            test::set_block_timestamp::<DefaultEnvironment>(2_000);
+            platform.calculate_and_distribute_matching(round_id).unwrap();
+            // The withdrawn campaign is still owed its matching
+            assert_eq!(platform.get_liabilities(), 300_000 + 5_000_000);
+
+            // Then the matching, exactly once
+            assert_eq!(platform.withdraw_matching(campaign_id), Ok(()));
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.django), Ok(14_700_000));
+            assert_eq!(platform.withdraw_matching(campaign_id), Err(Error::FundsAlreadyWithdrawn));
+            assert_eq!(platform.get_liabilities(), 300_000);
+        }
+
+        #[ink::test]
+        fn force_refundable_after_grace_period() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.django,
+            ).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+            assert_eq!(platform.claim_refund(campaign_id), Err(Error::CampaignFailed));
+
+            // Too early, even for the admin
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_block_timestamp::<DefaultEnvironment>(10_000_000 + FORCE_REFUND_GRACE_PERIOD);
+            assert_eq!(platform.force_refundable(campaign_id), Err(Error::GracePeriodActive));
+
+            test::set_block_timestamp::<DefaultEnvironment>(10_000_001 + FORCE_REFUND_GRACE_PERIOD);
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.force_refundable(campaign_id), Err(Error::NotCampaignOwner));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(platform.force_refundable(campaign_id), Ok(()));
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().state, CampaignState::Successful);
+
+            // The owner can no longer withdraw, but the donor can claim a refund
+            assert_eq!(platform.withdraw_funds(campaign_id), Err(Error::CampaignFailed));
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            let bob_before = test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(platform.claim_refund(campaign_id), Ok(()));
+            let bob_after = test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(bob_after - bob_before, 10_000_000);
+        }
+
+        #[ink::test]
+        fn role_getters_work() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            let platform = DonationPlatformV2::new();
+
+            assert_eq!(platform.get_admin(), accounts.bob);
+            assert_eq!(platform.get_treasury(), accounts.bob);
+            assert!(platform.is_admin(accounts.bob));
+            assert!(!platform.is_admin(accounts.alice));
+        }
+
+        #[ink::test]
+        fn auto_activate_first_milestone_on_success() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.add_milestones(campaign_id, vec![
+                (String::from("M1"), 5000, 30),
+                (String::from("M2"), 5000, 60),
+            ]).unwrap();
+            platform.set_auto_activate_first_milestone(campaign_id, true).unwrap();
+
+            // Not yet successful: nothing is live
+            platform.process_donation(campaign_id, 5_000_000).unwrap();
+            assert!(!platform.get_milestones(campaign_id).unwrap()[0].voting_active);
+
+            // Reaching the goal opens milestone 0 only
+            platform.process_donation(campaign_id, 5_000_000).unwrap();
+            let milestones = platform.get_milestones(campaign_id).unwrap();
+            assert!(milestones[0].voting_active);
+            assert!(!milestones[1].voting_active);
+
+            // Donors can vote right away
+            assert!(platform.vote_on_milestone(campaign_id, 0, true).is_ok());
+        }
+
+        #[ink::test]
+        fn auto_activate_is_opt_in() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.add_milestones(campaign_id, vec![(String::from("M1"), 10000, 30)]).unwrap();
+
+            // Only the owner may opt in
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                platform.set_auto_activate_first_milestone(campaign_id, true),
+                Err(Error::NotCampaignOwner)
+            );
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+            assert!(!platform.get_milestones(campaign_id).unwrap()[0].voting_active);
+        }
+
+        #[ink::test]
+        fn campaigns_awaiting_vote_lists_pending_milestones() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let mut campaign_ids = Vec::new();
+            for _ in 0..3 {
+                let campaign_id = platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap();
+                platform.add_milestones(campaign_id, vec![
+                    (String::from("M1"), 5000, 30),
+                    (String::from("M2"), 5000, 60),
+                ]).unwrap();
+                campaign_ids.push(campaign_id);
+            }
+
+            // Alice backs the first two campaigns, Bob backs the third
+            platform.process_donation(campaign_ids[0], 10_000_000).unwrap();
+            platform.process_donation(campaign_ids[1], 10_000_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_ids[2], 10_000_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+
+            for campaign_id in &campaign_ids {
+                platform.activate_milestone_voting(*campaign_id, 0).unwrap();
+            }
+
+            assert_eq!(
+                platform.get_campaigns_awaiting_my_vote(accounts.alice, None, 10),
+                (vec![(campaign_ids[0], 0), (campaign_ids[1], 0)], None)
+            );
+            assert_eq!(
+                platform.get_campaigns_awaiting_my_vote(accounts.alice, None, 1),
+                (vec![(campaign_ids[0], 0)], Some(campaign_ids[0]))
+            );
+            assert_eq!(
+                platform.get_campaigns_awaiting_my_vote(accounts.alice, Some(campaign_ids[0]), 10),
+                (vec![(campaign_ids[1], 0)], None)
+            );
+
+            // Voting removes the milestone from the list
+            platform.vote_on_milestone(campaign_ids[0], 0, true).unwrap();
+            assert_eq!(
+                platform.get_campaigns_awaiting_my_vote(accounts.alice, None, 10),
+                (vec![(campaign_ids[1], 0)], None)
+            );
+
+            // The scan is bounded; the cursor resumes past it
+            platform.campaign_count = MAX_CAMPAIGN_SCAN + 1;
+            assert_eq!(
+                platform.get_campaigns_awaiting_my_vote(accounts.alice, None, 10),
+                (vec![(campaign_ids[1], 0)], Some(MAX_CAMPAIGN_SCAN - 1))
+            );
+        }
+
+        #[ink::test]
+        fn redirect_refund_donates_to_active_campaign() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let failed_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            let active_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(failed_id, MIN_DONATION * 4).unwrap();
+
+            // Nothing to redirect while the source campaign is still active
+            assert_eq!(platform.redirect_refund(failed_id, active_id), Err(Error::CampaignFailed));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.cancel_campaign(failed_id).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            assert_eq!(platform.redirect_refund(failed_id, active_id), Ok(()));
+
+            let campaign = platform.get_campaign(active_id).unwrap();
+            assert_eq!(campaign.raised, MIN_DONATION * 4);
+            assert_eq!(platform.get_donor_total(active_id, accounts.charlie), MIN_DONATION * 4);
+
+            // The original refund is now claimed
+            assert_eq!(platform.claim_refund(failed_id), Err(Error::RefundAlreadyClaimed));
+            assert_eq!(
+                platform.redirect_refund(failed_id, active_id),
+                Err(Error::RefundAlreadyClaimed)
+            );
+        }
+
+        #[ink::test]
+        fn milestone_deadlines_must_be_ordered() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Out of order
+            assert_eq!(
+                platform.add_milestones(campaign_id, vec![
+                    (String::from("M1"), 5000, 60),
+                    (String::from("M2"), 5000, 30),
+                ]),
+                Err(Error::InvalidDeadline)
+            );
+
+            // Same day is not enough of a gap
+            assert_eq!(
+                platform.add_milestones(campaign_id, vec![
+                    (String::from("M1"), 5000, 30),
+                    (String::from("M2"), 5000, 30),
+                ]),
+                Err(Error::InvalidDeadline)
+            );
+
+            // Before the campaign deadline
+            assert_eq!(
+                platform.add_milestones(campaign_id, vec![(String::from("M1"), 10000, 0)]),
+                Err(Error::InvalidDeadline)
+            );
+
+            assert_eq!(
+                platform.add_milestones(campaign_id, vec![
+                    (String::from("M1"), 5000, 30),
+                    (String::from("M2"), 5000, 31),
+                ]),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn owner_top_up_is_fee_free_and_excluded_from_qf() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            // Run the contract under its own account so balances are observable
+            test::set_callee::<DefaultEnvironment>(accounts.django);
+            test::set_account_balance::<DefaultEnvironment>(accounts.django, 100_000_000);
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 6_000_000).unwrap();
+            assert_eq!(platform.get_accrued_fees(), 180_000);
+            let qf_score = platform.calculate_qf_score(campaign_id);
+
+            // Only the owner may top up
+            assert_eq!(platform.process_owner_top_up(campaign_id, 4_000_000), Err(Error::NotCampaignOwner));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(platform.process_owner_top_up(campaign_id, 4_000_000), Ok(()));
+            assert_eq!(platform.get_accrued_fees(), 180_000);
+
+            let campaign = platform.get_campaign(campaign_id).unwrap();
+            assert_eq!(campaign.raised, 10_000_000);
+            assert_eq!(campaign.state, CampaignState::Successful);
+            assert_eq!(platform.calculate_qf_score(campaign_id), qf_score);
+            assert_eq!(platform.get_donor_total(campaign_id, accounts.alice), 4_000_000);
+        }
+
+        #[ink::test]
+        fn can_claim_refund_checks_all_preconditions() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            // Unknown campaign
+            assert!(!platform.can_claim_refund(0, accounts.bob));
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, MIN_DONATION).unwrap();
+
+            // Campaign still active
+            assert!(!platform.can_claim_refund(campaign_id, accounts.bob));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.cancel_campaign(campaign_id).unwrap();
+
+            // Eligible donor vs. account that never donated
+            assert!(platform.can_claim_refund(campaign_id, accounts.bob));
+            assert!(!platform.can_claim_refund(campaign_id, accounts.charlie));
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            // Already claimed
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.claim_refund(campaign_id).unwrap();
+            assert!(!platform.can_claim_refund(campaign_id, accounts.bob));
+        }
+
+        #[ink::test]
+        fn campaign_tags_are_indexed() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let mut campaign_ids = Vec::new();
+            for _ in 0..3 {
+                campaign_ids.push(platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap());
+            }
+
+            let tags = |names: &[&str]| names.iter().map(|n| String::from(*n)).collect::<Vec<_>>();
+            platform.set_campaign_tags(campaign_ids[0], tags(&["water", "africa"])).unwrap();
+            platform.set_campaign_tags(campaign_ids[1], tags(&["water"])).unwrap();
+            platform.set_campaign_tags(campaign_ids[2], tags(&["education"])).unwrap();
+
+            assert_eq!(platform.get_campaign_tags(campaign_ids[0]), tags(&["water", "africa"]));
+            assert_eq!(
+                platform.get_campaigns_by_tag(String::from("water"), 0, 10),
+                vec![campaign_ids[0], campaign_ids[1]]
+            );
+            assert_eq!(platform.get_campaigns_by_tag(String::from("water"), 1, 10), vec![campaign_ids[1]]);
+            assert!(platform.get_campaigns_by_tag(String::from("Water"), 0, 10).is_empty());
+
+            // Retagging updates the index
+            platform.set_campaign_tags(campaign_ids[0], tags(&["education"])).unwrap();
+            assert_eq!(platform.get_campaigns_by_tag(String::from("water"), 0, 10), vec![campaign_ids[1]]);
+            assert!(platform.get_campaigns_by_tag(String::from("africa"), 0, 10).is_empty());
+            assert_eq!(
+                platform.get_campaigns_by_tag(String::from("education"), 0, 10),
+                vec![campaign_ids[2], campaign_ids[0]]
+            );
+        }
+
+        #[ink::test]
+        fn campaign_tags_are_validated() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            let too_many = (0..6).map(|i| format!("tag{}", i)).collect();
+            assert_eq!(platform.set_campaign_tags(campaign_id, too_many), Err(Error::InvalidTag));
+            assert_eq!(platform.set_campaign_tags(campaign_id, vec![String::new()]), Err(Error::InvalidTag));
+            assert_eq!(
+                platform.set_campaign_tags(campaign_id, vec!["x".repeat(33)]),
+                Err(Error::InvalidTag)
+            );
+            assert_eq!(
+                platform.set_campaign_tags(campaign_id, vec![String::from("a"), String::from("a")]),
+                Err(Error::InvalidTag)
+            );
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                platform.set_campaign_tags(campaign_id, vec![String::from("a")]),
+                Err(Error::NotCampaignOwner)
+            );
+        }
+
+        #[ink::test]
+        fn unique_donor_count_ignores_repeat_donations() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, MIN_DONATION).unwrap();
+            platform.process_donation(campaign_id, MIN_DONATION).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, MIN_DONATION).unwrap();
+
+            assert_eq!(platform.get_unique_donor_count(campaign_id), 2);
+        }
+
+        #[ink::test]
+        fn matching_requires_min_unique_donors() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            test::set_value_transferred::<DefaultEnvironment>(6_000_000);
+            platform.fund_matching_pool().unwrap();
+            let round_id = platform.create_scheduled_matching_round(6_000_000, 1, 1_000).unwrap();
+            platform.set_round_min_unique_donors(round_id, 3).unwrap();
+            test::set_block_timestamp::<DefaultEnvironment>(1);
+
+            let whale_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            let community_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // One large donor vs. three small ones
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(whale_id, MIN_DONATION * 100).unwrap();
+            for donor in [accounts.bob, accounts.charlie, accounts.django] {
+                test::set_caller::<DefaultEnvironment>(donor);
+                platform.process_donation(community_id, MIN_DONATION).unwrap();
+            }
+
+            assert_eq!(platform.get_estimated_matching(whale_id), 0);
+            assert_eq!(platform.get_estimated_matching(community_id), 6_000_000);
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_block_timestamp::<DefaultEnvironment>(2_000);
+            platform.calculate_and_distribute_matching(round_id).unwrap();
+
+            assert_eq!(platform.get_campaign(whale_id).unwrap().matching_amount, 0);
+            assert_eq!(platform.get_campaign(community_id).unwrap().matching_amount, 6_000_000);
+            assert_eq!(
+                platform.set_round_min_unique_donors(round_id, 1),
+                Err(Error::FundsAlreadyWithdrawn)
+            );
+        }
+
+        #[ink::test]
+        fn donation_emits_estimated_matching_when_enabled() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            test::set_value_transferred::<DefaultEnvironment>(5_000_000);
+            platform.fund_matching_pool().unwrap();
+            let round_id = platform.create_matching_round(5_000_000, 1_000).unwrap();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            let estimates = || {
+                let signature = <EstimatedMatchingUpdated as ink::env::Event>::SIGNATURE_TOPIC.unwrap();
+                test::recorded_events()
+                    .filter(|event| event.topics.first().map(|t| &t[..]) == Some(&signature[..]))
+                    .map(|event| {
+                        <EstimatedMatchingUpdated as scale::Decode>::decode(&mut &event.data[..]).unwrap()
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            // Off by default
+            platform.process_donation(campaign_id, MIN_DONATION).unwrap();
+            assert!(estimates().is_empty());
+
+            platform.set_round_emit_estimates(round_id, true).unwrap();
+            platform.process_donation(campaign_id, MIN_DONATION).unwrap();
+
+            let events = estimates();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].campaign_id, campaign_id);
+            assert_eq!(events[0].round_id, round_id);
+            // Sole campaign in the round: the whole pool
+            assert_eq!(events[0].estimate, 5_000_000);
+
+            // Only the round's size matters, not how many campaigns the platform has
+            platform.campaign_count += MAX_ESTIMATE_EVENT_CAMPAIGNS as u32;
+            platform.process_donation(campaign_id, MIN_DONATION).unwrap();
+            assert_eq!(estimates().len(), 2);
+        }
+
+        #[ink::test]
+        fn force_unlock_requires_admin() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Simulate a lock left behind by a trapped call
+            platform.locked = true;
+            assert_eq!(platform.donate(campaign_id), Err(Error::ReentrantCall));
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.force_unlock(), Err(Error::NotCampaignOwner));
+            assert!(platform.locked);
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(platform.force_unlock(), Ok(()));
+            assert!(!platform.locked);
+
+            test::set_value_transferred::<DefaultEnvironment>(MIN_DONATION);
+            assert_eq!(platform.donate(campaign_id), Ok(0));
+        }
+
+        #[ink::test]
+        fn get_campaigns_ending_soon_filters_by_window() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            // Deadlines at 2h, 5h and 48h
+            for deadline in [7_200_000, 18_000_000, 172_800_000] {
+                platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000,
+                    deadline,
+                    accounts.bob,
+                ).unwrap();
+            }
+
+            test::set_block_timestamp::<DefaultEnvironment>(3_600_000);
+
+            // Within the next 6 hours
+            let ending_soon = |platform: &DonationPlatformV2, cursor: Option<u32>, limit: u32| {
+                let (campaigns, next) = platform.get_campaigns_ending_soon(21_600_000, cursor, limit);
+                (campaigns.iter().map(|c| c.id).collect::<Vec<_>>(), next)
+            };
+            assert_eq!(ending_soon(&platform, None, 10), (vec![0, 1], None));
+            assert_eq!(ending_soon(&platform, None, 1), (vec![0], Some(0)));
+            assert_eq!(ending_soon(&platform, Some(0), 10), (vec![1], None));
+
+            // Past deadlines are excluded
+            test::set_block_timestamp::<DefaultEnvironment>(10_000_000);
+            assert_eq!(ending_soon(&platform, None, 10), (vec![1], None));
+
+            // The scan is bounded; the cursor resumes past it
+            platform.campaign_count = MAX_CAMPAIGN_SCAN + 1;
+            assert_eq!(ending_soon(&platform, None, 10), (vec![1], Some(MAX_CAMPAIGN_SCAN - 1)));
+        }
+
+        #[ink::test]
+        fn milestones_require_min_goal() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.set_min_goal_for_milestones(50_000_000), Err(Error::NotCampaignOwner));
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.set_min_goal_for_milestones(50_000_000).unwrap();
+
+            let small_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            let large_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                50_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            assert_eq!(
+                platform.add_milestones(small_id, vec![(String::from("M1"), 10000, 30)]),
+                Err(Error::InvalidGoal)
+            );
+            assert_eq!(
+                platform.add_milestones(large_id, vec![(String::from("M1"), 10000, 30)]),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn refund_goes_to_configured_address() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, MIN_DONATION * 2).unwrap();
+            assert_eq!(
+                platform.set_refund_address(campaign_id, AccountId::from([0; 32])),
+                Err(Error::InvalidBeneficiary)
+            );
+            platform.set_refund_address(campaign_id, accounts.eve).unwrap();
+            assert_eq!(platform.get_refund_address(campaign_id, accounts.bob), accounts.eve);
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.cancel_campaign(campaign_id).unwrap();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            let bob_before = test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(platform.claim_refund(campaign_id), Ok(()));
+
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.eve), Ok(MIN_DONATION * 2));
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.bob), Ok(bob_before));
+            assert_eq!(platform.claim_refund(campaign_id), Err(Error::RefundAlreadyClaimed));
+        }
+
+        #[ink::test]
+        fn treasury_earnings_sum_fees() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            assert_eq!(platform.get_treasury_earnings(), 0);
+            platform.process_donation(campaign_id, 1_000_000).unwrap();
+            platform.process_donation(campaign_id, 2_000_000).unwrap();
+            platform.process_donation(campaign_id, 5_000_000).unwrap();
+
+            // 3% of each donation
+            assert_eq!(platform.get_treasury_earnings(), 30_000 + 60_000 + 150_000);
+        }
+
+        #[ink::test]
+        fn donate_and_notify_subscription_reports_status() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // No manager configured: status unknown
+            test::set_value_transferred::<DefaultEnvironment>(MIN_DONATION);
+            let receipt = platform.donate_and_notify_subscription(campaign_id, accounts.bob).unwrap();
+            assert_eq!(receipt.is_subscriber, None);
+            assert_eq!(receipt.amount, MIN_DONATION);
+
+            // Alice subscribes to Bob, but not to Charlie
+            MOCK_SUBSCRIPTIONS.with(|subs| subs.borrow_mut().push((accounts.django, accounts.alice, accounts.bob)));
+            platform.set_subscription_manager(accounts.django).unwrap();
+
+            let receipt = platform.donate_and_notify_subscription(campaign_id, accounts.bob).unwrap();
+            assert_eq!(receipt.is_subscriber, Some(true));
+            let receipt = platform.donate_and_notify_subscription(campaign_id, accounts.charlie).unwrap();
+            assert_eq!(receipt.is_subscriber, Some(false));
+
+            assert_eq!(platform.get_donor_total(campaign_id, accounts.alice), MIN_DONATION * 3);
+
+            // The receipt reports what was accepted, not what was sent
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+            let capped_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.set_soft_cap(capped_id, 5_000_000).unwrap();
+            test::set_value_transferred::<DefaultEnvironment>(12_000_000);
+            let receipt = platform.donate_and_notify_subscription(capped_id, accounts.bob).unwrap();
+            assert_eq!(receipt.amount, 10_000_000);
+        }
+
+        #[ink::test]
+        fn milestones_are_bounded() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // 20 milestones of 5% each
+            let too_many = (0..20u64)
+                .map(|i| (format!("M{}", i), 500, 30 + i))
+                .collect();
+            assert_eq!(platform.add_milestones(campaign_id, too_many), Err(Error::InvalidGoal));
+
+            // A 0% milestone would never pay out
+            assert_eq!(
+                platform.add_milestones(campaign_id, vec![
+                    (String::from("M1"), 0, 30),
+                    (String::from("M2"), 10000, 31),
+                ]),
+                Err(Error::InvalidGoal)
+            );
+
+            // 10 milestones is fine
+            let ten = (0..10u64)
+                .map(|i| (format!("M{}", i), 1000, 30 + i))
+                .collect();
+            assert_eq!(platform.add_milestones(campaign_id, ten), Ok(()));
+        }
+
+        #[ink::test]
+        fn milestone_split_must_not_round_to_zero() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                1_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // 0.01% of 1000 rounds down to zero
+            assert_eq!(
+                platform.add_milestones(campaign_id, vec![
+                    (String::from("M1"), 1, 30),
+                    (String::from("M2"), 9999, 31),
+                ]),
+                Err(Error::InvalidGoal)
+            );
+            assert_eq!(
+                platform.add_milestones(campaign_id, vec![
+                    (String::from("M1"), 10, 30),
+                    (String::from("M2"), 9990, 31),
+                ]),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn milestone_voters_breakdown() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.add_milestones(campaign_id, vec![(String::from("M1"), 10000, 30)]).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 6_000_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, 4_000_000).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.activate_milestone_voting(campaign_id, 0).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            platform.vote_on_milestone(campaign_id, 0, false).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            test::set_block_timestamp::<DefaultEnvironment>(2_000);
+            platform.vote_on_milestone(campaign_id, 0, true).unwrap();
+
+            assert_eq!(
+                platform.get_milestone_voters(campaign_id, 0, 0, 10),
+                vec![(accounts.charlie, 4_000_000, false, 1_000), (accounts.bob, 6_000_000, true, 2_000)]
+            );
+            assert_eq!(
+                platform.get_milestone_voters(campaign_id, 0, 1, 10),
+                vec![(accounts.bob, 6_000_000, true, 2_000)]
+            );
+
+            // The vote events carry the block timestamp too
+            let signature = <MilestoneVoted as ink::env::Event>::SIGNATURE_TOPIC.unwrap();
+            let votes: Vec<(AccountId, Timestamp)> = test::recorded_events()
+                .filter(|event| event.topics.first().map(|t| &t[..]) == Some(&signature[..]))
+                .map(|event| <MilestoneVoted as scale::Decode>::decode(&mut &event.data[..]).unwrap())
+                .map(|vote| (vote.voter, vote.timestamp))
+                .collect();
+            assert_eq!(votes, vec![(accounts.charlie, 1_000), (accounts.bob, 2_000)]);
+            assert_eq!(platform.get_vote_weight(campaign_id, 0, accounts.bob), 6_000_000);
+        }
+
+        #[ink::test]
+        fn failed_refund_transfer_leaves_credit() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            // A contract account with no balance record makes transfers fail
+            let contract_addr = AccountId::from([0x42; 32]);
+            test::set_callee::<DefaultEnvironment>(contract_addr);
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Owner top-ups are fee-free, so no transfer happens before the refund
+            platform.process_owner_top_up(campaign_id, MIN_DONATION * 3).unwrap();
+            platform.cancel_campaign(campaign_id).unwrap();
+
+            assert_eq!(platform.get_refund_credit(accounts.alice), 0);
+            assert_eq!(platform.claim_refund(campaign_id), Ok(()));
+            assert_eq!(platform.get_refund_credit(accounts.alice), MIN_DONATION * 3);
+            assert_eq!(platform.claim_refund(campaign_id), Err(Error::RefundAlreadyClaimed));
+
+            // Retrying still fails and keeps the credit
+            assert_eq!(platform.withdraw_refund_credit(accounts.alice), Err(Error::TransferFailed));
+            assert_eq!(platform.get_refund_credit(accounts.alice), MIN_DONATION * 3);
+
+            // Anyone can pull it once transfers work again
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+            let alice_before = test::get_account_balance::<DefaultEnvironment>(accounts.alice).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.withdraw_refund_credit(accounts.alice), Ok(MIN_DONATION * 3));
+            assert_eq!(
+                test::get_account_balance::<DefaultEnvironment>(accounts.alice),
+                Ok(alice_before + MIN_DONATION * 3)
+            );
+            assert_eq!(platform.withdraw_refund_credit(accounts.alice), Err(Error::NoDonationFound));
+        }
+
+        #[ink::test]
+        fn withdrawn_funds_vest_linearly() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.django,
+            ).unwrap();
+            platform.set_vesting_duration(campaign_id, 1_000_000).unwrap();
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+
+            // Nothing is paid until vesting starts
+            assert_eq!(platform.withdraw_vested(campaign_id), Err(Error::GoalNotReached));
+            platform.withdraw_funds(campaign_id).unwrap();
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.django), Ok(0));
+            assert_eq!(platform.withdraw_vested(campaign_id), Err(Error::InsufficientFunds));
+
+            // A quarter of the way in
+            test::set_block_timestamp::<DefaultEnvironment>(250_000);
+            assert_eq!(platform.withdraw_vested(campaign_id), Ok(()));
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.django), Ok(2_425_000));
+
+            // Halfway: only the newly vested part is paid
+            test::set_block_timestamp::<DefaultEnvironment>(500_000);
+            assert_eq!(platform.get_vesting_status(campaign_id), Some((9_700_000, 4_850_000, 2_425_000)));
+            assert_eq!(platform.withdraw_vested(campaign_id), Ok(()));
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.django), Ok(4_850_000));
+
+            // After the full duration everything is paid, exactly once
+            test::set_block_timestamp::<DefaultEnvironment>(2_000_000);
+            assert_eq!(platform.withdraw_vested(campaign_id), Ok(()));
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.django), Ok(9_700_000));
+            assert_eq!(platform.withdraw_vested(campaign_id), Err(Error::InsufficientFunds));
+        }
+
+        #[ink::test]
+        fn platform_fee_is_bounded() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            assert_eq!(platform.get_platform_fee(), 300);
+            // Increases must go through the timelock
+            assert_eq!(platform.set_platform_fee(301), Err(Error::InvalidFee));
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.set_platform_fee(100), Err(Error::NotCampaignOwner));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(platform.set_platform_fee(100), Ok(()));
+            assert_eq!(platform.get_platform_fee(), 100);
+        }
+
+        #[ink::test]
+        fn withdrawal_uses_fees_actually_paid() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                20_000_000,
+                10_000_000,
+                accounts.django,
+            ).unwrap();
+
+            // 10M at 3%, then 10M at 1%
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+            platform.set_platform_fee(100).unwrap();
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+
+            platform.withdraw_funds(campaign_id).unwrap();
+            assert_eq!(
+                test::get_account_balance::<DefaultEnvironment>(accounts.django),
+                Ok(20_000_000 - 300_000 - 100_000)
+            );
+        }
+
+        #[ink::test]
+        fn active_campaign_count_tracks_transitions() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            let mut ids = Vec::new();
+            for _ in 0..3 {
+                ids.push(platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap());
+            }
+            assert_eq!(platform.get_active_campaign_count(), 3);
+
+            platform.cancel_campaign(ids[0]).unwrap();
+            assert_eq!(platform.get_active_campaign_count(), 2);
+
+            // Reaching the goal makes the campaign Successful
+            platform.process_donation(ids[1], 10_000_000).unwrap();
+            assert_eq!(platform.get_active_campaign_count(), 1);
+
+            // Withdrawing a Successful campaign does not count twice
+            platform.withdraw_funds(ids[1]).unwrap();
+            assert_eq!(platform.get_active_campaign_count(), 1);
+            assert_eq!(platform.get_campaign_count(), 3);
+        }
+
+        #[ink::test]
+        fn donation_event_carries_deadline() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            let deadline = platform.get_campaign(campaign_id).unwrap().deadline;
+
+            platform.process_donation(campaign_id, MIN_DONATION).unwrap();
+
+            let signature = <DonationReceived as ink::env::Event>::SIGNATURE_TOPIC.unwrap();
+            let event = test::recorded_events()
+                .find(|event| event.topics.first().map(|t| &t[..]) == Some(&signature[..]))
+                .unwrap();
+            let decoded = <DonationReceived as scale::Decode>::decode(&mut &event.data[..]).unwrap();
+            assert_eq!(decoded.campaign_id, campaign_id);
+            assert_eq!(decoded.amount, MIN_DONATION);
+            assert_eq!(decoded.deadline, deadline);
+        }
+
+        #[ink::test]
+        fn goal_helpers_work() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            let mut ids = Vec::new();
+            for _ in 0..3 {
+                ids.push(platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap());
+            }
+
+            // Under-funded
+            platform.process_donation(ids[0], 4_000_000).unwrap();
+            assert!(!platform.is_goal_reached(ids[0]));
+            assert_eq!(platform.remaining_to_goal(ids[0]), 6_000_000);
+
+            // Exactly funded
+            platform.process_donation(ids[1], 10_000_000).unwrap();
+            assert!(platform.is_goal_reached(ids[1]));
+            assert_eq!(platform.remaining_to_goal(ids[1]), 0);
+
+            // Over-funded
+            platform.process_donation(ids[2], 9_000_000).unwrap();
+            platform.process_donation(ids[2], 5_000_000).unwrap();
+            assert!(platform.is_goal_reached(ids[2]));
+            assert_eq!(platform.remaining_to_goal(ids[2]), 0);
+
+            // Unknown campaign
+            assert!(!platform.is_goal_reached(99));
+            assert_eq!(platform.remaining_to_goal(99), 0);
+        }
+
+        #[ink::test]
+        fn fees_accrue_until_collected() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            // Run the contract under its own account so balances are observable
+            test::set_callee::<DefaultEnvironment>(accounts.django);
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // Only the donated amount is held, so no fee transfer is needed
+            test::set_account_balance::<DefaultEnvironment>(accounts.django, 10_000_000);
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+            assert_eq!(platform.get_accrued_fees(), 300_000);
+
+            let bob_before = test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap();
+            platform.withdraw_funds(campaign_id).unwrap();
+            assert_eq!(
+                test::get_account_balance::<DefaultEnvironment>(accounts.bob),
+                Ok(bob_before + 9_700_000)
+            );
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.django), Ok(300_000));
+
+            // Only the admin collects, and the fees go to the treasury
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.collect_fees(), Err(Error::NotCampaignOwner));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            let treasury_before = test::get_account_balance::<DefaultEnvironment>(accounts.alice).unwrap();
+            assert_eq!(platform.collect_fees(), Ok(300_000));
+            assert_eq!(
+                test::get_account_balance::<DefaultEnvironment>(accounts.alice),
+                Ok(treasury_before + 300_000)
+            );
+            assert_eq!(platform.get_accrued_fees(), 0);
+            assert_eq!(platform.collect_fees(), Err(Error::InsufficientFunds));
+        }
+
+        #[ink::test]
+        fn final_matching_matches_distribution() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            test::set_value_transferred::<DefaultEnvironment>(6_000_000);
+            platform.fund_matching_pool().unwrap();
+            let round_id = platform.create_matching_round(6_000_000, 1_000).unwrap();
+
+            let mut ids = Vec::new();
+            for _ in 0..3 {
+                ids.push(platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap());
+            }
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(ids[0], MIN_DONATION * 4).unwrap();
+            platform.process_donation(ids[1], MIN_DONATION).unwrap();
+            platform.process_donation(ids[2], MIN_DONATION).unwrap();
+
+            // A cancelled campaign is excluded from distribution but not from the estimate
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.cancel_campaign(ids[2]).unwrap();
+            assert_eq!(platform.get_final_matching(round_id, ids[2]), Some(0));
+            assert_ne!(
+                platform.get_final_matching(round_id, ids[0]),
+                Some(platform.get_estimated_matching(ids[0]))
+            );
+
+            let before: Vec<_> = ids.iter().map(|id| platform.get_final_matching(round_id, *id)).collect();
+
+            test::set_block_timestamp::<DefaultEnvironment>(2_000);
+            platform.calculate_and_distribute_matching(round_id).unwrap();
+
+            for (i, id) in ids.iter().enumerate() {
+                let matching_amount = platform.get_campaign(*id).unwrap().matching_amount;
+                assert_eq!(platform.get_final_matching(round_id, *id), Some(matching_amount));
+                assert_eq!(before[i], Some(matching_amount));
+            }
+            assert_eq!(platform.get_final_matching(round_id + 1, ids[0]), None);
+            assert_eq!(platform.get_final_matching(round_id, 99), None);
+        }
+
+        #[ink::test]
+        fn recompute_campaign_state_fixes_stale_campaigns() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            let cancelled_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.cancel_campaign(cancelled_id).unwrap();
+
+            // Nothing to fix yet
+            assert_eq!(platform.recompute_campaign_state(campaign_id), Ok(CampaignState::Active));
+
+            // Still Active past the deadline with the goal unmet
+            test::set_block_timestamp::<DefaultEnvironment>(20_000_000);
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().state, CampaignState::Active);
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.recompute_campaign_state(campaign_id), Err(Error::NotCampaignOwner));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(platform.recompute_campaign_state(campaign_id), Ok(CampaignState::Failed));
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().state, CampaignState::Failed);
+            assert_eq!(platform.get_active_campaign_count(), 0);
+
+            // Terminal states are left alone
+            test::set_block_timestamp::<DefaultEnvironment>(0);
+            assert_eq!(platform.recompute_campaign_state(cancelled_id), Ok(CampaignState::Failed));
+            assert_eq!(platform.recompute_campaign_state(99), Err(Error::CampaignNotFound));
+
+            // A stale funded campaign is marked successful like a donation would
+            let funded_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.add_milestones(funded_id, vec![
+                (String::from("M1"), 5000, 30),
+                (String::from("M2"), 5000, 60),
+            ]).unwrap();
+            platform.set_auto_activate_first_milestone(funded_id, true).unwrap();
+            let mut campaign = platform.campaigns.get(funded_id).unwrap();
+            campaign.raised = 10_000_000;
+            platform.save_campaign(funded_id, &campaign);
+
+            assert_eq!(platform.recompute_campaign_state(funded_id), Ok(CampaignState::Successful));
+            let milestones = platform.get_milestones(funded_id).unwrap();
+            assert!(milestones[0].voting_active);
+            assert!(!milestones[1].voting_active);
+        }
+
+        #[ink::test]
+        fn milestone_release_waits_for_voting_window() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.add_milestones(campaign_id, vec![(String::from("M1"), 10000, 30)]).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            platform.activate_milestone_voting(campaign_id, 0).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.vote_on_milestone(campaign_id, 0, true).unwrap();
+
+            // A single early vote can't rush the release
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(platform.release_milestone_funds(campaign_id, 0), Err(Error::VotingStillOpen));
+
+            test::set_block_timestamp::<DefaultEnvironment>(1_000 + DEFAULT_MIN_VOTING_DURATION);
+            assert_eq!(platform.release_milestone_funds(campaign_id, 0), Ok(()));
+
+            // Only the admin can change the window
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.set_min_voting_duration(0), Err(Error::NotCampaignOwner));
+        }
+
+        #[ink::test]
+        fn late_donations_do_not_add_vote_weight() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.add_milestones(campaign_id, vec![
+                (String::from("M1"), 5000, 30),
+                (String::from("M2"), 5000, 60),
+            ]).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 6_000_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, 4_000_000).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            platform.activate_milestone_voting(campaign_id, 0).unwrap();
+
+            // Successful campaigns reject donations today, so record late ones directly
+            let mut campaign = platform.campaigns.get(campaign_id).unwrap();
+            for (donor, amount) in [(accounts.charlie, 50_000_000), (accounts.django, MIN_DONATION)] {
+                platform.campaign_donations.insert((campaign_id, campaign.donation_count), &Donation {
+                    donor,
+                    amount,
+                    timestamp: 2_000,
+                    owner_contribution: false,
+                    fee: 0,
+                });
+                let total = platform.donor_totals.get((campaign_id, donor)).unwrap_or(0);
+                platform.donor_totals.insert((campaign_id, donor), &(total + amount));
+                platform.record_late_donation(campaign_id, &campaign, donor, amount, 2_000);
+                campaign.donation_count += 1;
+            }
+            platform.save_campaign(campaign_id, &campaign);
+
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.vote_on_milestone(campaign_id, 0, false).unwrap();
+            assert_eq!(platform.get_vote_weight(campaign_id, 0, accounts.charlie), 4_000_000);
+
+            // A donor who only gave after activation can't vote at all
+            test::set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(platform.vote_on_milestone(campaign_id, 0, true), Err(Error::NoDonationFound));
+        }
+
+        #[ink::test]
+        fn donations_return_sequential_indices() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            for i in 0..3u32 {
+                let amount = MIN_DONATION * Balance::from(i + 1);
+                let (index, _) = platform.process_donation(campaign_id, amount).unwrap();
+                assert_eq!(index, i);
+                assert_eq!(platform.campaign_donations.get((campaign_id, index)).unwrap().amount, amount);
+            }
+
+            let signature = <DonationReceived as ink::env::Event>::SIGNATURE_TOPIC.unwrap();
+            let indices: Vec<u32> = test::recorded_events()
+                .filter(|event| event.topics.first().map(|t| &t[..]) == Some(&signature[..]))
+                .map(|event| <DonationReceived as scale::Decode>::decode(&mut &event.data[..]).unwrap().donation_index)
+                .collect();
+            assert_eq!(indices, vec![0, 1, 2]);
+        }
+
+        #[ink::test]
+        fn campaign_creation_fee_is_enforced() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.set_campaign_creation_fee(MIN_DONATION), Err(Error::NotCampaignOwner));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.set_campaign_creation_fee(MIN_DONATION).unwrap();
+
+            let create = |platform: &mut DonationPlatformV2, paid: bool| {
+                let args = (
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000,
+                    10_000_000,
+                    accounts.bob,
+                );
+                if paid {
+                    platform.create_campaign_paid(args.0, args.1, args.2, args.3, args.4)
+                } else {
+                    platform.create_campaign(args.0, args.1, args.2, args.3, args.4)
+                }
+            };
+
+            // Free creation and underpaying are rejected
+            assert_eq!(create(&mut platform, false), Err(Error::InsufficientFunds));
+            test::set_value_transferred::<DefaultEnvironment>(MIN_DONATION - 1);
+            assert_eq!(create(&mut platform, true), Err(Error::InsufficientFunds));
+            assert_eq!(platform.get_campaign_count(), 0);
+
+            test::set_value_transferred::<DefaultEnvironment>(MIN_DONATION);
+            assert_eq!(create(&mut platform, true), Ok(0));
+            assert_eq!(platform.get_accrued_fees(), MIN_DONATION);
+
+            // Back to free
+            platform.set_campaign_creation_fee(0).unwrap();
+            assert_eq!(create(&mut platform, false), Ok(1));
+        }
+
+        #[ink::test]
+        fn withdrawal_cooldown_spaces_out_payouts() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.django,
+            ).unwrap();
+            platform.set_vesting_duration(campaign_id, 1_000_000).unwrap();
+            platform.set_withdrawal_cooldown(campaign_id, 100_000).unwrap();
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+            platform.withdraw_funds(campaign_id).unwrap();
+
+            test::set_block_timestamp::<DefaultEnvironment>(250_000);
+            assert_eq!(platform.withdraw_vested(campaign_id), Ok(()));
+
+            // A second payout inside the cooldown is rejected
+            test::set_block_timestamp::<DefaultEnvironment>(349_999);
+            assert_eq!(platform.withdraw_vested(campaign_id), Err(Error::WithdrawalCooldown));
+
+            test::set_block_timestamp::<DefaultEnvironment>(350_000);
+            assert_eq!(platform.withdraw_vested(campaign_id), Ok(()));
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(accounts.django), Ok(3_395_000));
+
+            // The cooldown is locked in once the campaign is no longer active
+            assert_eq!(platform.set_withdrawal_cooldown(campaign_id, 0), Err(Error::CampaignNotActive));
+        }
+
+        #[ink::test]
+        fn milestone_campaigns_are_listed() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            for i in 0..5 {
+                let campaign_id = platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap();
+                if i % 2 == 0 {
+                    platform.add_milestones(campaign_id, vec![(String::from("M1"), 10000, 30)]).unwrap();
+                }
+            }
+
+            let milestone_ids = |platform: &DonationPlatformV2, cursor: Option<u32>, limit: u32| {
+                let (campaigns, next) = platform.get_milestone_campaigns(cursor, limit);
+                (campaigns.iter().map(|c| c.id).collect::<Vec<_>>(), next)
+            };
+            assert_eq!(milestone_ids(&platform, None, 10), (vec![0, 2, 4], None));
+            assert_eq!(milestone_ids(&platform, Some(0), 1), (vec![2], Some(2)));
+            assert_eq!(milestone_ids(&platform, Some(4), 10), (vec![], None));
+
+            // The scan is bounded; the cursor resumes past it
+            platform.campaign_count = MAX_CAMPAIGN_SCAN + 1;
+            assert_eq!(milestone_ids(&platform, None, 10), (vec![0, 2, 4], Some(MAX_CAMPAIGN_SCAN - 1)));
+        }
+
+        #[ink::test]
+        fn donor_total_contributions_span_campaigns() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let mut ids = Vec::new();
+            for _ in 0..3 {
+                ids.push(platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap());
+            }
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(ids[0], MIN_DONATION).unwrap();
+            platform.process_donation(ids[1], MIN_DONATION * 2).unwrap();
+            platform.process_donation(ids[1], MIN_DONATION * 3).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(ids[2], MIN_DONATION * 4).unwrap();
+
+            assert_eq!(platform.get_donor_total_contributions(accounts.bob), MIN_DONATION * 6);
+            assert_eq!(platform.get_donor_total_contributions(accounts.charlie), MIN_DONATION * 4);
+            assert_eq!(platform.get_donor_total_contributions(accounts.django), 0);
+
+            // Refunded donations no longer count
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.cancel_campaign(ids[1]).unwrap();
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.claim_refund(ids[1]).unwrap();
+            assert_eq!(platform.get_donor_total_contributions(accounts.bob), MIN_DONATION);
+        }
+
+        #[ink::test]
+        fn donation_receipt_has_net_amount() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                100_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.set_platform_fee(100).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            test::set_block_timestamp::<DefaultEnvironment>(2_000);
+            let (index, _) = platform.process_donation(campaign_id, 2_000_000).unwrap();
+
+            assert_eq!(
+                platform.get_donation_receipt(campaign_id, accounts.bob, index),
+                Some(TaxReceipt {
+                    campaign_id,
+                    campaign_title: String::from("Test Campaign"),
+                    donor: accounts.bob,
+                    donation_index: 1,
+                    amount: 2_000_000,
+                    net_amount: 1_980_000,
+                    timestamp: 2_000,
+                })
+            );
+            let first = platform.get_donation_receipt(campaign_id, accounts.bob, 0).unwrap();
+            assert_eq!(first.net_amount, 9_700_000);
+            assert_eq!(first.timestamp, 1_000);
+
+            assert_eq!(platform.get_donation_receipt(campaign_id, accounts.charlie, 0), None);
+            assert_eq!(platform.get_donation_receipt(campaign_id, accounts.bob, 2), None);
+            assert_eq!(platform.get_donation_receipt(99, accounts.bob, 0), None);
+        }
+
+        #[ink::test]
+        fn round_leaderboard_sorted_by_qf_score() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            // Created before the round, so not enrolled
+            let outside_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            test::set_value_transferred::<DefaultEnvironment>(7_250_000);
+            platform.fund_matching_pool().unwrap();
+            let round_id = platform.create_matching_round(7_250_000, 1_000).unwrap();
+
+            let mut campaign_ids = Vec::new();
+            for _ in 0..3 {
+                campaign_ids.push(platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap());
+            }
+            assert_eq!(platform.get_round(round_id).unwrap().campaign_ids, campaign_ids);
+
+            // Scores: 1_000², (1_000 + 1_000)², 1_500²
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(outside_id, 9_000_000).unwrap();
+            platform.process_donation(campaign_ids[0], 1_000_000).unwrap();
+            platform.process_donation(campaign_ids[1], 1_000_000).unwrap();
+            platform.process_donation(campaign_ids[2], 2_250_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_ids[1], 1_000_000).unwrap();
+
+            assert_eq!(
+                platform.get_round_leaderboard(round_id, 10),
+                vec![
+                    (campaign_ids[1], 4_000_000, 4_000_000),
+                    (campaign_ids[2], 2_250_000, 2_250_000),
+                    (campaign_ids[0], 1_000_000, 1_000_000),
+                ]
+            );
+            assert_eq!(
+                platform.get_round_leaderboard(round_id, 1),
+                vec![(campaign_ids[1], 4_000_000, 4_000_000)]
+            );
+            assert!(platform.get_round_leaderboard(99, 10).is_empty());
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_block_timestamp::<DefaultEnvironment>(2_000);
+            platform.calculate_and_distribute_matching(round_id).unwrap();
+            assert_eq!(
+                platform.get_round_leaderboard(round_id, 10)[0],
+                (campaign_ids[1], 4_000_000, 4_000_000)
+            );
+        }
+
+        #[ink::test]
+        fn frozen_campaign_rejects_donations_but_keeps_voting() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.add_milestones(campaign_id, vec![(String::from("M1"), 10000, 30)]).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 6_000_000).unwrap();
+
+            // Only the owner or admin can freeze
+            assert_eq!(platform.freeze_donations(campaign_id), Err(Error::NotCampaignOwner));
+            assert!(!platform.is_donations_frozen(campaign_id));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.freeze_donations(campaign_id).unwrap();
+            assert!(platform.is_donations_frozen(campaign_id));
+
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            assert_eq!(platform.process_donation(campaign_id, 4_000_000), Err(Error::DonationsFrozen));
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().state, CampaignState::Active);
+
+            // The owner can still close the gap, and voting works as usual
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.process_owner_top_up(campaign_id, 4_000_000).unwrap();
+            test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            platform.activate_milestone_voting(campaign_id, 0).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.vote_on_milestone(campaign_id, 0, true).unwrap();
+            assert_eq!(platform.get_milestones(campaign_id).unwrap()[0].votes_for, 6_000_000);
+        }
+
+        #[ink::test]
+        fn refundable_campaigns_are_listed_per_donor() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let mut campaign_ids = Vec::new();
+            for _ in 0..3 {
+                campaign_ids.push(platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap());
+            }
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_ids[0], 2_000_000).unwrap();
+            platform.process_donation(campaign_ids[1], 3_000_000).unwrap();
+            platform.process_donation(campaign_ids[1], 1_000_000).unwrap();
+            // Reaches the goal, so never refundable
+            platform.process_donation(campaign_ids[2], 10_000_000).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.cancel_campaign(campaign_ids[0]).unwrap();
+            platform.cancel_campaign(campaign_ids[1]).unwrap();
+
+            assert_eq!(
+                platform.get_my_refundable_campaigns(accounts.bob, None, 10),
+                (vec![(campaign_ids[0], 2_000_000), (campaign_ids[1], 4_000_000)], None)
+            );
+            assert_eq!(
+                platform.get_my_refundable_campaigns(accounts.bob, None, 1),
+                (vec![(campaign_ids[0], 2_000_000)], Some(campaign_ids[0]))
+            );
+            assert_eq!(
+                platform.get_my_refundable_campaigns(accounts.bob, Some(campaign_ids[0]), 10),
+                (vec![(campaign_ids[1], 4_000_000)], None)
+            );
+            assert_eq!(platform.get_my_refundable_campaigns(accounts.charlie, None, 10), (vec![], None));
+
+            // Claimed refunds drop out of the list
+            platform.refund_claimed.insert((campaign_ids[0], accounts.bob), &true);
+            assert_eq!(
+                platform.get_my_refundable_campaigns(accounts.bob, None, 10),
+                (vec![(campaign_ids[1], 4_000_000)], None)
+            );
+
+            // The scan is bounded; the cursor resumes past it
+            platform.campaign_count = MAX_CAMPAIGN_SCAN + 1;
+            assert_eq!(
+                platform.get_my_refundable_campaigns(accounts.bob, None, 10),
+                (vec![(campaign_ids[1], 4_000_000)], Some(MAX_CAMPAIGN_SCAN - 1))
+            );
+            assert_eq!(
+                platform.get_my_refundable_campaigns(accounts.bob, Some(MAX_CAMPAIGN_SCAN - 1), 10),
+                (vec![], None)
+            );
+        }
+
+        #[ink::test]
+        fn emergency_withdraw_requires_deprecation_and_free_balance() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 50_000_000);
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                100_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_value_transferred::<DefaultEnvironment>(5_000_000);
+            platform.fund_matching_pool().unwrap();
+
+            // Gross donations (refundable in full), accrued fees and the matching pool are owed
+            assert_eq!(platform.get_liabilities(), 10_000_000 + 300_000 + 5_000_000);
+            let free = platform.get_free_balance();
+            assert_eq!(free, 50_000_000 - 15_300_000);
+
+            assert_eq!(platform.emergency_withdraw(1_000_000, accounts.eve), Err(Error::NotDeprecated));
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.set_deprecated(true), Err(Error::NotCampaignOwner));
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.set_deprecated(true).unwrap();
+            assert!(platform.is_deprecated());
+
+            assert_eq!(platform.emergency_withdraw(free + 1, accounts.eve), Err(Error::InsufficientFunds));
+            assert_eq!(platform.emergency_withdraw(free, accounts.eve), Ok(()));
+            assert_eq!(platform.get_free_balance(), 0);
+            assert_eq!(platform.emergency_withdraw(1, accounts.eve), Err(Error::InsufficientFunds));
+
+            let signature = <EmergencyWithdrawal as ink::env::Event>::SIGNATURE_TOPIC.unwrap();
+            let emitted = test::recorded_events()
+                .filter(|event| event.topics.first().map(|t| &t[..]) == Some(&signature[..]))
+                .count();
+            assert_eq!(emitted, 1);
+        }
+
+        #[ink::test]
+        fn soft_cap_succeeds_early_and_hard_cap_closes() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            assert_eq!(platform.set_soft_cap(campaign_id, 10_000_001), Err(Error::InvalidGoal));
+            platform.set_soft_cap(campaign_id, 4_000_000).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.set_soft_cap(campaign_id, 0), Err(Error::NotCampaignOwner));
+            platform.process_donation(campaign_id, 3_000_000).unwrap();
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().state, CampaignState::Active);
 
-    /// Emitted when funds are withdrawn from a campaign.
-    #[ink(event)]
-    pub struct FundsWithdrawn {
-        /// The ID of the campaign from which funds were withdrawn.
-        #[ink(topic)]
-        campaign_id: u32,
-        /// The account that received the funds.
-        #[ink(topic)]
-        beneficiary: AccountId,
-        /// The amount of funds withdrawn.
-        amount: Balance,
-    }
+            // The threshold is fixed once donations arrive
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(platform.set_soft_cap(campaign_id, 1), Err(Error::CampaignHasDonations));
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
 
-    /// Emitted when a campaign is cancelled.
-    #[ink(event)]
-    pub struct CampaignCancelled {
-        /// The ID of the cancelled campaign.
-        #[ink(topic)]
-        campaign_id: u32,
-        /// The account that cancelled the campaign.
-        #[ink(topic)]
-        cancelled_by: AccountId,
-    }
+            // Soft cap reached: successful, but still accepting
+            platform.process_donation(campaign_id, 2_000_000).unwrap();
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().state, CampaignState::Successful);
+            platform.process_donation(campaign_id, 4_000_000).unwrap();
 
-    /// Emitted when a donor claims a refund for a failed campaign.
-    #[ink(event)]
-    pub struct RefundClaimed {
-        /// The ID of the campaign.
-        #[ink(topic)]
-        campaign_id: u32,
-        /// The donor who claimed the refund.
-        #[ink(topic)]
-        donor: AccountId,
-        /// The amount refunded.
-        amount: Balance,
-    }
+            // Hard cap reached: closed
+            platform.process_donation(campaign_id, 1_000_000).unwrap();
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().raised, 10_000_000);
+            assert_eq!(platform.process_donation(campaign_id, 1_000_000), Err(Error::GoalReached));
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().state, CampaignState::Successful);
+        }
 
-    /// Emitted when NFT minting fails after a donation.
-    #[ink(event)]
-    pub struct NftMintingFailed {
-        /// The ID of the campaign.
-        #[ink(topic)]
-        campaign_id: u32,
-        /// The donor who made the donation.
-        #[ink(topic)]
-        donor: AccountId,
-        /// Error code from NFT minting.
-        error_code: u8,
-    }
+        #[ink::test]
+        fn missing_soft_cap_is_refundable() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
 
-    /// Emitted when a donation NFT receipt is minted.
-    #[ink(event)]
-    pub struct NftReceiptMinted {
-        /// The ID of the campaign.
-        #[ink(topic)]
-        campaign_id: u32,
-        /// The donor who received the NFT.
-        #[ink(topic)]
-        donor: AccountId,
-        /// The NFT token ID.
-        nft_token_id: u128,
-        /// The donation amount.
-        amount: Balance,
-    }
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.set_soft_cap(campaign_id, 4_000_000).unwrap();
 
-    /// Emitted when funds are added to the matching pool.
-    #[ink(event)]
-    pub struct MatchingPoolFunded {
-        /// The account that funded the pool.
-        #[ink(topic)]
-        funder: AccountId,
-        /// The amount added to the pool.
-        amount: Balance,
-        /// The new total pool balance.
-        total_pool: Balance,
-    }
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 3_000_000).unwrap();
 
-    /// Emitted when a new matching round is created.
-    #[ink(event)]
-    pub struct MatchingRoundCreated {
-        /// The ID of the new round.
-        #[ink(topic)]
-        round_id: u32,
-        /// The pool amount allocated to this round.
-        pool_amount: Balance,
-        /// When the round ends.
-        end_time: Timestamp,
-    }
+            test::set_block_timestamp::<DefaultEnvironment>(10_000_001);
+            assert_eq!(platform.process_donation(campaign_id, 1_000_000), Err(Error::DeadlinePassed));
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().state, CampaignState::Failed);
+            assert!(platform.can_claim_refund(campaign_id, accounts.bob));
 
-    /// Emitted when matching funds are distributed to a campaign.
-    #[ink(event)]
-    pub struct MatchingDistributed {
-        /// The campaign that received matching.
-        #[ink(topic)]
-        campaign_id: u32,
-        /// The matching amount distributed.
-        matching_amount: Balance,
-        /// The round ID.
-        round_id: u32,
-    }
+            // Past its soft cap, a campaign stops filling at the deadline without failing
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_block_timestamp::<DefaultEnvironment>(0);
+            let funded_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.set_soft_cap(funded_id, 4_000_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(funded_id, 5_000_000).unwrap();
 
-    /// Emitted when milestones are added to a campaign.
-    #[ink(event)]
-    pub struct MilestonesAdded {
-        /// The campaign ID.
-        #[ink(topic)]
-        campaign_id: u32,
-        /// Number of milestones added.
-        milestone_count: u32,
-    }
+            test::set_block_timestamp::<DefaultEnvironment>(10_000_001);
+            assert_eq!(platform.process_donation(funded_id, 1_000_000), Err(Error::DeadlinePassed));
+            assert_eq!(platform.get_campaign(funded_id).unwrap().state, CampaignState::Successful);
+            assert!(!platform.can_claim_refund(funded_id, accounts.bob));
+        }
 
-    /// Emitted when voting is activated for a milestone.
-    #[ink(event)]
-    pub struct MilestoneVotingActivated {
-        /// The campaign ID.
-        #[ink(topic)]
-        campaign_id: u32,
-        /// The milestone index.
-        milestone_index: u32,
-    }
+        #[ink::test]
+        fn quote_donation_splits_amount() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            assert_eq!(platform.quote_donation(accounts.alice, 10_000_000), (300_000, 9_700_000));
+            // 3% of 1_234_567 is 37_037.01, rounded down
+            assert_eq!(platform.quote_donation(accounts.alice, 1_234_567), (37_037, 1_197_530));
+            assert_eq!(platform.quote_donation(accounts.alice, 33), (0, 33));
+            assert_eq!(platform.quote_donation(accounts.alice, 0), (0, 0));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.set_platform_fee(125).unwrap();
+            for amount in [1, 9_999, 10_001, 1_234_567, 99_999_999, Balance::MAX] {
+                let (fee, net) = platform.quote_donation(accounts.alice, amount);
+                assert_eq!(fee + net, amount);
+            }
+            assert_eq!(platform.quote_donation(accounts.alice, 1_000_000), (12_500, 987_500));
+
+            // Matches what a donation actually pays
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                100_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.process_donation(campaign_id, 1_234_567).unwrap();
+            assert_eq!(platform.get_accrued_fees(), platform.quote_donation(accounts.alice, 1_234_567).0);
+
+            // A discounted donor is quoted their own rate
+            platform.set_fee_discount_tiers(vec![(1_000_000, 50)]).unwrap();
+            assert_eq!(platform.quote_donation(accounts.alice, 1_000_000), (5_000, 995_000));
+            assert_eq!(platform.quote_donation(accounts.bob, 1_000_000), (12_500, 987_500));
+            let fees_before = platform.get_accrued_fees();
+            platform.process_donation(campaign_id, 1_000_000).unwrap();
+            assert_eq!(platform.get_accrued_fees() - fees_before, 5_000);
+        }
+
+        #[ink::test]
+        fn merge_campaigns_combines_donations() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let mut campaign_ids = Vec::new();
+            for _ in 0..2 {
+                campaign_ids.push(platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    100_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap());
+            }
+            let (source_id, target_id) = (campaign_ids[0], campaign_ids[1]);
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(source_id, 2_000_000).unwrap();
+            platform.process_donation(target_id, 3_000_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(source_id, 4_000_000).unwrap();
+
+            assert_eq!(platform.merge_campaigns(source_id, target_id), Err(Error::NotCampaignOwner));
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(platform.merge_campaigns(source_id, source_id), Err(Error::BatchOperationFailed));
+            platform.merge_campaigns(source_id, target_id).unwrap();
+
+            let target = platform.get_campaign(target_id).unwrap();
+            assert_eq!(target.raised, 9_000_000);
+            assert_eq!(target.donation_count, 3);
+            assert_eq!(target.unique_donor_count, 2);
+            assert_eq!(target.fees_paid, 270_000);
+
+            let source = platform.get_campaign(source_id).unwrap();
+            assert_eq!(source.state, CampaignState::Merged);
+            assert_eq!(source.raised, 0);
+            assert_eq!(platform.get_merged_into(source_id), Some(target_id));
+            assert_eq!(platform.get_active_campaign_count(), 1);
+            assert_eq!(platform.merge_campaigns(source_id, target_id), Err(Error::CampaignNotActive));
+
+            // Donor records live on the target, and source queries follow the merge
+            assert_eq!(platform.get_donor_total(target_id, accounts.bob), 5_000_000);
+            assert_eq!(platform.get_donor_total(source_id, accounts.charlie), 4_000_000);
+
+            // Refunds are claimed from the target
+            platform.cancel_campaign(target_id).unwrap();
+            assert!(platform.can_claim_refund(source_id, accounts.charlie));
+            assert!(platform.can_claim_refund(target_id, accounts.bob));
+            assert_eq!(
+                platform.get_my_refundable_campaigns(accounts.charlie, None, 10),
+                (vec![(target_id, 4_000_000)], None)
+            );
+        }
+
+        #[ink::test]
+        fn success_hook_is_called_once_funded() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+            let hook = AccountId::from([0x42; 32]);
+
+            let campaign_id = platform.create_campaign_with_hook(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+                Some(hook),
+            ).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 6_000_000).unwrap();
+            assert!(MOCK_HOOK_CALLS.with(|calls| calls.borrow().is_empty()));
+
+            platform.process_donation(campaign_id, 4_000_000).unwrap();
+            assert_eq!(MOCK_HOOK_CALLS.with(|calls| calls.borrow().clone()), vec![(hook, campaign_id)]);
+        }
+
+        #[ink::test]
+        fn failed_success_hook_emits_event() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+            let hook = AccountId::from([0x43; 32]);
+            MOCK_FAILING_HOOKS.with(|hooks| hooks.borrow_mut().push(hook));
+
+            let campaign_id = platform.create_campaign_with_hook(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+                Some(hook),
+            ).unwrap();
+
+            // The donation goes through regardless
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().state, CampaignState::Successful);
+
+            let signature = <SuccessHookFailed as ink::env::Event>::SIGNATURE_TOPIC.unwrap();
+            let event = test::recorded_events()
+                .find(|event| event.topics.first().map(|t| &t[..]) == Some(&signature[..]))
+                .unwrap();
+            let decoded = <SuccessHookFailed as scale::Decode>::decode(&mut &event.data[..]).unwrap();
+            assert_eq!(decoded.hook, hook);
+            assert!(MOCK_HOOK_CALLS.with(|calls| calls.borrow().is_empty()));
+        }
+
+        #[ink::test]
+        fn milestone_dashboard_shows_viewer_votes() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.add_milestones(campaign_id, vec![
+                (String::from("M1"), 4000, 30),
+                (String::from("M2"), 3000, 60),
+                (String::from("M3"), 3000, 90),
+            ]).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 6_000_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, 4_000_000).unwrap();
+
+            // M1 voted and released, M2 open with Bob's vote, M3 pending
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.activate_milestone_voting(campaign_id, 0).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.vote_on_milestone(campaign_id, 0, true).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_block_timestamp::<DefaultEnvironment>(DEFAULT_MIN_VOTING_DURATION);
+            platform.release_milestone_funds(campaign_id, 0).unwrap();
+            platform.activate_milestone_voting(campaign_id, 1).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.vote_on_milestone(campaign_id, 1, false).unwrap();
+
+            let dashboard = platform.get_milestone_dashboard(campaign_id, accounts.bob).unwrap();
+            assert_eq!(dashboard.campaign.id, campaign_id);
+            assert_eq!(dashboard.viewer_voting_power, 6_000_000);
+            let statuses: Vec<MilestoneStatus> = dashboard.milestones.iter().map(|m| m.status).collect();
+            assert_eq!(statuses, vec![MilestoneStatus::Released, MilestoneStatus::Voting, MilestoneStatus::Pending]);
+            let voted: Vec<bool> = dashboard.milestones.iter().map(|m| m.viewer_voted).collect();
+            assert_eq!(voted, vec![true, true, false]);
+            let weights: Vec<Balance> = dashboard.milestones.iter().map(|m| m.viewer_vote_weight).collect();
+            assert_eq!(weights, vec![6_000_000, 6_000_000, 0]);
+
+            let dashboard = platform.get_milestone_dashboard(campaign_id, accounts.charlie).unwrap();
+            assert_eq!(dashboard.viewer_voting_power, 4_000_000);
+            assert!(dashboard.milestones.iter().all(|m| !m.viewer_voted));
+
+            // A donation after M2 opened doesn't add to the power shown for it
+            let campaign = platform.campaigns.get(campaign_id).unwrap();
+            let late_time = DEFAULT_MIN_VOTING_DURATION + 1;
+            platform.donor_totals.insert((campaign_id, accounts.charlie), &5_000_000);
+            platform.record_late_donation(campaign_id, &campaign, accounts.charlie, 1_000_000, late_time);
+            let dashboard = platform.get_milestone_dashboard(campaign_id, accounts.charlie).unwrap();
+            assert_eq!(dashboard.viewer_voting_power, 4_000_000);
+
+            // Past M3's deadline without a vote it shows as overdue
+            test::set_block_timestamp::<DefaultEnvironment>(90 * 86_400_000 + 1);
+            let dashboard = platform.get_milestone_dashboard(campaign_id, accounts.bob).unwrap();
+            assert_eq!(dashboard.milestones[2].status, MilestoneStatus::Overdue);
+            assert!(platform.get_milestone_dashboard(99, accounts.bob).is_none());
+        }
+
+        #[ink::test]
+        fn matching_distribution_conserves_pool() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            test::set_value_transferred::<DefaultEnvironment>(2_000_001);
+            platform.fund_matching_pool().unwrap();
+            let round_id = platform.create_matching_round(1_000_001, 1_000).unwrap();
+
+            let mut campaign_ids = Vec::new();
+            for _ in 0..3 {
+                campaign_ids.push(platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap());
+            }
+
+            // Scores 1_000², 1_000² and 2_000²: shares of 1/6, 1/6 and 2/3 don't divide evenly
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_ids[0], 1_000_000).unwrap();
+            platform.process_donation(campaign_ids[1], 1_000_000).unwrap();
+            platform.process_donation(campaign_ids[2], 4_000_000).unwrap();
+
+            let expected = [166_666, 166_666, 666_669];
+            for (campaign_id, share) in campaign_ids.iter().zip(expected) {
+                assert_eq!(platform.get_final_matching(round_id, *campaign_id), Some(share));
+            }
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_block_timestamp::<DefaultEnvironment>(2_000);
+            platform.calculate_and_distribute_matching(round_id).unwrap();
+
+            let distributed: Balance = campaign_ids
+                .iter()
+                .map(|id| platform.get_campaign(*id).unwrap().matching_amount)
+                .sum();
+            assert_eq!(distributed, 1_000_001);
+            assert_eq!(platform.get_campaign(campaign_ids[2]).unwrap().matching_amount, 666_669);
+
+            // A round nobody qualifies for gives its pool back
+            let empty_round = platform.create_matching_round(1_000_000, 1_000).unwrap();
+            assert_eq!(platform.get_matching_pool_balance(), 0);
+            test::set_block_timestamp::<DefaultEnvironment>(4_000);
+            platform.calculate_and_distribute_matching(empty_round).unwrap();
+            assert_eq!(platform.get_matching_pool_balance(), 1_000_000);
+        }
+
+        #[ink::test]
+        fn admin_actions_wait_for_timelock() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
 
-    /// Emitted when a donor votes on a milestone.
-    #[ink(event)]
-    pub struct MilestoneVoted {
-        /// The campaign ID.
-        #[ink(topic)]
-        campaign_id: u32,
-        /// The milestone index.
-        milestone_index: u32,
-        /// The voter.
-        #[ink(topic)]
-        voter: AccountId,
-        /// Whether they approved.
-        approve: bool,
-        /// The vote weight (donation amount).
-        weight: Balance,
-    }
+            assert_eq!(
+                platform.queue_admin_action(AdminAction::SetFee(500), MIN_ADMIN_ACTION_DELAY - 1),
+                Err(Error::InvalidDeadline)
+            );
+            assert_eq!(
+                platform.queue_admin_action(AdminAction::SetFee(MAX_FEE_BPS + 1), MIN_ADMIN_ACTION_DELAY),
+                Err(Error::InvalidFee)
+            );
+            let action_id = platform.queue_admin_action(AdminAction::SetFee(500), MIN_ADMIN_ACTION_DELAY).unwrap();
+            assert_eq!(
+                platform.get_queued_admin_action(action_id),
+                Some((AdminAction::SetFee(500), MIN_ADMIN_ACTION_DELAY))
+            );
 
-    /// Emitted when milestone funds are released.
-    #[ink(event)]
-    pub struct MilestoneFundsReleased {
-        /// The campaign ID.
-        #[ink(topic)]
-        campaign_id: u32,
-        /// The milestone index.
-        milestone_index: u32,
-        /// The amount released.
-        amount: Balance,
-        /// The beneficiary who received funds.
-        #[ink(topic)]
-        beneficiary: AccountId,
-    }
+            // Too early
+            test::set_block_timestamp::<DefaultEnvironment>(MIN_ADMIN_ACTION_DELAY - 1);
+            assert_eq!(platform.execute_admin_action(action_id), Err(Error::TimelockNotExpired));
+            assert_eq!(platform.get_platform_fee(), DEFAULT_FEE_BPS);
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        use ink::env::{test, DefaultEnvironment};
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.execute_admin_action(action_id), Err(Error::NotCampaignOwner));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_block_timestamp::<DefaultEnvironment>(MIN_ADMIN_ACTION_DELAY);
+            assert_eq!(platform.execute_admin_action(action_id), Ok(()));
+            assert_eq!(platform.get_platform_fee(), 500);
+            assert_eq!(platform.execute_admin_action(action_id), Err(Error::AdminActionNotFound));
+        }
 
         #[ink::test]
-        fn create_campaign_works() {
+        fn timelocked_pause_and_treasury() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
-            let result = platform.create_campaign(
+            let campaign_id = platform.create_campaign(
                 String::from("Test Campaign"),
                 String::from("Description"),
-                1000,
                 10_000_000,
+                1_000_000_000,
                 accounts.bob,
-            );
+            ).unwrap();
 
-            assert!(result.is_ok());
-            assert_eq!(platform.get_campaign_count(), 1);
+            let pause_id = platform.queue_admin_action(AdminAction::SetPaused(true), MIN_ADMIN_ACTION_DELAY).unwrap();
+            let treasury_id = platform
+                .queue_admin_action(AdminAction::SetTreasury(accounts.eve), MIN_ADMIN_ACTION_DELAY)
+                .unwrap();
+            let cancelled_id = platform.queue_admin_action(AdminAction::SetFee(0), MIN_ADMIN_ACTION_DELAY).unwrap();
+            platform.cancel_admin_action(cancelled_id).unwrap();
+
+            test::set_block_timestamp::<DefaultEnvironment>(MIN_ADMIN_ACTION_DELAY);
+            platform.execute_admin_action(pause_id).unwrap();
+            platform.execute_admin_action(treasury_id).unwrap();
+            assert_eq!(platform.execute_admin_action(cancelled_id), Err(Error::AdminActionNotFound));
+
+            assert!(platform.is_paused());
+            assert_eq!(platform.get_treasury(), accounts.eve);
+            assert_eq!(platform.process_donation(campaign_id, MIN_DONATION), Err(Error::ContractPaused));
         }
 
         #[ink::test]
-        fn batch_create_campaigns_works() {
+        fn repeat_donors_get_fee_discount() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
-            let campaigns_data = vec![
-                (String::from("Campaign 1"), String::from("Desc 1"), 1000, 10_000_000, accounts.bob),
-                (String::from("Campaign 2"), String::from("Desc 2"), 2000, 10_000_000, accounts.bob),
-            ];
+            assert_eq!(
+                platform.set_fee_discount_tiers(vec![(20_000_000, 200), (10_000_000, 100)]),
+                Err(Error::InvalidFee)
+            );
+            platform.set_fee_discount_tiers(vec![(10_000_000, 200), (50_000_000, 100)]).unwrap();
 
-            let result = platform.create_campaigns_batch(campaigns_data);
-            assert!(result.is_ok());
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                100_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
 
-            let batch_result = result.unwrap();
-            assert_eq!(batch_result.successful, 2);
-            assert_eq!(batch_result.failed, 0);
-            assert_eq!(platform.get_campaign_count(), 2);
+            // First donation at the full 3%, then Bob passes the first threshold
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.get_donor_fee_bps(accounts.bob), DEFAULT_FEE_BPS);
+            platform.process_donation(campaign_id, 10_000_000).unwrap();
+            assert_eq!(platform.get_donor_fee_bps(accounts.bob), 200);
+
+            let (index, _) = platform.process_donation(campaign_id, 5_000_000).unwrap();
+            assert_eq!(platform.get_donation_receipt(campaign_id, accounts.bob, index).unwrap().net_amount, 4_900_000);
+
+            // Charlie has no history and pays the full fee
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, 5_000_000).unwrap();
+
+            // Withdrawal accounting uses the fees actually charged
+            let campaign = platform.get_campaign(campaign_id).unwrap();
+            assert_eq!(campaign.fees_paid, 300_000 + 100_000 + 150_000);
+            assert_eq!(platform.get_accrued_fees(), campaign.fees_paid);
+
+            // Discounts never raise the fee above the platform fee
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.set_platform_fee(150).unwrap();
+            assert_eq!(platform.get_donor_fee_bps(accounts.bob), 150);
         }
 
         #[ink::test]
-        fn version_tracking_works() {
-            let platform = DonationPlatformV2::new();
-            assert_eq!(platform.get_version(), 2);
+        fn events_summary_matches_campaigns() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            for _ in 0..4 {
+                platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap();
+            }
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(1, 3_000_000).unwrap();
+            platform.process_donation(2, 10_000_000).unwrap();
+            platform.process_donation(2, 1_000_000).unwrap_err();
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.cancel_campaign(3).unwrap();
+
+            let snapshot = platform.get_events_summary(1, 10);
+            assert_eq!(snapshot.campaign_count, 4);
+            assert_eq!(snapshot.campaigns.len(), 3);
+            for entry in &snapshot.campaigns {
+                let campaign = platform.get_campaign(entry.id).unwrap();
+                assert_eq!(entry.state, campaign.state);
+                assert_eq!(entry.raised, campaign.raised);
+                assert_eq!(entry.donation_count, campaign.donation_count);
+            }
+            let states: Vec<CampaignState> = snapshot.campaigns.iter().map(|c| c.state).collect();
+            assert_eq!(states, vec![CampaignState::Active, CampaignState::Successful, CampaignState::Failed]);
+
+            assert!(platform.get_events_summary(2, 2).campaigns.is_empty());
+            assert!(platform.get_events_summary(7, 10).campaigns.is_empty());
         }
 
         #[ink::test]
-        fn invalid_campaign_title_fails() {
+        fn donation_to_missing_campaign_takes_no_fee() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
-            // Empty title
-            let result = platform.create_campaign(
-                String::from(""),
+            assert_eq!(platform.process_donation(9999, 10_000_000), Err(Error::CampaignNotFound));
+            assert_eq!(platform.get_accrued_fees(), 0);
+            assert_eq!(platform.get_treasury_earnings(), 0);
+
+            // Same for a campaign that rejects the donation
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
                 String::from("Description"),
-                1000,
+                10_000_000,
                 10_000_000,
                 accounts.bob,
-            );
-            assert_eq!(result, Err(Error::InvalidTitle));
+            ).unwrap();
+            platform.cancel_campaign(campaign_id).unwrap();
+            assert_eq!(platform.process_donation(campaign_id, 10_000_000), Err(Error::CampaignNotActive));
+            assert_eq!(platform.get_accrued_fees(), 0);
+        }
 
-            // Title too long (>100 chars)
-            let long_title = "a".repeat(101);
-            let result = platform.create_campaign(
-                long_title,
+        #[ink::test]
+        fn audit_campaign_detects_drift() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
                 String::from("Description"),
-                1000,
+                100_000_000,
                 10_000_000,
                 accounts.bob,
+            ).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            for amount in [1_000_000, 2_000_000, 3_000_000] {
+                platform.process_donation(campaign_id, amount).unwrap();
+            }
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.process_owner_top_up(campaign_id, 4_000_000).unwrap();
+
+            assert_eq!(platform.audit_campaign(campaign_id, 0, 10), (10_000_000, 10_000_000, true));
+
+            // Pages add up to the total
+            assert_eq!(platform.audit_campaign(campaign_id, 0, 2), (10_000_000, 3_000_000, false));
+            assert_eq!(platform.audit_campaign(campaign_id, 2, 2), (10_000_000, 7_000_000, false));
+
+            // Corrupted accounting shows up as a mismatch
+            let mut campaign = platform.campaigns.get(campaign_id).unwrap();
+            campaign.raised += 1;
+            platform.save_campaign(campaign_id, &campaign);
+            assert_eq!(platform.audit_campaign(campaign_id, 0, 10), (10_000_001, 10_000_000, false));
+
+            assert_eq!(platform.audit_campaign(99, 0, 10), (0, 0, false));
+        }
+
+        #[ink::test]
+        fn funding_exponent_linear_vs_quadratic() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+
+            // Same donations under a given exponent: [1M], [1M, 1M], [2.25M]
+            let shares = |funding_exponent: u32| {
+                test::set_caller::<DefaultEnvironment>(accounts.alice);
+                let mut platform = DonationPlatformV2::new();
+                use ink::codegen::Env;
+                let contract_addr = platform.env().account_id();
+                test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+                test::set_block_timestamp::<DefaultEnvironment>(0);
+                test::set_value_transferred::<DefaultEnvironment>(7_250_000);
+                platform.fund_matching_pool().unwrap();
+                let round_id = platform.create_scheduled_matching_round(7_250_000, 1, 1_000).unwrap();
+                assert_eq!(platform.get_round(round_id).unwrap().funding_exponent, 2_000);
+                platform.set_round_funding_exponent(round_id, funding_exponent).unwrap();
+                test::set_block_timestamp::<DefaultEnvironment>(1);
+
+                let mut campaign_ids = Vec::new();
+                for _ in 0..3 {
+                    campaign_ids.push(platform.create_campaign(
+                        String::from("Test Campaign"),
+                        String::from("Description"),
+                        10_000_000_000,
+                        10_000_000,
+                        accounts.bob,
+                    ).unwrap());
+                }
+
+                test::set_caller::<DefaultEnvironment>(accounts.bob);
+                platform.process_donation(campaign_ids[0], 1_000_000).unwrap();
+                platform.process_donation(campaign_ids[1], 1_000_000).unwrap();
+                platform.process_donation(campaign_ids[2], 2_250_000).unwrap();
+                test::set_caller::<DefaultEnvironment>(accounts.charlie);
+                platform.process_donation(campaign_ids[1], 1_000_000).unwrap();
+
+                platform.get_round_leaderboard(round_id, 10)
+            };
+
+            // Quadratic: two donors beat one larger donor
+            assert_eq!(
+                shares(2_000),
+                vec![
+                    (1, 4_000_000, 4_000_000),
+                    (2, 2_250_000, 2_250_000),
+                    (0, 1_000_000, 1_000_000),
+                ]
             );
-            assert_eq!(result, Err(Error::InvalidTitle));
+
+            // Linear: matching follows the amount raised, remainder to the top campaign
+            assert_eq!(
+                shares(1_000),
+                vec![
+                    (2, 2_250_000, 3_107_144),
+                    (1, 2_000_000, 2_761_904),
+                    (0, 1_000_000, 1_380_952),
+                ]
+            );
+
+            // In between: (2 · 1M^(2/3))^1.5 = 2^1.5 · 1M, approximated in fixed point
+            let mid = shares(1_500);
+            let score = mid.iter().find(|(id, _, _)| *id == 1).unwrap().1;
+            assert!(score.abs_diff(2_828_427) < 1_000);
+            let single = mid.iter().find(|(id, _, _)| *id == 0).unwrap().1;
+            assert!(single.abs_diff(1_000_000) < 1_000);
         }
 
         #[ink::test]
-        fn invalid_goal_fails() {
+        fn exp2_fixed_saturates_instead_of_wrapping() {
+            assert_eq!(DonationPlatformV2::exp2_fixed(64 << 32), 1 << 64);
+            assert_eq!(DonationPlatformV2::exp2_fixed(127 << 32), 1 << 127);
+
+            // Large shifts never wrap to a small value
+            for integer_part in 63..128u128 {
+                let y = (integer_part << 32) | 0xFFFF_FFFF;
+                assert!(DonationPlatformV2::exp2_fixed(y) >= 1 << integer_part);
+            }
+            assert_eq!(DonationPlatformV2::exp2_fixed(128 << 32), u128::MAX);
+        }
+
+        #[ink::test]
+        fn set_round_funding_exponent_validates() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
-            // Zero goal
-            let result = platform.create_campaign(
-                String::from("Test"),
+            let round_id = platform.create_scheduled_matching_round(0, 1_000, 1_000).unwrap();
+            assert_eq!(platform.set_round_funding_exponent(round_id, 999), Err(Error::InvalidFundingExponent));
+            assert_eq!(platform.set_round_funding_exponent(round_id, 2_001), Err(Error::InvalidFundingExponent));
+            assert_eq!(platform.set_round_funding_exponent(99, 1_500), Err(Error::NoActiveRound));
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.set_round_funding_exponent(round_id, 1_500), Err(Error::NotCampaignOwner));
+
+            // The rules are fixed once the round opens
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            assert_eq!(platform.set_round_funding_exponent(round_id, 1_500), Err(Error::RoundAlreadyStarted));
+            assert_eq!(platform.set_round_min_qf_donation(round_id, 0), Err(Error::RoundAlreadyStarted));
+            assert_eq!(platform.set_round_donor_breadth(round_id, 0), Err(Error::RoundAlreadyStarted));
+            assert_eq!(platform.set_round_min_unique_donors(round_id, 0), Err(Error::RoundAlreadyStarted));
+        }
+
+        #[ink::test]
+        fn dust_donations_earn_no_qf_credit() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            let round_id = platform.create_scheduled_matching_round(0, 1, 1_000_000).unwrap();
+            platform.set_round_min_qf_donation(round_id, 4_000_000).unwrap();
+            test::set_block_timestamp::<DefaultEnvironment>(1);
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
                 String::from("Description"),
-                0,
+                10_000_000_000,
                 10_000_000,
                 accounts.bob,
+            ).unwrap();
+
+            // A flood of minimum donations still raises funds but not the score
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            for _ in 0..20 {
+                platform.process_donation(campaign_id, 1_000_000).unwrap();
+            }
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().raised, 20_000_000);
+            assert_eq!(platform.calculate_qf_score(campaign_id), 0);
+
+            // Donations at the threshold count: (2_000 + 3_000)²
+            platform.process_donation(campaign_id, 4_000_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.django);
+            platform.process_donation(campaign_id, 9_000_000).unwrap();
+            assert_eq!(platform.calculate_qf_score(campaign_id), 25_000_000);
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.set_round_min_qf_donation(round_id, 0), Err(Error::NotCampaignOwner));
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(platform.set_round_min_qf_donation(99, 0), Err(Error::NoActiveRound));
+        }
+
+        #[ink::test]
+        fn get_my_permissions_reflects_caller() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let platform = DonationPlatformV2::new();
+
+            assert_eq!(
+                platform.get_my_permissions(),
+                Permissions { is_admin: true, is_pauser: true, is_fee_manager: true }
             );
-            assert_eq!(result, Err(Error::InvalidGoal));
 
-            // Goal too large
-            let result = platform.create_campaign(
-                String::from("Test"),
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                platform.get_my_permissions(),
+                Permissions { is_admin: false, is_pauser: false, is_fee_manager: false }
+            );
+        }
+
+        #[ink::test]
+        fn donation_history_cap_archives_old_donations() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+            platform.set_max_donation_history(3).unwrap();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
                 String::from("Description"),
-                1_000_000_000_000_001,
+                100_000_000,
                 10_000_000,
                 accounts.bob,
+            ).unwrap();
+
+            // charlie: 1M, 2M; django: 3M, 4M; charlie: 5M
+            for (donor, amount) in [
+                (accounts.charlie, 1_000_000),
+                (accounts.charlie, 2_000_000),
+                (accounts.django, 3_000_000),
+                (accounts.django, 4_000_000),
+                (accounts.charlie, 5_000_000),
+            ] {
+                test::set_caller::<DefaultEnvironment>(donor);
+                platform.process_donation(campaign_id, amount).unwrap();
+            }
+
+            // Only the last three are kept in detail
+            let details = platform.get_campaign_details(campaign_id, 0, 10).unwrap();
+            assert_eq!(details.total_donations, 5);
+            assert_eq!(
+                details.donations.iter().map(|d| d.amount).collect::<Vec<_>>(),
+                vec![3_000_000, 4_000_000, 5_000_000]
             );
-            assert_eq!(result, Err(Error::InvalidGoal));
+            assert_eq!(platform.get_donation_archive(campaign_id), Some((2, 3_000_000)));
+
+            // Totals stay exact
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().raised, 15_000_000);
+            assert_eq!(platform.get_donor_total(campaign_id, accounts.charlie), 8_000_000);
+            assert_eq!(platform.audit_campaign(campaign_id, 0, 10), (15_000_000, 15_000_000, true));
+
+            // Archived donations still count toward the QF score: (1000 + 1414 + 1732 + 2000 + 2236)²
+            assert_eq!(platform.calculate_qf_score(campaign_id), 8_382 * 8_382);
+
+            // Refunds pay back archived donations too
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.cancel_campaign(campaign_id).unwrap();
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            let before = test::get_account_balance::<DefaultEnvironment>(accounts.charlie).unwrap();
+            assert_eq!(platform.claim_refund(campaign_id), Ok(()));
+            let after = test::get_account_balance::<DefaultEnvironment>(accounts.charlie).unwrap();
+            assert_eq!(after - before, 8_000_000);
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.set_max_donation_history(0), Err(Error::NotCampaignOwner));
         }
 
         #[ink::test]
-        fn invalid_deadline_fails() {
+        fn donation_event_reports_nft_receipt() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
-            // Deadline too soon
-            let result = platform.create_campaign(
-                String::from("Test"),
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
                 String::from("Description"),
-                1000,
-                1000, // Too soon
+                100_000_000,
+                10_000_000,
                 accounts.bob,
+            ).unwrap();
+
+            let signature = <DonationReceived as ink::env::Event>::SIGNATURE_TOPIC.unwrap();
+            let last_donation_event = || {
+                test::recorded_events()
+                    .filter(|event| event.topics.first().map(|t| &t[..]) == Some(&signature[..]))
+                    .map(|event| <DonationReceived as scale::Decode>::decode(&mut &event.data[..]).unwrap())
+                    .last()
+                    .unwrap()
+            };
+
+            // NFT disabled: nothing minted
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, 1_000_000).unwrap();
+            let event = last_donation_event();
+            assert!(!event.nft_minted);
+            assert_eq!(event.nft_token_id, None);
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.set_nft_contract(accounts.frank).unwrap();
+            platform.set_nft_enabled(true).unwrap();
+
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, 1_000_000).unwrap();
+            let event = last_donation_event();
+            assert!(event.nft_minted);
+            assert_eq!(event.nft_token_id, Some(1));
+            assert_eq!(
+                MOCK_NFT_MINTS.with(|mints| mints.borrow().clone()),
+                vec![(accounts.frank, accounts.charlie, campaign_id, false)]
+            );
+
+            // Soulbound receipts are chosen before the first donation
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            let soulbound_id = platform.create_campaign(
+                String::from("Soulbound Campaign"),
+                String::from("Description"),
+                100_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            assert_eq!(platform.set_soulbound_receipts(campaign_id, true), Err(Error::CampaignHasDonations));
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            assert_eq!(platform.set_soulbound_receipts(soulbound_id, true), Err(Error::NotCampaignOwner));
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.set_soulbound_receipts(soulbound_id, true).unwrap();
+            assert!(platform.has_soulbound_receipts(soulbound_id));
+            assert!(!platform.has_soulbound_receipts(campaign_id));
+
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(soulbound_id, 1_000_000).unwrap();
+            assert_eq!(
+                MOCK_NFT_MINTS.with(|mints| mints.borrow().last().copied()),
+                Some((accounts.frank, accounts.charlie, soulbound_id, true))
             );
-            assert_eq!(result, Err(Error::InvalidDeadline));
         }
 
         #[ink::test]
-        fn cancel_campaign_works() {
+        fn nft_contract_is_told_when_a_campaign_is_funded() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
             let campaign_id = platform.create_campaign(
                 String::from("Test Campaign"),
                 String::from("Description"),
-                1000,
+                10_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            let unlinked_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000,
                 10_000_000,
                 accounts.bob,
             ).unwrap();
 
-            // Cancel campaign
-            let result = platform.cancel_campaign(campaign_id);
-            assert!(result.is_ok());
+            // NFT disabled: no notification
+            platform.process_donation(unlinked_id, 10_000_000).unwrap();
+            assert!(MOCK_NFT_FUNDED.with(|funded| funded.borrow().is_empty()));
 
-            // Verify state changed to Failed
-            let campaign = platform.get_campaign(campaign_id).unwrap();
-            assert_eq!(campaign.state, CampaignState::Failed);
+            platform.set_nft_contract(accounts.frank).unwrap();
+            platform.set_nft_enabled(true).unwrap();
+
+            // Only the donation that reaches the goal notifies
+            platform.process_donation(campaign_id, 5_000_000).unwrap();
+            assert!(MOCK_NFT_FUNDED.with(|funded| funded.borrow().is_empty()));
+            platform.process_donation(campaign_id, 5_000_000).unwrap();
+            assert_eq!(
+                MOCK_NFT_FUNDED.with(|funded| funded.borrow().clone()),
+                vec![(accounts.frank, campaign_id)]
+            );
+        }
+
+        #[ink::test]
+        fn get_config_matches_individual_getters() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            platform.set_max_batch_size(7).unwrap();
+            platform.set_nft_contract(accounts.frank).unwrap();
+            platform.set_nft_enabled(true).unwrap();
+            platform.set_platform_fee(150).unwrap();
+            test::set_value_transferred::<DefaultEnvironment>(5_000_000);
+            platform.fund_matching_pool().unwrap();
+            platform.create_matching_round(2_000_000, 1_000).unwrap();
+
+            let config = platform.get_config();
+            assert_eq!(
+                config,
+                PlatformConfig {
+                    version: platform.get_version(),
+                    max_batch_size: platform.get_max_batch_size(),
+                    nft_enabled: platform.is_nft_enabled(),
+                    nft_contract: platform.get_nft_contract(),
+                    matching_pool_balance: platform.get_matching_pool_balance(),
+                    current_round: platform.get_current_round(),
+                    fee_bps: platform.get_platform_fee(),
+                    paused: platform.is_paused(),
+                    treasury: platform.get_treasury(),
+                }
+            );
+            assert_eq!(config.max_batch_size, 7);
+            assert_eq!(config.nft_contract, Some(accounts.frank));
+            assert_eq!(config.matching_pool_balance, 3_000_000);
+            assert_eq!(config.current_round, Some(0));
+            assert_eq!(config.fee_bps, 150);
         }
 
         #[ink::test]
-        fn non_owner_cannot_cancel() {
+        fn get_donations_after_paginates_with_cursor() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
             let campaign_id = platform.create_campaign(
                 String::from("Test Campaign"),
                 String::from("Description"),
-                1000,
+                100_000_000,
                 10_000_000,
                 accounts.bob,
             ).unwrap();
+            let (page, next) = platform.get_donations_after(campaign_id, None, 10);
+            assert!(page.is_empty() && next.is_none());
 
-            // Set caller to non-owner
-            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            for i in 1..=7 {
+                assert_eq!(platform.process_donation(campaign_id, i * 1_000_000), Ok((i as u32 - 1, i * 1_000_000)));
+            }
 
-            let result = platform.cancel_campaign(campaign_id);
-            assert_eq!(result, Err(Error::NotCampaignOwner));
+            // Walk forward three at a time
+            let mut seen = Vec::new();
+            let mut cursor = None;
+            loop {
+                let (page, next) = platform.get_donations_after(campaign_id, cursor, 3);
+                assert!(page.len() <= 3);
+                seen.extend(page.into_iter().map(|(index, donation)| (index, donation.amount)));
+                if next.is_none() {
+                    break;
+                }
+                cursor = next;
+            }
+            assert_eq!(seen, (0..7).map(|i| (i, (i as u128 + 1) * 1_000_000)).collect::<Vec<_>>());
+
+            // New donations show up after the last cursor
+            platform.process_donation(campaign_id, 8_000_000).unwrap();
+            let (page, next) = platform.get_donations_after(campaign_id, Some(6), 3);
+            assert_eq!(page.iter().map(|(index, _)| *index).collect::<Vec<_>>(), vec![7]);
+            assert_eq!(next, None);
+            let (page, next) = platform.get_donations_after(99, None, 3);
+            assert!(page.is_empty() && next.is_none());
         }
 
         #[ink::test]
-        fn minimum_donation_enforced() {
+        fn donation_past_hard_cap_refunds_excess() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
             let campaign_id = platform.create_campaign(
                 String::from("Test Campaign"),
                 String::from("Description"),
-                1000,
+                10_000_000,
                 10_000_000,
                 accounts.bob,
             ).unwrap();
+            platform.set_soft_cap(campaign_id, 5_000_000).unwrap();
 
-            // Try donating below minimum
-            let result = platform.process_donation(campaign_id, MIN_DONATION - 1);
-            assert_eq!(result, Err(Error::InvalidDonationAmount));
-
-            // Donate at minimum should work
-            let result = platform.process_donation(campaign_id, MIN_DONATION);
-            assert!(result.is_ok());
-        }
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, 6_000_000).unwrap();
 
-        #[ink::test]
-        fn donation_count_overflow_protection() {
-            let accounts = test::default_accounts::<DefaultEnvironment>();
-            let mut platform = DonationPlatformV2::new();
+            // Only 4M fits under the 10M hard cap
+            let before = test::get_account_balance::<DefaultEnvironment>(accounts.charlie).unwrap();
+            let (index, accepted) = platform.process_donation(campaign_id, 7_000_000).unwrap();
+            assert_eq!(accepted, 4_000_000);
+            let after = test::get_account_balance::<DefaultEnvironment>(accounts.charlie).unwrap();
+            assert_eq!(after - before, 3_000_000);
 
-            let campaign_id = platform.create_campaign(
+            let campaign = platform.get_campaign(campaign_id).unwrap();
+            assert_eq!(campaign.raised, 10_000_000);
+            assert_eq!(platform.get_donor_total(campaign_id, accounts.charlie), 10_000_000);
+            let details = platform.get_campaign_details(campaign_id, index, 1).unwrap();
+            assert_eq!(details.donations[0].amount, 4_000_000);
+            // Fee only on the accepted part: 3% of 6M + 3% of 4M
+            assert_eq!(platform.get_accrued_fees(), 300_000);
+
+            let signature = <DonationExcessRefunded as ink::env::Event>::SIGNATURE_TOPIC.unwrap();
+            let event = test::recorded_events()
+                .find(|event| event.topics.first().map(|t| &t[..]) == Some(&signature[..]))
+                .unwrap();
+            let decoded = <DonationExcessRefunded as scale::Decode>::decode(&mut &event.data[..]).unwrap();
+            assert_eq!(decoded.amount, 3_000_000);
+
+            // An accepted part below the minimum donation is rejected rather than recorded as dust
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            let nearly_full_id = platform.create_campaign(
                 String::from("Test Campaign"),
                 String::from("Description"),
-                1000,
+                10_000_000,
                 10_000_000,
                 accounts.bob,
             ).unwrap();
+            platform.set_soft_cap(nearly_full_id, 5_000_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(nearly_full_id, 9_500_000).unwrap();
+            assert_eq!(platform.process_donation(nearly_full_id, 2_000_000), Err(Error::GoalReached));
+            assert_eq!(platform.get_campaign(nearly_full_id).unwrap().raised, 9_500_000);
 
-            // Get campaign and manually set donation_count near max
-            let mut campaign = platform.campaigns.get(campaign_id).unwrap();
-            campaign.donation_count = u32::MAX;
-            platform.campaigns.insert(campaign_id, &campaign);
-
-            // Try to donate - should fail with overflow protection
-            let result = platform.process_donation(campaign_id, MIN_DONATION);
-            assert_eq!(result, Err(Error::InvalidDonationAmount));
-        }
-
-        #[ink::test]
-        fn get_campaign_details_works() {
-            let accounts = test::default_accounts::<DefaultEnvironment>();
-            let mut platform = DonationPlatformV2::new();
-
-            let campaign_id = platform.create_campaign(
+            // Without a soft cap a donation may overshoot the goal as before
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            let uncapped_id = platform.create_campaign(
                 String::from("Test Campaign"),
                 String::from("Description"),
-                10_000_000_000,
+                10_000_000,
                 10_000_000,
                 accounts.bob,
             ).unwrap();
-
-            // Add some donations
-            platform.process_donation(campaign_id, MIN_DONATION).unwrap();
-            platform.process_donation(campaign_id, MIN_DONATION * 2).unwrap();
-
-            // Get details with pagination
-            let details = platform.get_campaign_details(campaign_id, 0, 10).unwrap();
-            assert_eq!(details.total_donations, 2);
-            assert_eq!(details.donations.len(), 2);
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(uncapped_id, 12_000_000).unwrap();
+            assert_eq!(platform.get_campaign(uncapped_id).unwrap().raised, 12_000_000);
         }
 
         #[ink::test]
-        fn batch_operations_respect_max_size() {
+        fn get_campaigns_by_state_filters() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
-            // Try to create more campaigns than max_batch_size
-            let mut campaigns_data = Vec::new();
-            for _ in 0..51 {
-                campaigns_data.push((
-                    String::from("Campaign"),
-                    String::from("Desc"),
-                    1000,
+            let mut ids = Vec::new();
+            for _ in 0..5 {
+                ids.push(platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000,
                     10_000_000,
                     accounts.bob,
-                ));
+                ).unwrap());
             }
 
-            let result = platform.create_campaigns_batch(campaigns_data);
-            assert_eq!(result, Err(Error::BatchSizeTooLarge));
+            platform.cancel_campaign(ids[1]).unwrap();
+            platform.cancel_campaign(ids[3]).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(ids[2], 10_000_000).unwrap();
+
+            let ids_of = |(campaigns, cursor): (Vec<Campaign>, Option<u32>)| {
+                (campaigns.into_iter().map(|c| c.id).collect::<Vec<_>>(), cursor)
+            };
+            assert_eq!(ids_of(platform.get_campaigns_by_state(CampaignState::Failed, None, 10)), (vec![ids[1], ids[3]], None));
+            assert_eq!(ids_of(platform.get_campaigns_by_state(CampaignState::Successful, None, 10)), (vec![ids[2]], None));
+            assert_eq!(ids_of(platform.get_campaigns_by_state(CampaignState::Active, None, 1)), (vec![ids[0]], Some(ids[0])));
+            assert_eq!(ids_of(platform.get_campaigns_by_state(CampaignState::Active, Some(ids[0]), 10)), (vec![ids[4]], None));
+            assert_eq!(ids_of(platform.get_campaigns_by_state(CampaignState::Withdrawn, None, 10)), (vec![], None));
+            assert_eq!(platform.get_active_campaigns(0, 1).into_iter().map(|c| c.id).collect::<Vec<_>>(), vec![ids[0]]);
+            assert_eq!(platform.get_active_campaigns(1, 10).into_iter().map(|c| c.id).collect::<Vec<_>>(), vec![ids[4]]);
+
+            // Each call scans a bounded number of campaigns and says where to resume
+            platform.campaign_count = MAX_CAMPAIGN_SCAN * 2;
+            assert_eq!(
+                ids_of(platform.get_campaigns_by_state(CampaignState::Withdrawn, None, 10)),
+                (vec![], Some(MAX_CAMPAIGN_SCAN - 1))
+            );
+            assert_eq!(
+                ids_of(platform.get_campaigns_by_state(CampaignState::Withdrawn, Some(MAX_CAMPAIGN_SCAN - 1), 10)),
+                (vec![], None)
+            );
         }
 
         #[ink::test]
-        fn set_max_batch_size_requires_admin() {
+        fn max_donors_rejects_new_donors_only() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
-            // Set caller to non-admin
-            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                100_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+            platform.set_max_donors(campaign_id, 2).unwrap();
 
-            let result = platform.set_max_batch_size(100);
-            assert_eq!(result, Err(Error::NotCampaignOwner));
-        }
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.set_max_donors(campaign_id, 5), Err(Error::NotCampaignOwner));
+            platform.process_donation(campaign_id, 1_000_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, 1_000_000).unwrap();
+
+            // A third distinct donor is turned away, without being charged a fee
+            let fees = platform.get_accrued_fees();
+            test::set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(platform.process_donation(campaign_id, 1_000_000), Err(Error::DonorLimitReached));
+            assert_eq!(platform.get_accrued_fees(), fees);
+
+            // Existing donors can keep giving
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 2_000_000).unwrap();
 
-        #[ink::test]
-        fn set_max_batch_size_works() {
-            let mut platform = DonationPlatformV2::new();
+            let campaign = platform.get_campaign(campaign_id).unwrap();
+            assert_eq!(campaign.unique_donor_count, 2);
+            assert_eq!(campaign.raised, 4_000_000);
 
-            let result = platform.set_max_batch_size(100);
-            assert!(result.is_ok());
-            assert_eq!(platform.get_max_batch_size(), 100);
+            // Lifting the cap lets new donors in again
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.set_max_donors(campaign_id, 0).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.django);
+            platform.process_donation(campaign_id, 1_000_000).unwrap();
         }
 
         #[ink::test]
-        fn get_campaigns_paginated_works() {
+        fn initialize_runs_once() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
-            // Create 5 campaigns
-            for i in 0..5 {
-                platform.create_campaign(
-                    format!("Campaign {}", i),
-                    String::from("Description"),
-                    1000,
-                    10_000_000,
-                    accounts.bob,
-                ).unwrap();
-            }
-
-            // Get first 3
-            let campaigns = platform.get_campaigns_paginated(0, 3);
-            assert_eq!(campaigns.len(), 3);
+            // A constructed contract is already initialized
+            assert!(platform.is_initialized());
+            assert_eq!(platform.initialize(accounts.bob, accounts.bob), Err(Error::AlreadyInitialized));
+            assert_eq!(platform.get_admin(), accounts.alice);
 
-            // Get next 2
-            let campaigns = platform.get_campaigns_paginated(3, 3);
-            assert_eq!(campaigns.len(), 2);
+            // Deferred setup: only the deployer may initialize
+            let mut platform = DonationPlatformV2::new_uninitialized();
+            assert!(!platform.is_initialized());
+            test::set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(platform.initialize(accounts.django, accounts.django), Err(Error::NotCampaignOwner));
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            let zero = AccountId::from([0; 32]);
+            assert_eq!(platform.initialize(zero, accounts.eve), Err(Error::ZeroAddress));
+            assert_eq!(platform.initialize(accounts.charlie, zero), Err(Error::ZeroAddress));
+            assert!(!platform.is_initialized());
+            assert_eq!(platform.initialize(accounts.charlie, accounts.eve), Ok(()));
+            assert_eq!(platform.get_admin(), accounts.charlie);
+            assert_eq!(platform.get_treasury(), accounts.eve);
+
+            assert_eq!(platform.initialize(accounts.django, accounts.django), Err(Error::AlreadyInitialized));
+            assert_eq!(platform.get_admin(), accounts.charlie);
         }
 
         #[ink::test]
-        fn migration_constructor_works() {
-            let platform = DonationPlatformV2::migrate_from_v1(42);
-            assert_eq!(platform.get_campaign_count(), 42);
-            assert_eq!(platform.get_version(), 2);
+        fn sqrt_handles_full_u128_range() {
+            assert_eq!(DonationPlatformV2::sqrt(0), 0);
+            assert_eq!(DonationPlatformV2::sqrt(1), 1);
+            assert_eq!(DonationPlatformV2::sqrt(2), 1);
+            assert_eq!(DonationPlatformV2::sqrt(3), 1);
+
+            for root in [2u128, 1_000, 1_000_000_007, u64::MAX as u128] {
+                assert_eq!(DonationPlatformV2::sqrt(root * root), root);
+                assert_eq!(DonationPlatformV2::sqrt(root * root - 1), root - 1);
+            }
+
+            // Largest inputs, where a naive first step would overflow
+            let max_root = u64::MAX as u128;
+            assert_eq!(DonationPlatformV2::sqrt(u128::MAX), max_root);
+            assert_eq!(DonationPlatformV2::sqrt(u128::MAX - 1), max_root);
+            assert_eq!(DonationPlatformV2::sqrt(max_root * max_root + 1), max_root);
         }
 
         #[ink::test]
-        fn campaign_reaches_goal() {
+        fn scheduled_round_phases() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
+            test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            assert_eq!(platform.create_scheduled_matching_round(0, 999, 1_000), Err(Error::InvalidDeadline));
+            let round_id = platform.create_scheduled_matching_round(0, 5_000, 10_000).unwrap();
+            assert_eq!(platform.get_round(round_id).unwrap().end_time, 15_000);
+            assert_eq!(platform.get_round_phase(round_id), Some(RoundPhase::Scheduled));
+            assert_eq!(platform.get_round_phase(99), None);
+
+            // Campaigns can enroll ahead of time, but early donations don't count
             let campaign_id = platform.create_campaign(
                 String::from("Test Campaign"),
                 String::from("Description"),
-                10_000_000,  // Goal of 10M (10 DOT)
+                100_000_000,
                 10_000_000,
                 accounts.bob,
             ).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, 4_000_000).unwrap();
+            assert_eq!(platform.calculate_qf_score(campaign_id), 0);
 
-            // Donate exactly the goal amount
-            platform.process_donation(campaign_id, 10_000_000).unwrap();
+            test::set_block_timestamp::<DefaultEnvironment>(5_000);
+            assert_eq!(platform.get_round_phase(round_id), Some(RoundPhase::Active));
+            platform.process_donation(campaign_id, 9_000_000).unwrap();
+            assert_eq!(platform.calculate_qf_score(campaign_id), 9_000_000);
 
-            let campaign = platform.get_campaign(campaign_id).unwrap();
-            assert_eq!(campaign.state, CampaignState::Successful);
-            assert_eq!(campaign.raised, 10_000_000);
+            test::set_block_timestamp::<DefaultEnvironment>(15_000);
+            assert_eq!(platform.get_round_phase(round_id), Some(RoundPhase::Ended));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.calculate_and_distribute_matching(round_id).unwrap();
+            assert_eq!(platform.get_round_phase(round_id), Some(RoundPhase::Distributed));
         }
 
         #[ink::test]
-        fn cannot_donate_to_inactive_campaign() {
+        fn pro_rata_refunds_share_the_shortfall() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
             let campaign_id = platform.create_campaign(
                 String::from("Test Campaign"),
                 String::from("Description"),
-                1000,
+                10_000_000_000,
                 10_000_000,
                 accounts.bob,
             ).unwrap();
 
-            // Cancel campaign
+            let donors = [
+                (accounts.bob, 2_000_000),
+                (accounts.charlie, 3_000_000),
+                (accounts.django, 5_000_000),
+            ];
+            for (donor, amount) in donors {
+                test::set_caller::<DefaultEnvironment>(donor);
+                platform.process_donation(campaign_id, amount).unwrap();
+            }
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(platform.enable_pro_rata_refunds(campaign_id), Err(Error::CampaignFailed));
             platform.cancel_campaign(campaign_id).unwrap();
 
-            // Try to donate
-            let result = platform.process_donation(campaign_id, MIN_DONATION);
-            assert_eq!(result, Err(Error::CampaignNotActive));
+            // The 300_000 in accrued fees is owed to the treasury, not the donors
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 10_300_000);
+            assert_eq!(platform.enable_pro_rata_refunds(campaign_id), Err(Error::NotInsolvent));
+
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 5_300_001);
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.enable_pro_rata_refunds(campaign_id), Err(Error::NotCampaignOwner));
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(platform.enable_pro_rata_refunds(campaign_id), Ok(()));
+            assert_eq!(platform.get_pro_rata_refunds(campaign_id), Some((5_000_001, 10_000_000)));
+
+            let mut paid = Vec::new();
+            for (donor, _) in donors {
+                test::set_caller::<DefaultEnvironment>(donor);
+                let before = test::get_account_balance::<DefaultEnvironment>(donor).unwrap();
+                assert_eq!(platform.claim_refund(campaign_id), Ok(()));
+                let after = test::get_account_balance::<DefaultEnvironment>(donor).unwrap();
+                paid.push(after - before);
+            }
+
+            assert_eq!(paid, vec![1_000_000, 1_500_000, 2_500_001]);
+            assert_eq!(paid.iter().sum::<Balance>(), 5_000_001);
+            assert_eq!(platform.get_pro_rata_refunds(campaign_id), Some((0, 0)));
+            assert_eq!(test::get_account_balance::<DefaultEnvironment>(contract_addr), Ok(300_000));
         }
 
         #[ink::test]
-        fn get_active_campaigns_filters_correctly() {
+        fn pro_rata_refunds_ignore_refunds_already_paid_elsewhere() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
-            // Create 3 campaigns
-            for i in 0..3 {
+            let create = |platform: &mut DonationPlatformV2| {
                 platform.create_campaign(
-                    format!("Campaign {}", i),
+                    String::from("Test Campaign"),
                     String::from("Description"),
-                    1000,
+                    10_000_000_000,
                     10_000_000,
                     accounts.bob,
-                ).unwrap();
+                ).unwrap()
+            };
+            let first = create(&mut platform);
+            let second = create(&mut platform);
+
+            for (campaign_id, donor, amount) in [
+                (first, accounts.bob, 4_000_000),
+                (first, accounts.charlie, 6_000_000),
+                (second, accounts.django, 5_000_000),
+                (second, accounts.eve, 5_000_000),
+            ] {
+                test::set_caller::<DefaultEnvironment>(donor);
+                platform.process_donation(campaign_id, amount).unwrap();
             }
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            platform.cancel_campaign(first).unwrap();
+            platform.cancel_campaign(second).unwrap();
 
-            // Cancel one
-            platform.cancel_campaign(1).unwrap();
+            // 5_000_000 short across both campaigns; Bob is refunded in full first
+            use ink::codegen::Env;
+            let contract_addr = platform.env().account_id();
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 15_600_000);
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.claim_refund(first).unwrap();
+            assert_eq!(platform.get_liabilities(), 16_600_000);
 
-            // Get active campaigns
-            let active = platform.get_active_campaigns(0, 10);
-            assert_eq!(active.len(), 2);
+            // With nothing left for the first campaign, pro-rata mode is refused
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 10_600_000);
+            assert_eq!(platform.enable_pro_rata_refunds(first), Err(Error::InsufficientFunds));
+
+            // Bob's refund no longer counts against the second campaign
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 11_600_000);
+            assert_eq!(platform.enable_pro_rata_refunds(second), Ok(()));
+            assert_eq!(platform.get_pro_rata_refunds(second), Some((5_000_000, 10_000_000)));
+
+            test::set_caller::<DefaultEnvironment>(accounts.django);
+            let before = test::get_account_balance::<DefaultEnvironment>(accounts.django).unwrap();
+            platform.claim_refund(second).unwrap();
+            let after = test::get_account_balance::<DefaultEnvironment>(accounts.django).unwrap();
+            assert_eq!(after - before, 2_500_000);
         }
+
         #[ink::test]
-        fn platform_fee_deducted() {
+        fn campaigns_indexed_by_beneficiary() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
-            let campaign_id = platform.create_campaign(
-                String::from("Test Campaign"),
-                String::from("Description"),
-                1000,
-                10_000_000,
-                accounts.bob,
-            ).unwrap();
+            let create = |platform: &mut DonationPlatformV2, beneficiary| {
+                platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000_000,
+                    10_000_000,
+                    beneficiary,
+                ).unwrap()
+            };
 
-            // Donate 10_000_000 (10 DOT)
-            platform.process_donation(campaign_id, 10_000_000).unwrap();
+            let first = create(&mut platform, accounts.eve);
+            let other = create(&mut platform, accounts.frank);
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            let second = create(&mut platform, accounts.eve);
 
-            // Check campaign raised (should be gross 10_000_000)
-            let campaign = platform.get_campaign(campaign_id).unwrap();
-            assert_eq!(campaign.raised, 10_000_000);
+            assert_eq!(platform.get_campaigns_by_beneficiary(accounts.eve, 0, 10), vec![first, second]);
+            assert_eq!(platform.get_campaigns_by_beneficiary(accounts.eve, 1, 10), vec![second]);
+            assert_eq!(platform.get_campaigns_by_beneficiary(accounts.eve, 0, 1), vec![first]);
+            assert_eq!(platform.get_campaigns_by_beneficiary(accounts.frank, 0, 10), vec![other]);
+            assert!(platform.get_campaigns_by_beneficiary(accounts.alice, 0, 10).is_empty());
+        }
 
-            // In a real environment, 3 would be sent to treasury.
-            // In unit tests, we can't easily check the transfer without mocking,
-            // but we can check the withdrawal amount later.
+        #[ink::test]
+        fn donor_breadth_weighting_favours_more_donors() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            // Four donors of 1M to one campaign, one of 16M to another, under a given weighting
+            let mut run = |donor_breadth_bps: u32| {
+                test::set_caller::<DefaultEnvironment>(accounts.alice);
+                test::set_block_timestamp::<DefaultEnvironment>(0);
+                test::set_value_transferred::<DefaultEnvironment>(10_000_000);
+                platform.fund_matching_pool().unwrap();
+                let round_id = platform.create_scheduled_matching_round(10_000_000, 1, 1_000_000).unwrap();
+
+                test::set_caller::<DefaultEnvironment>(accounts.bob);
+                assert_eq!(platform.set_round_donor_breadth(round_id, 5_000), Err(Error::NotCampaignOwner));
+                test::set_caller::<DefaultEnvironment>(accounts.alice);
+                assert_eq!(platform.set_round_donor_breadth(round_id, 10_001), Err(Error::InvalidDonorBreadth));
+                assert_eq!(platform.set_round_donor_breadth(99, 5_000), Err(Error::NoActiveRound));
+                platform.set_round_donor_breadth(round_id, donor_breadth_bps).unwrap();
+
+                let mut create = || platform.create_campaign(
+                    String::from("Test Campaign"),
+                    String::from("Description"),
+                    10_000_000_000,
+                    10_000_000,
+                    accounts.bob,
+                ).unwrap();
+                let broad = create();
+                let narrow = create();
+
+                test::set_block_timestamp::<DefaultEnvironment>(1);
+                for donor in [accounts.bob, accounts.charlie, accounts.django, accounts.eve] {
+                    test::set_caller::<DefaultEnvironment>(donor);
+                    platform.process_donation(broad, 1_000_000).unwrap();
+                }
+                test::set_caller::<DefaultEnvironment>(accounts.frank);
+                platform.process_donation(narrow, 16_000_000).unwrap();
+
+                (
+                    platform.calculate_qf_score(broad),
+                    platform.calculate_qf_score(narrow),
+                    platform.get_estimated_matching(broad),
+                    platform.get_estimated_matching(narrow),
+                )
+            };
+
+            // Without weighting both score (4_000)²
+            assert_eq!(run(0), (16_000_000, 16_000_000, 5_000_000, 5_000_000));
+
+            // +50% per doubling: four donors double the score, one donor leaves it alone
+            assert_eq!(run(5_000), (32_000_000, 16_000_000, 6_666_666, 3_333_333));
         }
 
         #[ink::test]
-        fn withdrawal_respects_fees() {
+        fn refund_window_limits_claims_then_allows_sweep() {
             let accounts = test::default_accounts::<DefaultEnvironment>();
             let mut platform = DonationPlatformV2::new();
 
             let campaign_id = platform.create_campaign(
                 String::from("Test Campaign"),
                 String::from("Description"),
-                100, // Goal 100
+                10_000_000_000,
                 10_000_000,
                 accounts.bob,
             ).unwrap();
+            assert_eq!(
+                platform.set_refund_window(campaign_id, MIN_REFUND_WINDOW, AccountId::from([0; 32])),
+                Err(Error::InvalidBeneficiary)
+            );
+            assert_eq!(
+                platform.set_refund_window(campaign_id, MIN_REFUND_WINDOW, accounts.alice),
+                Err(Error::InvalidBeneficiary)
+            );
+            assert_eq!(
+                platform.set_refund_window(campaign_id, MIN_REFUND_WINDOW, accounts.bob),
+                Err(Error::InvalidBeneficiary)
+            );
+            assert_eq!(
+                platform.set_refund_window(campaign_id, 1, accounts.frank),
+                Err(Error::InvalidRefundWindow)
+            );
+            platform.set_refund_window(campaign_id, MIN_REFUND_WINDOW, accounts.frank).unwrap();
 
-            // Donate 10_000_000 (10 DOT)
-            platform.process_donation(campaign_id, 10_000_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 2_000_000).unwrap();
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            platform.process_donation(campaign_id, 3_000_000).unwrap();
 
-            // Campaign successful
-            let campaign = platform.get_campaign(campaign_id).unwrap();
-            assert_eq!(campaign.state, CampaignState::Successful);
+            // The terms are fixed once donations arrive
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                platform.set_refund_window(campaign_id, MIN_REFUND_WINDOW, accounts.django),
+                Err(Error::CampaignHasDonations)
+            );
+
+            test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            platform.cancel_campaign(campaign_id).unwrap();
 
-            // Withdraw
-            // We need to mock the contract having funds, otherwise transfer fails in test?
-            // ink! tests usually start with some balance.
-            // But we transferred fee OUT.
-            // Fee = 10_000_000 * 3 / 100 = 300_000.
-            // Net remaining = 9_700_000.
-            
-            // We need to set the contract balance to simulate the donation remaining amount.
-            // In ink! 5, we might need to import Env to call env() on the contract instance in tests
             use ink::codegen::Env;
             let contract_addr = platform.env().account_id();
-            test::set_account_balance::<DefaultEnvironment>(contract_addr, 9_700_000);
+            test::set_account_balance::<DefaultEnvironment>(contract_addr, 100_000_000);
+
+            // Inside the window
+            test::set_block_timestamp::<DefaultEnvironment>(1_000 + MIN_REFUND_WINDOW);
+            assert_eq!(platform.sweep_unclaimed_refunds(campaign_id), Err(Error::RefundWindowOpen));
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(platform.claim_refund(campaign_id), Ok(()));
+
+            // After it
+            test::set_block_timestamp::<DefaultEnvironment>(1_001 + MIN_REFUND_WINDOW);
+            test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            assert!(!platform.can_claim_refund(campaign_id, accounts.charlie));
+            assert_eq!(platform.claim_refund(campaign_id), Err(Error::RefundWindowClosed));
+            assert_eq!(platform.sweep_unclaimed_refunds(campaign_id), Err(Error::NotCampaignOwner));
 
-            // Set caller to owner (Alice created it)
             test::set_caller::<DefaultEnvironment>(accounts.alice);
-            
-            let result = platform.withdraw_funds(campaign_id);
-            assert_eq!(result, Ok(()));
+            let frank_before = test::get_account_balance::<DefaultEnvironment>(accounts.frank).unwrap();
+            assert_eq!(platform.sweep_unclaimed_refunds(campaign_id), Ok(3_000_000));
+            assert_eq!(
+                test::get_account_balance::<DefaultEnvironment>(accounts.frank),
+                Ok(frank_before + 3_000_000)
+            );
+            assert_eq!(platform.sweep_unclaimed_refunds(campaign_id), Err(Error::NoDonationFound));
+        }
+        #[ink::test]
+        fn donor_breadth_ignores_donors_without_qf_credit() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut platform = DonationPlatformV2::new();
+
+            test::set_value_transferred::<DefaultEnvironment>(10_000_000);
+            platform.fund_matching_pool().unwrap();
+            let round_id = platform.create_scheduled_matching_round(10_000_000, 1_000, 1_000_000).unwrap();
+            platform.set_round_min_qf_donation(round_id, 2_000_000).unwrap();
+            platform.set_round_donor_breadth(round_id, 5_000).unwrap();
+
+            let campaign_id = platform.create_campaign(
+                String::from("Test Campaign"),
+                String::from("Description"),
+                10_000_000_000,
+                10_000_000,
+                accounts.bob,
+            ).unwrap();
+
+            // A donor before the round opened, then one real donor and three dust accounts
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            platform.process_donation(campaign_id, 4_000_000).unwrap();
+            test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            test::set_caller::<DefaultEnvironment>(accounts.frank);
+            platform.process_donation(campaign_id, 16_000_000).unwrap();
+            for donor in [accounts.charlie, accounts.django, accounts.eve] {
+                test::set_caller::<DefaultEnvironment>(donor);
+                platform.process_donation(campaign_id, 1_000_000).unwrap();
+            }
+            assert_eq!(platform.get_campaign(campaign_id).unwrap().unique_donor_count, 5);
+
+            // Only frank earns QF credit, so there is no breadth bonus
+            assert_eq!(platform.calculate_qf_score(campaign_id), 16_000_000);
         }
     }
 }
@@ -358,6 +358,35 @@ export const NftProvider = ({ children }) => {
     }
   }, [nftContract, selectedAccount]);
 
+  // Get combined donation amount of the receipts the user currently holds
+  // Unlike getTotalDonated, this follows receipts when they are transferred
+  const getHeldDonationValue = useCallback(async () => {
+    if (!nftContract || !selectedAccount) return 0;
+
+    try {
+      console.log('[NFT] Fetching held donation value for:', selectedAccount.address);
+      
+      const { result, output } = await nftContract.query.getHeldDonationValue(
+        selectedAccount.address,
+        { value: 0, gasLimit: -1 },
+        selectedAccount.address
+      );
+
+      if (result.isOk && output) {
+        const held = output.toHuman();
+        console.log('[NFT] Held donation value:', held);
+        // Parse the balance string (removes commas)
+        return parseInt(held?.replace(/,/g, '') || '0');
+      } else {
+        console.log('[NFT] No held donation value found');
+        return 0;
+      }
+    } catch (err) {
+      console.error('[NFT] Error fetching held donation value:', err);
+      return 0;
+    }
+  }, [nftContract, selectedAccount]);
+
   // Get achievements for user
   const getAchievements = useCallback(async () => {
     if (!nftContract || !selectedAccount) return [];
@@ -437,6 +466,7 @@ export const NftProvider = ({ children }) => {
     getNftsByRarity,
     getRarityDistribution,
     getTotalDonated,
+    getHeldDonationValue,
     getAchievements,
     areTransfersEnabled,
   };
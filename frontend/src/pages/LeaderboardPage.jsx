@@ -4,7 +4,7 @@ import { useWallet } from '../contexts/WalletContext';
 import { formatBalance } from '../utils/formatters';
 
 const LeaderboardPage = () => {
-  const { getLeaderboard, getTotalDonated, nftEnabled } = useNft();
+  const { getLeaderboard, getTotalDonated, getHeldDonationValue, nftEnabled } = useNft();
   const { selectedAccount } = useWallet();
   const [leaderboard, setLeaderboard] = useState([]);
   const [loading, setLoading] = useState(true);
@@ -21,16 +21,19 @@ const LeaderboardPage = () => {
 
         // Find user's rank if they're in the leaderboard
         if (selectedAccount) {
-          const totalDonated = await getTotalDonated();
+          const [totalDonated, heldValue] = await Promise.all([
+            getTotalDonated(),
+            getHeldDonationValue(),
+          ]);
           const userIndex = data.findIndex(
             entry => entry.address.toLowerCase() === selectedAccount.address.toLowerCase()
           );
           
           if (userIndex !== -1) {
             setUserRank(userIndex + 1);
-            setUserStats(data[userIndex]);
+            setUserStats({ ...data[userIndex], heldValue });
           } else {
-            setUserStats({ totalDonated, nftCount: 0 });
+            setUserStats({ totalDonated, heldValue, nftCount: 0 });
           }
         }
       } catch (error) {
@@ -43,7 +46,7 @@ const LeaderboardPage = () => {
     if (nftEnabled) {
       loadLeaderboard();
     }
-  }, [nftEnabled, selectedAccount, getLeaderboard, getTotalDonated]);
+  }, [nftEnabled, selectedAccount, getLeaderboard, getTotalDonated, getHeldDonationValue]);
 
   // Sort leaderboard based on selected criteria
   const sortedLeaderboard = [...leaderboard].sort((a, b) => {
@@ -138,6 +141,10 @@ const LeaderboardPage = () => {
                   <div className="text-2xl font-bold text-text-primary">{userStats.totalDonated ? formatBalance(userStats.totalDonated) : '0'}</div>
                   <div className="text-xs text-text-muted">Total Donated</div>
                 </div>
+                <div className="text-center">
+                  <div className="text-2xl font-bold text-text-primary">{userStats.heldValue ? formatBalance(userStats.heldValue) : '0'}</div>
+                  <div className="text-xs text-text-muted">Receipts Held</div>
+                </div>
                 <div className="text-center">
                   <div className="text-2xl font-bold text-text-primary">{userStats.nftCount || 0}</div>
                   <div className="text-xs text-text-muted">NFTs Collected</div>
@@ -2,12 +2,15 @@ import { useState, useEffect, useMemo } from 'react';
 import toast from 'react-hot-toast';
 import { useWallet } from '../contexts/WalletContext';
 import { useCampaign } from '../contexts/CampaignContext.jsx';
+import { useNft } from '../contexts/NftContext.jsx';
 import { formatDOT, shortenAddress } from '../utils/formatters';
 import { saveProfile, loadProfile } from '../utils/aiApi';
 
 const UserProfilePage = () => {
   const { selectedAccount, balance } = useWallet();
   const { campaigns } = useCampaign();
+  const { getHeldDonationValue } = useNft();
+  const [heldValue, setHeldValue] = useState(0);
 
   // Profile state
   const [profile, setProfile] = useState({
@@ -61,6 +64,13 @@ const UserProfilePage = () => {
     };
   }, [selectedAccount, campaigns]);
 
+  // Load the donation value of the receipts the user holds
+  useEffect(() => {
+    if (selectedAccount) {
+      getHeldDonationValue().then(setHeldValue);
+    }
+  }, [selectedAccount, getHeldDonationValue]);
+
   // Load profile data
   useEffect(() => {
     const fetchProfile = async () => {
@@ -274,6 +284,9 @@ const UserProfilePage = () => {
           <p className="text-sm text-white/60">
             to {userStats?.campaignsSupported || 0} campaigns
           </p>
+          <p className="text-sm text-white/60">
+            {formatDOT(heldValue)} DOT in receipts held
+          </p>
         </div>
       </div>
 
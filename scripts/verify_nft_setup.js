@@ -128,13 +128,13 @@ async function verifyNftSetup() {
             } else if (configuredAddress === null) {
                 console.log('  ❌ Platform contract address is NOT set (null)');
                 console.log(`    Expected: ${PLATFORM_ADDRESS}`);
-                console.log('     ACTION: Call setPlatformContract() on NFT contract');
+                console.log('     ACTION: Call proposePlatformContract() then acceptPlatformContract() on NFT contract');
                 allChecksPass = false;
             } else {
                 console.log('  ❌ Platform contract address MISMATCH on NFT');
                 console.log(`    Expected: ${PLATFORM_ADDRESS}`);
                 console.log(`    Configured: ${configuredAddress}`);
-                console.log('     ACTION: Call setPlatformContract() on NFT contract');
+                console.log('     ACTION: Call proposePlatformContract() then acceptPlatformContract() on NFT contract');
                 allChecksPass = false;
             }
         } else {
@@ -249,7 +249,7 @@ async function verifyNftSetup() {
         console.log('\nRequired actions:');
         console.log('1. Ensure setNftContract() was called on platform contract');
         console.log('2. Ensure setNftEnabled(true) was called on platform contract');
-        console.log('3. Ensure setPlatformContract() was called on NFT contract');
+        console.log('3. Ensure proposePlatformContract() and acceptPlatformContract() were called on NFT contract');
     }
     
     console.log('='.repeat(80));
@@ -12,6 +12,11 @@ mod subscription_manager {
         pub price: Balance,
         pub benefits: Vec<String>,
         pub creator: AccountId,
+        /// Length of the one-time free trial in days (0 = no trial).
+        pub free_trial_days: u32,
+        /// Access level; higher levels include the access of lower ones.
+        /// Level 0 is reserved for legacy (tier 0) subscriptions.
+        pub level: u32,
     }
 
     #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
@@ -40,8 +45,32 @@ mod subscription_manager {
         Unauthorized,
         /// Invalid tier data.
         InvalidTierData,
+        /// Too many items in a single batch call.
+        BatchSizeTooLarge,
+        /// The tier does not offer a free trial.
+        TrialNotAvailable,
+        /// The subscriber already used their free trial with this creator.
+        TrialAlreadyUsed,
+        /// The subscriber still has an active subscription to this creator.
+        SubscriptionActive,
+        /// The subscription is not yet within `RENEWAL_WINDOW` of expiring.
+        RenewalTooEarly,
+        /// The tier's price is above what the subscriber agreed to pay from their deposit.
+        PriceAboveLimit,
     }
 
+    /// Maximum number of tiers that can be created in one `create_tiers_batch` call.
+    const MAX_TIER_BATCH_SIZE: usize = 10;
+
+    /// Maximum number of entries kept in a subscription's payment history.
+    const MAX_SUBSCRIPTION_HISTORY: usize = 50;
+
+    /// Maximum number of subscribers checked per `get_creator_reputation` call.
+    const MAX_REPUTATION_SCAN: u32 = 100;
+
+    /// How long before expiry a subscription can be renewed from its deposit (3 days).
+    const RENEWAL_WINDOW: Timestamp = 3 * 86_400_000;
+
     #[ink(storage)]
     pub struct SubscriptionManager {
         /// Mapping from (Subscriber, Creator) -> Subscription Record
@@ -52,6 +81,23 @@ mod subscription_manager {
         tiers: Mapping<(AccountId, u32), Tier>,
         /// Mapping from Creator -> Next Tier ID
         next_tier_id: Mapping<AccountId, u32>,
+        /// Mapping from (Subscriber, Creator) -> whether the free trial was used
+        trial_used: Mapping<(AccountId, AccountId), bool>,
+        /// Mapping from (Subscriber, Creator) -> prepaid balance held for renewals
+        renewal_deposits: Mapping<(AccountId, AccountId), Balance>,
+        /// Mapping from (Subscriber, Creator) -> highest price a deposit-funded renewal may charge
+        renewal_max_prices: Mapping<(AccountId, AccountId), Balance>,
+        /// Total value held in escrow across all renewal deposits
+        total_escrow: Balance,
+        /// Total platform fees sent to the treasury to date
+        total_fees_collected: Balance,
+        /// Mapping from (Creator, Index) -> every subscriber who ever subscribed (in order)
+        creator_subscribers: Mapping<(AccountId, u32), AccountId>,
+        /// Mapping from Creator -> number of entries in `creator_subscribers`
+        creator_subscriber_count: Mapping<AccountId, u32>,
+        /// Mapping from (Subscriber, Creator) -> paid periods as (timestamp, tier_id, amount_paid), oldest first
+        #[allow(clippy::type_complexity)]
+        subscription_history: Mapping<(AccountId, AccountId), Vec<(Timestamp, u32, Balance)>>,
         /// Treasury account for platform fees
         treasury: AccountId,
         /// Admin account
@@ -66,6 +112,14 @@ mod subscription_manager {
                 creator_prices: Mapping::default(),
                 tiers: Mapping::default(),
                 next_tier_id: Mapping::default(),
+                trial_used: Mapping::default(),
+                renewal_deposits: Mapping::default(),
+                renewal_max_prices: Mapping::default(),
+                total_escrow: 0,
+                total_fees_collected: 0,
+                creator_subscribers: Mapping::default(),
+                creator_subscriber_count: Mapping::default(),
+                subscription_history: Mapping::default(),
                 treasury,
                 admin: Self::env().caller(),
             }
@@ -90,47 +144,8 @@ mod subscription_manager {
                 return Err(Error::InvalidPaymentAmount);
             }
 
-            // Calculate fee (3%)
-            let fee = payment.checked_mul(3).unwrap().checked_div(100).unwrap();
-            let creator_share = payment.checked_sub(fee).unwrap();
-
-            // Transfer shares
-            if fee > 0 {
-                self.env().transfer(self.treasury, fee).map_err(|_| Error::TransferFailed)?;
-            }
-            if creator_share > 0 {
-                self.env().transfer(creator, creator_share).map_err(|_| Error::TransferFailed)?;
-            }
-
-            // Update subscription
-            let current_time = self.env().block_timestamp();
-            let existing_record = self.subscriptions.get((caller, creator));
-            
-            // If expired or new, start from now. If active, extend from current expiration.
-            let start_time = if let Some(record) = existing_record {
-                if record.expiration > current_time {
-                    record.expiration
-                } else {
-                    current_time
-                }
-            } else {
-                current_time
-            };
-
-            // Add 30 days (in milliseconds)
-            // 30 * 24 * 60 * 60 * 1000 = 2,592,000,000
-            let new_expiration = start_time + 2_592_000_000;
-            
-            let subscription_record = SubscriptionRecord {
-                subscriber: caller,
-                creator,
-                tier_id: 0, // Default tier 0 for legacy subscriptions
-                expiration: new_expiration,
-            };
-
-            self.subscriptions.insert((caller, creator), &subscription_record);
-
-            Ok(())
+            // Default tier 0 for legacy subscriptions
+            self.settle_period(caller, creator, 0, payment)
         }
 
         /// Check if a user has an active subscription to a creator.
@@ -163,11 +178,28 @@ mod subscription_manager {
         // ===== NEW MULTI-TIER SUBSCRIPTION FUNCTIONS =====
 
         /// Create a new subscription tier as a creator.
+        ///
+        /// The tier's access level defaults to its tier ID, so later tiers rank higher;
+        /// use `create_tier_with_level` to set the level explicitly.
         #[ink(message)]
         pub fn create_tier(&mut self, name: String, price: Balance, benefits: Vec<String>) -> Result<u32, Error> {
             let caller = self.env().caller();
+            let tier_id = self.next_tier_id.get(caller).unwrap_or(1);
+            self.create_tier_with_level(name, price, benefits, tier_id)
+        }
+
+        /// Create a new subscription tier with an explicit access level (must be non-zero).
+        #[ink(message)]
+        pub fn create_tier_with_level(
+            &mut self,
+            name: String,
+            price: Balance,
+            benefits: Vec<String>,
+            level: u32,
+        ) -> Result<u32, Error> {
+            let caller = self.env().caller();
             
-            if name.is_empty() || price == 0 {
+            if name.is_empty() || price == 0 || level == 0 {
                 return Err(Error::InvalidTierData);
             }
 
@@ -180,6 +212,8 @@ mod subscription_manager {
                 price,
                 benefits,
                 creator: caller,
+                free_trial_days: 0,
+                level,
             };
 
             self.tiers.insert((caller, tier_id), &tier);
@@ -188,6 +222,28 @@ mod subscription_manager {
             Ok(tier_id)
         }
 
+        /// Create several tiers in one call, assigning consecutive tier IDs.
+        ///
+        /// The batch is atomic: every entry is validated before any tier is stored,
+        /// so either all tiers are created or none are.
+        #[ink(message)]
+        pub fn create_tiers_batch(&mut self, tiers: Vec<(String, Balance, Vec<String>)>) -> Result<Vec<u32>, Error> {
+            if tiers.len() > MAX_TIER_BATCH_SIZE {
+                return Err(Error::BatchSizeTooLarge);
+            }
+
+            if tiers.iter().any(|(name, price, _)| name.is_empty() || *price == 0) {
+                return Err(Error::InvalidTierData);
+            }
+
+            let mut tier_ids = Vec::new();
+            for (name, price, benefits) in tiers {
+                tier_ids.push(self.create_tier(name, price, benefits)?);
+            }
+
+            Ok(tier_ids)
+        }
+
         /// Subscribe to a specific tier of a creator.
         #[ink(message, payable)]
         pub fn subscribe_to_tier(&mut self, creator: AccountId, tier_id: u32) -> Result<(), Error> {
@@ -201,6 +257,17 @@ mod subscription_manager {
                 return Err(Error::InvalidPaymentAmount);
             }
 
+            self.settle_period(caller, creator, tier_id, payment)
+        }
+
+        /// Split a 30-day payment between treasury and creator and extend the subscription.
+        fn settle_period(
+            &mut self,
+            subscriber: AccountId,
+            creator: AccountId,
+            tier_id: u32,
+            payment: Balance,
+        ) -> Result<(), Error> {
             // Calculate fee (3%)
             let fee = payment.checked_mul(3).unwrap().checked_div(100).unwrap();
             let creator_share = payment.checked_sub(fee).unwrap();
@@ -208,6 +275,7 @@ mod subscription_manager {
             // Transfer shares
             if fee > 0 {
                 self.env().transfer(self.treasury, fee).map_err(|_| Error::TransferFailed)?;
+                self.total_fees_collected = self.total_fees_collected.saturating_add(fee);
             }
             if creator_share > 0 {
                 self.env().transfer(creator, creator_share).map_err(|_| Error::TransferFailed)?;
@@ -215,10 +283,10 @@ mod subscription_manager {
 
             // Update subscription
             let current_time = self.env().block_timestamp();
-            let existing_record = self.subscriptions.get((caller, creator));
+            let existing_record = self.subscriptions.get((subscriber, creator));
             
             // If expired or new, start from now. If active, extend from current expiration.
-            let start_time = if let Some(record) = existing_record {
+            let start_time = if let Some(record) = &existing_record {
                 if record.expiration > current_time {
                     record.expiration
                 } else {
@@ -229,20 +297,228 @@ mod subscription_manager {
             };
 
             // Add 30 days (in milliseconds)
+            // 30 * 24 * 60 * 60 * 1000 = 2,592,000,000
             let new_expiration = start_time + 2_592_000_000;
             
             let subscription_record = SubscriptionRecord {
-                subscriber: caller,
+                subscriber,
                 creator,
                 tier_id,
                 expiration: new_expiration,
             };
 
+            if existing_record.is_none() {
+                self.index_subscriber(subscriber, creator);
+            }
+            self.subscriptions.insert((subscriber, creator), &subscription_record);
+
+            // Log the payment, dropping the oldest entry once the history is full
+            let mut history = self.subscription_history.get((subscriber, creator)).unwrap_or_default();
+            if history.len() >= MAX_SUBSCRIPTION_HISTORY {
+                history.remove(0);
+            }
+            history.push((current_time, tier_id, payment));
+            self.subscription_history.insert((subscriber, creator), &history);
+
+            Ok(())
+        }
+
+        /// Get a subscriber's paid periods with a creator as (timestamp, tier_id, amount_paid).
+        ///
+        /// Covers subscriptions and renewals (not free trials), oldest first, up to the
+        /// last `MAX_SUBSCRIPTION_HISTORY` payments.
+        #[ink(message)]
+        pub fn get_subscription_history(&self, user: AccountId, creator: AccountId) -> Vec<(Timestamp, u32, Balance)> {
+            self.subscription_history.get((user, creator)).unwrap_or_default()
+        }
+
+        /// Record a first-time subscriber in the creator's subscriber index.
+        fn index_subscriber(&mut self, subscriber: AccountId, creator: AccountId) {
+            let count = self.creator_subscriber_count.get(creator).unwrap_or(0);
+            self.creator_subscribers.insert((creator, count), &subscriber);
+            self.creator_subscriber_count.insert(creator, &count.saturating_add(1));
+        }
+
+        /// Get a creator's reputation as (total subscribers ever, currently active subscribers).
+        ///
+        /// The active count covers subscribers `[start, start + limit)` in subscription
+        /// order, with `limit` capped at `MAX_REPUTATION_SCAN`; sum the pages for the full
+        /// count.
+        #[ink(message)]
+        pub fn get_creator_reputation(&self, creator: AccountId, start: u32, limit: u32) -> (u32, u32) {
+            let total = self.creator_subscriber_count.get(creator).unwrap_or(0);
+            let end = start.saturating_add(limit.min(MAX_REPUTATION_SCAN)).min(total);
+            let active = (start..end)
+                .filter_map(|index| self.creator_subscribers.get((creator, index)))
+                .filter(|subscriber| self.check_subscription(*subscriber, creator))
+                .count() as u32;
+
+            (total, active)
+        }
+
+        // ===== RENEWAL ESCROW =====
+
+        /// Prepay future renewals of a subscription to a creator.
+        ///
+        /// The deposit is held by the contract until it is spent by `renew_from_deposit`
+        /// or withdrawn by the subscriber. `max_price` is the most a single renewal may
+        /// charge, so a later price rise can't drain the deposit; each deposit replaces it.
+        #[ink(message, payable)]
+        pub fn deposit_for_renewal(&mut self, creator: AccountId, max_price: Balance) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let amount = self.env().transferred_value();
+
+            if amount == 0 {
+                return Err(Error::InvalidPaymentAmount);
+            }
+
+            let deposit = self.renewal_deposits.get((caller, creator)).unwrap_or(0);
+            self.renewal_deposits.insert((caller, creator), &deposit.checked_add(amount).ok_or(Error::InvalidPaymentAmount)?);
+            self.total_escrow = self.total_escrow.checked_add(amount).ok_or(Error::InvalidPaymentAmount)?;
+            self.renewal_max_prices.insert((caller, creator), &max_price);
+
+            Ok(())
+        }
+
+        /// Renew a subscription for another 30 days, paid from the subscriber's deposit.
+        ///
+        /// Anyone may trigger the renewal (e.g. a keeper bot), but only within
+        /// `RENEWAL_WINDOW` of expiry or after it. The subscriber keeps the tier of their
+        /// current record and pays that tier's current price, as long as it is within
+        /// the `max_price` they set when depositing.
+        #[ink(message)]
+        pub fn renew_from_deposit(&mut self, subscriber: AccountId, creator: AccountId) -> Result<(), Error> {
+            let record = self.subscriptions.get((subscriber, creator)).ok_or(Error::SubscriptionExpired)?;
+
+            let current_time = self.env().block_timestamp();
+            if current_time.saturating_add(RENEWAL_WINDOW) < record.expiration {
+                return Err(Error::RenewalTooEarly);
+            }
+
+            let price = if record.tier_id == 0 {
+                self.creator_prices.get(creator).ok_or(Error::CreatorNotRegistered)?
+            } else {
+                self.tiers.get((creator, record.tier_id)).ok_or(Error::TierNotFound)?.price
+            };
+            if price > self.renewal_max_prices.get((subscriber, creator)).unwrap_or(0) {
+                return Err(Error::PriceAboveLimit);
+            }
+
+            let deposit = self.renewal_deposits.get((subscriber, creator)).unwrap_or(0);
+            if deposit < price {
+                return Err(Error::InvalidPaymentAmount);
+            }
+
+            self.renewal_deposits.insert((subscriber, creator), &(deposit - price));
+            self.total_escrow = self.total_escrow.saturating_sub(price);
+
+            self.settle_period(subscriber, creator, record.tier_id, price)
+        }
+
+        /// Withdraw the caller's unspent renewal deposit for a creator.
+        #[ink(message)]
+        pub fn withdraw_renewal_deposit(&mut self, creator: AccountId) -> Result<Balance, Error> {
+            let caller = self.env().caller();
+            let deposit = self.renewal_deposits.get((caller, creator)).unwrap_or(0);
+
+            if deposit == 0 {
+                return Err(Error::InvalidPaymentAmount);
+            }
+
+            self.renewal_deposits.remove((caller, creator));
+            self.renewal_max_prices.remove((caller, creator));
+            self.total_escrow = self.total_escrow.saturating_sub(deposit);
+
+            self.env().transfer(caller, deposit).map_err(|_| Error::TransferFailed)?;
+
+            Ok(deposit)
+        }
+
+        /// Get the unspent renewal deposit of a subscriber for a creator.
+        #[ink(message)]
+        pub fn get_renewal_deposit(&self, user: AccountId, creator: AccountId) -> Balance {
+            self.renewal_deposits.get((user, creator)).unwrap_or(0)
+        }
+
+        /// Get the total value held in escrow (unspent renewal deposits).
+        #[ink(message)]
+        pub fn get_total_escrow(&self) -> Balance {
+            self.total_escrow
+        }
+
+        /// Get the contract's current balance, for comparison with `get_total_escrow`.
+        #[ink(message)]
+        pub fn get_contract_balance(&self) -> Balance {
+            self.env().balance()
+        }
+
+        /// Set the free trial length of a tier (only by the creator who owns it).
+        #[ink(message)]
+        pub fn set_tier_free_trial(&mut self, tier_id: u32, free_trial_days: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let mut tier = self.tiers.get((caller, tier_id)).ok_or(Error::TierNotFound)?;
+
+            if tier.creator != caller {
+                return Err(Error::Unauthorized);
+            }
+
+            tier.free_trial_days = free_trial_days;
+            self.tiers.insert((caller, tier_id), &tier);
+
+            Ok(())
+        }
+
+        /// Start a free trial of a tier without payment.
+        ///
+        /// Each subscriber can use one free trial per creator, and not while a paid
+        /// subscription to that creator is still active (it would swap in the trial's
+        /// tier). Once the trial ends, regular paid subscriptions apply.
+        #[ink(message)]
+        pub fn start_free_trial(&mut self, creator: AccountId, tier_id: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let tier = self.tiers.get((creator, tier_id)).ok_or(Error::TierNotFound)?;
+
+            if tier.free_trial_days == 0 {
+                return Err(Error::TrialNotAvailable);
+            }
+
+            if self.trial_used.get((caller, creator)).unwrap_or(false) {
+                return Err(Error::TrialAlreadyUsed);
+            }
+
+            let current_time = self.env().block_timestamp();
+            let existing_record = self.subscriptions.get((caller, creator));
+            if existing_record.as_ref().is_some_and(|record| record.expiration > current_time) {
+                return Err(Error::SubscriptionActive);
+            }
+
+            // Trial length in days (in milliseconds)
+            let trial_duration = u64::from(tier.free_trial_days) * 86_400_000;
+
+            let subscription_record = SubscriptionRecord {
+                subscriber: caller,
+                creator,
+                tier_id,
+                expiration: current_time + trial_duration,
+            };
+
+            if existing_record.is_none() {
+                self.index_subscriber(caller, creator);
+            }
             self.subscriptions.insert((caller, creator), &subscription_record);
+            self.trial_used.insert((caller, creator), &true);
 
             Ok(())
         }
 
+        /// Check if a user has already used their free trial with a creator.
+        #[ink(message)]
+        pub fn has_used_free_trial(&self, user: AccountId, creator: AccountId) -> bool {
+            self.trial_used.get((user, creator)).unwrap_or(false)
+        }
+
         /// Get all tiers for a creator (returns up to 10 tiers).
         #[ink(message)]
         pub fn get_creator_tiers(&self, creator: AccountId) -> Vec<Tier> {
@@ -279,13 +555,75 @@ mod subscription_manager {
             if let Some(record) = self.subscriptions.get((user, creator)) {
                 let current_time = self.env().block_timestamp();
                 if record.expiration > current_time {
-                    // User has access if their tier_id is >= required_tier_id
-                    return record.tier_id >= required_tier_id;
+                    // User has access if their tier's level is >= the required tier's level
+                    let subscribed = self.tier_level(creator, record.tier_id);
+                    let required = self.tier_level(creator, required_tier_id);
+                    if let (Some(subscribed), Some(required)) = (subscribed, required) {
+                        return subscribed >= required;
+                    }
                 }
             }
             false
         }
 
+        /// Get the benefits a subscriber has unlocked: those of every creator tier at or
+        /// below their active tier's level, deduplicated, lowest tier ID first.
+        #[ink(message)]
+        pub fn get_unlocked_benefits(&self, user: AccountId, creator: AccountId) -> Vec<String> {
+            let mut benefits: Vec<String> = Vec::new();
+
+            let Some(tier_id) = self.get_subscriber_tier(user, creator) else {
+                return benefits;
+            };
+            let Some(subscribed_level) = self.tier_level(creator, tier_id) else {
+                return benefits;
+            };
+
+            let max_tier_id = self.next_tier_id.get(creator).unwrap_or(1);
+            for id in 1..max_tier_id {
+                if let Some(tier) = self.tiers.get((creator, id)) {
+                    if tier.level <= subscribed_level {
+                        for benefit in tier.benefits {
+                            if !benefits.contains(&benefit) {
+                                benefits.push(benefit);
+                            }
+                        }
+                    }
+                }
+            }
+
+            benefits
+        }
+
+        /// Access level of a creator's tier; tier 0 is the legacy subscription at level 0.
+        fn tier_level(&self, creator: AccountId, tier_id: u32) -> Option<u32> {
+            if tier_id == 0 {
+                return Some(0);
+            }
+            self.tiers.get((creator, tier_id)).map(|tier| tier.level)
+        }
+
+        /// Set the access level of a tier (only by the creator who owns it, must be non-zero).
+        #[ink(message)]
+        pub fn set_tier_level(&mut self, tier_id: u32, level: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+
+            let mut tier = self.tiers.get((caller, tier_id)).ok_or(Error::TierNotFound)?;
+
+            if tier.creator != caller {
+                return Err(Error::Unauthorized);
+            }
+
+            if level == 0 {
+                return Err(Error::InvalidTierData);
+            }
+
+            tier.level = level;
+            self.tiers.insert((caller, tier_id), &tier);
+
+            Ok(())
+        }
+
         /// Update an existing tier (only by the creator who owns it).
         #[ink(message)]
         pub fn update_tier(&mut self, tier_id: u32, new_price: Balance, new_benefits: Vec<String>) -> Result<(), Error> {
@@ -330,6 +668,24 @@ mod subscription_manager {
         pub fn get_tier(&self, creator: AccountId, tier_id: u32) -> Option<Tier> {
             self.tiers.get((creator, tier_id))
         }
+
+        /// Get the admin account.
+        #[ink(message)]
+        pub fn get_admin(&self) -> AccountId {
+            self.admin
+        }
+
+        /// Get the treasury account that receives platform fees.
+        #[ink(message)]
+        pub fn get_treasury(&self) -> AccountId {
+            self.treasury
+        }
+
+        /// Get the total platform fees collected to date.
+        #[ink(message)]
+        pub fn get_treasury_earnings(&self) -> Balance {
+            self.total_fees_collected
+        }
     }
 
     #[cfg(test)]
@@ -424,5 +780,358 @@ mod subscription_manager {
             assert_eq!(tiers[1].name, "Silver");
             assert_eq!(tiers[2].name, "Gold");
         }
+
+        #[ink::test]
+        fn create_tiers_batch_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = SubscriptionManager::new(accounts.alice);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            let tier_ids = contract.create_tiers_batch(vec![
+                ("Bronze".to_string(), 100, vec!["Basic".to_string()]),
+                ("Silver".to_string(), 250, vec!["Medium".to_string()]),
+                ("Gold".to_string(), 500, vec!["Premium".to_string()]),
+                ("Platinum".to_string(), 1000, vec!["Everything".to_string()]),
+            ]).unwrap();
+
+            assert_eq!(tier_ids, vec![1, 2, 3, 4]);
+            assert_eq!(contract.get_creator_tiers(accounts.bob).len(), 4);
+            assert_eq!(contract.get_tier(accounts.bob, 4).unwrap().name, "Platinum");
+        }
+
+        #[ink::test]
+        fn create_tiers_batch_is_atomic() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = SubscriptionManager::new(accounts.alice);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            // An invalid entry rejects the whole batch
+            let result = contract.create_tiers_batch(vec![
+                ("Bronze".to_string(), 100, vec![]),
+                ("Free".to_string(), 0, vec![]),
+            ]);
+            assert_eq!(result, Err(Error::InvalidTierData));
+            assert!(contract.get_creator_tiers(accounts.bob).is_empty());
+
+            let too_many = (0..11).map(|i| (format!("Tier {}", i), 100, vec![])).collect();
+            assert_eq!(contract.create_tiers_batch(too_many), Err(Error::BatchSizeTooLarge));
+        }
+
+        #[ink::test]
+        fn free_trial_works_once() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = SubscriptionManager::new(accounts.alice);
+
+            // Bob creates a tier with a 7-day trial
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let tier_id = contract.create_tier("Gold".to_string(), 500, vec![]).unwrap();
+            assert_eq!(contract.set_tier_free_trial(tier_id, 7), Ok(()));
+
+            // Charlie starts the trial without paying
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.start_free_trial(accounts.bob, tier_id), Ok(()));
+            assert!(contract.check_tier_access(accounts.charlie, accounts.bob, tier_id));
+            assert!(contract.has_used_free_trial(accounts.charlie, accounts.bob));
+            assert_eq!(
+                contract.get_subscription_expiration(accounts.charlie, accounts.bob),
+                7 * 86_400_000
+            );
+
+            // The trial cannot be restarted, even after it expires
+            assert_eq!(contract.start_free_trial(accounts.bob, tier_id), Err(Error::TrialAlreadyUsed));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(8 * 86_400_000);
+            assert!(!contract.check_subscription(accounts.charlie, accounts.bob));
+            assert_eq!(contract.start_free_trial(accounts.bob, tier_id), Err(Error::TrialAlreadyUsed));
+        }
+
+        #[ink::test]
+        fn free_trial_requires_trial_days() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = SubscriptionManager::new(accounts.alice);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let tier_id = contract.create_tier("Gold".to_string(), 500, vec![]).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.start_free_trial(accounts.bob, tier_id), Err(Error::TrialNotAvailable));
+            assert!(!contract.check_subscription(accounts.charlie, accounts.bob));
+        }
+
+        #[ink::test]
+        fn free_trial_rejected_during_paid_subscription() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = SubscriptionManager::new(accounts.alice);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let basic = contract.create_tier("Basic".to_string(), 100, vec![]).unwrap();
+            let gold = contract.create_tier("Gold".to_string(), 500, vec![]).unwrap();
+            contract.set_tier_free_trial(gold, 7).unwrap();
+
+            // Charlie pays for Basic, so can't trial Gold on top of it
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            contract.subscribe_to_tier(accounts.bob, basic).unwrap();
+            assert_eq!(contract.start_free_trial(accounts.bob, gold), Err(Error::SubscriptionActive));
+            assert!(!contract.check_tier_access(accounts.charlie, accounts.bob, gold));
+            assert!(!contract.has_used_free_trial(accounts.charlie, accounts.bob));
+
+            // Once it lapses the trial is available
+            let expiration = contract.get_subscription_expiration(accounts.charlie, accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(expiration);
+            assert_eq!(contract.start_free_trial(accounts.bob, gold), Ok(()));
+            assert!(contract.check_tier_access(accounts.charlie, accounts.bob, gold));
+        }
+
+        #[ink::test]
+        fn renewal_escrow_tracks_deposits() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = SubscriptionManager::new(accounts.alice);
+
+            // Bob creates a tier, Charlie subscribes
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let tier_id = contract.create_tier("Gold".to_string(), 500, vec![]).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            contract.subscribe_to_tier(accounts.bob, tier_id).unwrap();
+
+            // Charlie prepays two renewals, Django prepays a legacy one
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            contract.deposit_for_renewal(accounts.bob, 500).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(300);
+            contract.deposit_for_renewal(accounts.bob, 300).unwrap();
+            assert_eq!(contract.get_total_escrow(), 1300);
+
+            // One renewal releases the tier price from escrow
+            let expiration = contract.get_subscription_expiration(accounts.charlie, accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(expiration - RENEWAL_WINDOW);
+            contract.renew_from_deposit(accounts.charlie, accounts.bob).unwrap();
+            assert_eq!(contract.get_renewal_deposit(accounts.charlie, accounts.bob), 500);
+            assert_eq!(contract.get_total_escrow(), 800);
+            assert_eq!(
+                contract.get_subscription_expiration(accounts.charlie, accounts.bob),
+                expiration + 2_592_000_000
+            );
+
+            // Withdrawing the rest releases it as well
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.withdraw_renewal_deposit(accounts.bob), Ok(500));
+            assert_eq!(contract.get_total_escrow(), 300);
+
+            let callee = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+            assert_eq!(
+                Ok(contract.get_contract_balance()),
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(callee)
+            );
+        }
+
+        #[ink::test]
+        fn renewal_from_deposit_is_limited_by_time_and_price() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = SubscriptionManager::new(accounts.alice);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let tier_id = contract.create_tier("Gold".to_string(), 500, vec![]).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            contract.subscribe_to_tier(accounts.bob, tier_id).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(5000);
+            contract.deposit_for_renewal(accounts.bob, 500).unwrap();
+
+            // Nobody can renew long before expiry
+            let expiration = contract.get_subscription_expiration(accounts.charlie, accounts.bob);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(expiration - RENEWAL_WINDOW - 1);
+            assert_eq!(contract.renew_from_deposit(accounts.charlie, accounts.bob), Err(Error::RenewalTooEarly));
+
+            // A price rise above what Charlie agreed to can't be charged
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.update_tier(tier_id, 5000, vec![]).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(expiration - RENEWAL_WINDOW);
+            assert_eq!(contract.renew_from_deposit(accounts.charlie, accounts.bob), Err(Error::PriceAboveLimit));
+            assert_eq!(contract.get_renewal_deposit(accounts.charlie, accounts.bob), 5000);
+
+            // Back within the limit, the renewal goes through
+            contract.update_tier(tier_id, 400, vec![]).unwrap();
+            assert_eq!(contract.renew_from_deposit(accounts.charlie, accounts.bob), Ok(()));
+            assert_eq!(contract.get_renewal_deposit(accounts.charlie, accounts.bob), 4600);
+        }
+
+        #[ink::test]
+        fn admin_and_treasury_getters_work() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let contract = SubscriptionManager::new(accounts.charlie);
+
+            assert_eq!(contract.get_admin(), accounts.bob);
+            assert_eq!(contract.get_treasury(), accounts.charlie);
+        }
+
+        #[ink::test]
+        fn treasury_earnings_sum_fees() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = SubscriptionManager::new(accounts.alice);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.register_creator(1000).unwrap();
+            let tier_id = contract.create_tier("Gold".to_string(), 500, vec![]).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            contract.subscribe(accounts.bob).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            contract.subscribe_to_tier(accounts.bob, tier_id).unwrap();
+
+            // 3% of each payment
+            assert_eq!(contract.get_treasury_earnings(), 30 + 15);
+        }
+
+        #[ink::test]
+        fn creator_reputation_counts_subscribers() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = SubscriptionManager::new(accounts.alice);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.register_creator(1000).unwrap();
+            let tier_id = contract.create_tier("Gold".to_string(), 500, vec![]).unwrap();
+            contract.set_tier_free_trial(tier_id, 7).unwrap();
+
+            // Charlie subscribes early and lets it lapse
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            contract.subscribe(accounts.bob).unwrap();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3_000_000_000);
+
+            // Django subscribes twice (counted once), Eve takes a trial
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            contract.subscribe_to_tier(accounts.bob, tier_id).unwrap();
+            contract.subscribe_to_tier(accounts.bob, tier_id).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            contract.start_free_trial(accounts.bob, tier_id).unwrap();
+
+            assert_eq!(contract.get_creator_reputation(accounts.bob, 0, 10), (3, 2));
+            assert_eq!(contract.get_creator_reputation(accounts.charlie, 0, 10), (0, 0));
+
+            // Pages split the active count; Charlie (index 0) has lapsed
+            assert_eq!(contract.get_creator_reputation(accounts.bob, 0, 1), (3, 0));
+            assert_eq!(contract.get_creator_reputation(accounts.bob, 1, 10), (3, 2));
+            assert_eq!(contract.get_creator_reputation(accounts.bob, 3, 10), (3, 0));
+        }
+
+        #[ink::test]
+        fn tier_access_uses_levels_not_ids() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = SubscriptionManager::new(accounts.alice);
+
+            // Bob creates the premium tier first, then a cheaper one
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let gold = contract.create_tier_with_level("Gold".to_string(), 500, vec![], 3).unwrap();
+            let bronze = contract.create_tier_with_level("Bronze".to_string(), 100, vec![], 1).unwrap();
+            assert!(bronze > gold);
+            assert_eq!(
+                contract.create_tier_with_level("Free".to_string(), 100, vec![], 0),
+                Err(Error::InvalidTierData)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie, 10_000_000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            contract.subscribe_to_tier(accounts.bob, bronze).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.django, 10_000_000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            contract.subscribe_to_tier(accounts.bob, gold).unwrap();
+
+            // The later-created Bronze tier doesn't grant Gold access
+            assert!(contract.check_tier_access(accounts.charlie, accounts.bob, bronze));
+            assert!(!contract.check_tier_access(accounts.charlie, accounts.bob, gold));
+            assert!(contract.check_tier_access(accounts.django, accounts.bob, bronze));
+            assert!(contract.check_tier_access(accounts.django, accounts.bob, gold));
+
+            // Re-ranking Bronze above Gold flips it
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_tier_level(bronze, 0), Err(Error::InvalidTierData));
+            contract.set_tier_level(bronze, 5).unwrap();
+            assert!(contract.check_tier_access(accounts.charlie, accounts.bob, gold));
+            assert!(!contract.check_tier_access(accounts.django, accounts.bob, bronze));
+        }
+
+        #[ink::test]
+        fn subscription_history_logs_payments() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = SubscriptionManager::new(accounts.alice);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let silver = contract.create_tier("Silver".to_string(), 250, vec![]).unwrap();
+            let gold = contract.create_tier("Gold".to_string(), 500, vec![]).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(250);
+            contract.subscribe_to_tier(accounts.bob, silver).unwrap();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2_000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            contract.subscribe_to_tier(accounts.bob, gold).unwrap();
+
+            assert_eq!(
+                contract.get_subscription_history(accounts.charlie, accounts.bob),
+                vec![(1_000, silver, 250), (2_000, gold, 500)]
+            );
+            assert!(contract.get_subscription_history(accounts.django, accounts.bob).is_empty());
+
+            // The history is capped, keeping the latest payments
+            for _ in 0..MAX_SUBSCRIPTION_HISTORY {
+                contract.subscribe_to_tier(accounts.bob, gold).unwrap();
+            }
+            let history = contract.get_subscription_history(accounts.charlie, accounts.bob);
+            assert_eq!(history.len(), MAX_SUBSCRIPTION_HISTORY);
+            assert!(history.iter().all(|(_, tier_id, amount)| *tier_id == gold && *amount == 500));
+        }
+
+        #[ink::test]
+        fn unlocked_benefits_merge_lower_tiers() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = SubscriptionManager::new(accounts.alice);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let gold = contract.create_tier_with_level(
+                "Gold".to_string(), 500, vec!["Discord".to_string(), "1:1 call".to_string()], 3,
+            ).unwrap();
+            contract.create_tier_with_level(
+                "Bronze".to_string(), 100, vec!["Newsletter".to_string()], 1,
+            ).unwrap();
+            let silver = contract.create_tier_with_level(
+                "Silver".to_string(), 250, vec!["Newsletter".to_string(), "Discord".to_string()], 2,
+            ).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(contract.get_unlocked_benefits(accounts.charlie, accounts.bob).is_empty());
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie, 10_000_000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            contract.subscribe_to_tier(accounts.bob, gold).unwrap();
+            assert_eq!(
+                contract.get_unlocked_benefits(accounts.charlie, accounts.bob),
+                vec!["Discord".to_string(), "1:1 call".to_string(), "Newsletter".to_string()]
+            );
+
+            // Silver doesn't unlock Gold's benefits
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.django, 10_000_000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(250);
+            contract.subscribe_to_tier(accounts.bob, silver).unwrap();
+            assert_eq!(
+                contract.get_unlocked_benefits(accounts.django, accounts.bob),
+                vec!["Newsletter".to_string(), "Discord".to_string()]
+            );
+        }
     }
 }